@@ -0,0 +1,159 @@
+use actix_web::http::header::RETRY_AFTER;
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::infrastructure::dataset::errors::dataset_errors::DatasetError;
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::ml::errors::ml_errors::MLError;
+
+/// Ошибка JSON API
+///
+/// Возвращается клиенту как `{"success": false, "error": {"code": ..., "message": ...}}`
+/// с HTTP статусом, соответствующим причине.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error(transparent)]
+    Explorer(#[from] ExplorerError),
+    #[error(transparent)]
+    Ml(#[from] MLError),
+    #[error("Dataset error: {0}")]
+    Dataset(#[from] std::io::Error),
+    #[error("Invalid dataset: {0}")]
+    Schema(#[from] SchemaError),
+    #[error(transparent)]
+    Folder(#[from] DatasetError),
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+/// Тело ошибки
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiErrorBody {
+    /// Машиночитаемый код ошибки, например `NOT_FOUND`
+    pub code: String,
+    pub message: String,
+}
+
+/// Ответ с ошибкой
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiErrorResponse {
+    pub success: bool,
+    pub error: ApiErrorBody,
+}
+
+impl ApiError {
+    /// Машиночитаемый код ошибки
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "NOT_FOUND",
+            ApiError::InvalidInput(_) => "INVALID_INPUT",
+            ApiError::Explorer(error) => match error {
+                ExplorerError::NotFound(_) => "NOT_FOUND",
+                ExplorerError::InvalidArgument(_) => "INVALID_INPUT",
+                ExplorerError::MissingInputValue => "MISSING_INPUT_VALUE",
+                ExplorerError::RateLimited { .. } => "RATE_LIMITED",
+                ExplorerError::UpstreamUnavailable(_) => "UPSTREAM_UNAVAILABLE",
+                ExplorerError::Unsupported { .. } => "NOT_SUPPORTED",
+                ExplorerError::ClientNotInitialized | ExplorerError::ClientBuildError(_) => "INTERNAL_ERROR",
+                _ => "EXPLORER_ERROR",
+            },
+            ApiError::Ml(MLError::Http(_)) => "ML_UNAVAILABLE",
+            ApiError::Ml(MLError::ClientBuildError(_)) => "INTERNAL_ERROR",
+            ApiError::Ml(_) => "ML_SERVICE_ERROR",
+            ApiError::Dataset(error) if error.kind() == std::io::ErrorKind::NotFound => "DATASET_NOT_FOUND",
+            ApiError::Dataset(_) => "DATASET_ERROR",
+            ApiError::Schema(error) if error.is_not_found() => "DATASET_NOT_FOUND",
+            ApiError::Schema(SchemaError::Io { .. }) => "DATASET_ERROR",
+            ApiError::Schema(_) => "DATASET_INVALID",
+            ApiError::Folder(error) => match error {
+                DatasetError::InvalidName(_) => "INVALID_INPUT",
+                DatasetError::NotFound(_) => "DATASET_NOT_FOUND",
+                DatasetError::AlreadyExists(_) => "DATASET_EXISTS",
+                DatasetError::Archive(_) | DatasetError::UnexpectedFile(_) => "INVALID_ARCHIVE",
+                DatasetError::NoDatasetFiles | DatasetError::Schema(_) => "DATASET_INVALID",
+                DatasetError::TooLarge { .. } => "PAYLOAD_TOO_LARGE",
+                DatasetError::Io(_) => "DATASET_ERROR",
+            },
+            ApiError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self.code() {
+            "NOT_FOUND" | "DATASET_NOT_FOUND" => StatusCode::NOT_FOUND,
+            "INVALID_INPUT" | "INVALID_ARCHIVE" => StatusCode::BAD_REQUEST,
+            "DATASET_EXISTS" => StatusCode::CONFLICT,
+            "PAYLOAD_TOO_LARGE" => StatusCode::PAYLOAD_TOO_LARGE,
+            "MISSING_INPUT_VALUE" | "DATASET_INVALID" => StatusCode::UNPROCESSABLE_ENTITY,
+            "RATE_LIMITED" => StatusCode::TOO_MANY_REQUESTS,
+            "UPSTREAM_UNAVAILABLE" | "ML_UNAVAILABLE" => StatusCode::SERVICE_UNAVAILABLE,
+            "NOT_SUPPORTED" => StatusCode::NOT_IMPLEMENTED,
+            "EXPLORER_ERROR" | "ML_SERVICE_ERROR" => StatusCode::BAD_GATEWAY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        if let ApiError::Explorer(ExplorerError::RateLimited { retry_after: Some(retry_after) }) = self {
+            response.insert_header((RETRY_AFTER, retry_after.as_secs().max(1).to_string()));
+        }
+        response.json(ApiErrorResponse {
+            success: false,
+            error: ApiErrorBody {
+                code: self.code().to_string(),
+                message: self.to_string(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_status_codes() {
+        let cases = [
+            (ApiError::NotFound("tx".to_string()), StatusCode::NOT_FOUND),
+            (ApiError::InvalidInput("txid".to_string()), StatusCode::BAD_REQUEST),
+            (ExplorerError::NotFound("/tx/00".to_string()).into(), StatusCode::NOT_FOUND),
+            (ExplorerError::MissingInputValue.into(), StatusCode::UNPROCESSABLE_ENTITY),
+            (ExplorerError::UpstreamUnavailable("503".to_string()).into(), StatusCode::SERVICE_UNAVAILABLE),
+            (ExplorerError::InvalidResponse("bad".to_string()).into(), StatusCode::BAD_GATEWAY),
+            (std::io::Error::from(std::io::ErrorKind::NotFound).into(), StatusCode::NOT_FOUND),
+            (
+                SchemaError::FieldCount { file: "f.csv".to_string(), line: 2, expected: 2, found: 1 }.into(),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                MLError::InvalidResponse { status: 500, body: "oops".to_string() }.into(),
+                StatusCode::BAD_GATEWAY,
+            ),
+            (DatasetError::AlreadyExists("set".to_string()).into(), StatusCode::CONFLICT),
+            (ApiError::Internal("boom".to_string()), StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+        for (error, status) in cases {
+            assert_eq!(error.status_code(), status, "{}", error.code());
+        }
+    }
+
+    #[test]
+    fn test_rate_limited_response_has_retry_after() {
+        let error = ApiError::from(ExplorerError::RateLimited { retry_after: Some(Duration::from_secs(7)) });
+        let response = error.error_response();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(RETRY_AFTER).and_then(|v| v.to_str().ok()), Some("7"));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod response;
+pub mod state;
+pub mod v1;
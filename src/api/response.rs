@@ -0,0 +1,21 @@
+use actix_web::HttpResponse;
+use serde::{Deserialize, Serialize};
+
+/// Успешный ответ JSON API: `{"success": true, "data": ...}`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: T,
+}
+
+impl<T: Serialize> ApiResponse<T> {
+    /// Формирует ответ `200 OK` с данными
+    pub fn ok(data: T) -> HttpResponse {
+        HttpResponse::Ok().json(ApiResponse { success: true, data })
+    }
+
+    /// Формирует ответ `201 Created` с данными созданного ресурса
+    pub fn created(data: T) -> HttpResponse {
+        HttpResponse::Created().json(ApiResponse { success: true, data })
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::application::services::graph::graph_store::GraphStore;
+use crate::infrastructure::config::Config;
+use crate::infrastructure::dataset::dataset_store::DatasetStore;
+use crate::services::explorer::explorer_client::ExplorerClient;
+use crate::services::explorer::explorer_registry::ExplorerRegistry;
+use crate::services::ml::ml_client::MLClient;
+
+/// Общее состояние веб-сервера
+pub struct AppState {
+    /// Конфигурация приложения
+    pub config: Config,
+    /// Именованные клиенты explorer'а, созданные по конфигурации
+    pub explorers: ExplorerRegistry,
+    /// Клиент ML сервиса
+    pub ml: MLClient,
+    /// Индексы CSV файлов папок с данными
+    pub datasets: DatasetStore,
+    /// Графы папок с данными, разделяются с задачами пула блокирующих операций
+    pub graphs: Arc<GraphStore>,
+}
+
+impl AppState {
+    /// Возвращает клиент explorer'а по имени из запроса или клиент по умолчанию
+    ///
+    /// # Returns
+    /// `Result<&ExplorerClient, ApiError>` - клиент или `InvalidInput` для неизвестного имени
+    pub fn explorer(&self, name: Option<&str>) -> Result<&ExplorerClient, ApiError> {
+        self.explorers.get_or_default(name).map_err(|_| {
+            ApiError::InvalidInput(format!(
+                "unknown explorer: {}",
+                name.unwrap_or(&self.config.default_explorer)
+            ))
+        })
+    }
+}
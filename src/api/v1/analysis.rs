@@ -0,0 +1,28 @@
+use actix_web::{get, web, HttpResponse};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::explorer::ExplorerQuery;
+use crate::api::v1::validation::parse_txid;
+use crate::application::services::analysis::transaction_analysis::{
+    collect_evidence, request_verdict, TransactionAnalysis,
+};
+
+/// `GET /api/v1/analyze/tx/{txid}` - признаки транзакции и вердикт ML сервиса
+///
+/// В отличие от HTML страницы, недоступность ML сервиса возвращается как ошибка.
+#[get("/analyze/tx/{txid}")]
+pub async fn analyze_transaction(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ExplorerQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let txid = parse_txid(&path)?;
+    let client = state.explorer(query.explorer.as_deref())?;
+
+    let evidence = collect_evidence(client, &txid).await?;
+    let verdict = request_verdict(&state.ml, &evidence).await?;
+
+    Ok(ApiResponse::ok(TransactionAnalysis { evidence, verdict }))
+}
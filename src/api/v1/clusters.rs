@@ -0,0 +1,137 @@
+use std::path::Path;
+
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::resolve_folder;
+use crate::application::services::clustering::address_clustering::{
+    cluster_addresses, AddressClusters, ClusteringOptions,
+};
+
+/// Параметры кластеризации в запросе
+///
+/// Незаданные параметры кластеризации берутся из конфигурации (`CLUSTER_*`).
+#[derive(Debug, Deserialize)]
+pub struct ClusterQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// Исключать похожие на CoinJoin транзакции
+    pub exclude_coinjoin: Option<bool>,
+    /// Максимальное число адресов входов транзакции, объединяющей адреса
+    pub max_inputs: Option<usize>,
+    /// Добавлять в кластер отправителя выход-сдачу
+    pub change: Option<bool>,
+    /// Минимальный размер кластера в списке (по умолчанию 2)
+    pub min_size: Option<usize>,
+    /// Максимальное число кластеров в списке
+    pub limit: Option<usize>,
+}
+
+/// Кластер в списке, без адресов
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterSummary {
+    pub id: usize,
+    pub size: usize,
+}
+
+/// Список кластеров папки с данными
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClusterList {
+    pub folder: String,
+    /// Число всех кластеров, включая одноэлементные
+    pub total_clusters: usize,
+    /// Число адресов в графе
+    pub total_addresses: usize,
+    /// Транзакции, исключенные фильтром CoinJoin
+    pub excluded_transactions: usize,
+    pub clusters: Vec<ClusterSummary>,
+}
+
+/// Кластеризует адреса папки в пуле блокирующих задач
+async fn load_clusters(
+    state: &web::Data<AppState>,
+    query: &ClusterQuery,
+) -> Result<(String, AddressClusters), ApiError> {
+    let folder = resolve_folder(state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(&folder);
+
+    let mut options = ClusteringOptions::from_config(&state.config);
+    if let Some(exclude_coinjoin) = query.exclude_coinjoin {
+        options.exclude_coinjoin = exclude_coinjoin;
+    }
+    if let Some(max_inputs) = query.max_inputs {
+        options.max_input_addresses = Some(max_inputs);
+    }
+    if let Some(change) = query.change {
+        options.use_change = change;
+    }
+
+    let graphs = state.graphs.clone();
+    let clusters = web::block(move || {
+        graphs.load(&path).map(|graph| cluster_addresses(&graph, &options))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok((folder, clusters))
+}
+
+/// `GET /api/v1/clusters` - кластеры адресов общего владельца, от крупных к мелким
+#[get("/clusters")]
+pub async fn list_clusters(
+    state: web::Data<AppState>,
+    query: web::Query<ClusterQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (folder, clusters) = load_clusters(&state, &query).await?;
+    let min_size = query.min_size.unwrap_or(2);
+
+    let summaries = clusters
+        .clusters
+        .iter()
+        .take_while(|cluster| cluster.size >= min_size)
+        .take(query.limit.unwrap_or(usize::MAX))
+        .map(|cluster| ClusterSummary { id: cluster.id, size: cluster.size })
+        .collect();
+
+    Ok(ApiResponse::ok(ClusterList {
+        folder,
+        total_clusters: clusters.len(),
+        total_addresses: clusters.clusters.iter().map(|cluster| cluster.size).sum(),
+        excluded_transactions: clusters.excluded_transactions.len(),
+        clusters: summaries,
+    }))
+}
+
+/// `GET /api/v1/clusters/{id}` - адреса кластера
+#[get("/clusters/{id}")]
+pub async fn get_cluster(
+    state: web::Data<AppState>,
+    path: web::Path<usize>,
+    query: web::Query<ClusterQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let id = path.into_inner();
+    let (folder, mut clusters) = load_clusters(&state, &query).await?;
+
+    if id >= clusters.len() {
+        return Err(ApiError::NotFound(format!("cluster {} not found in {}", id, folder)));
+    }
+    Ok(ApiResponse::ok(clusters.clusters.swap_remove(id)))
+}
+
+/// `GET /api/v1/clusters/address/{address}` - кластер, в который входит адрес
+#[get("/clusters/address/{address}")]
+pub async fn address_cluster(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ClusterQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let address = path.into_inner();
+    let (folder, clusters) = load_clusters(&state, &query).await?;
+
+    let cluster = clusters
+        .cluster_of(&address)
+        .ok_or_else(|| ApiError::NotFound(format!("address {} not found in {}", address, folder)))?;
+    Ok(ApiResponse::ok(cluster))
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::resolve_folder;
+use crate::application::services::clustering::address_clustering::ClusteringOptions;
+use crate::application::services::graph::graph_data::{address_graph_data, style_nodes, NodeColoring, NodeSizing};
+use crate::infrastructure::constants::{TX_CLASSES_FILE, TX_FEATURES_FILE, WALLET_FEATURES_FILE};
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+use crate::infrastructure::dataset::schema::Class;
+
+/// Параметры запросов к данным из папки
+#[derive(Debug, Deserialize)]
+pub struct DatasetQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+}
+
+/// Параметры запроса графа адресов
+#[derive(Debug, Deserialize)]
+pub struct GraphQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// Раскраска узлов: `class` (по умолчанию), `cluster`, `component`, `community` или `risk`
+    #[serde(default)]
+    pub color_by: NodeColoring,
+    /// Размер узлов: `uniform` (по умолчанию), `degree`, `betweenness` или `pagerank`
+    #[serde(default)]
+    pub size_by: NodeSizing,
+}
+
+/// Запись датасета
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatasetRecord {
+    /// Ключ записи (ID транзакции или адрес)
+    pub key: String,
+    /// Класс записи, если он указан в данных
+    pub class: Option<Class>,
+    /// Значения колонок записи
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Ищет запись в индексе файла папки в пуле блокирующих задач
+async fn lookup(state: &web::Data<AppState>, folder: &str, file: &'static str, key: &str) -> Result<Option<BTreeMap<String, String>>, ApiError> {
+    let path = Path::new(&state.config.data_dir).join(folder).join(file);
+    let key = key.to_string();
+    let state = state.clone();
+    let record = web::block(move || state.datasets.lookup(&path, &key))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(record.map(|record| record.into_iter().collect()))
+}
+
+/// `GET /api/v1/tx/{tx_id}` - признаки и класс транзакции из папки с данными
+#[get("/tx/{tx_id}")]
+pub async fn dataset_transaction(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<DatasetQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let tx_id = path.into_inner();
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+
+    let fields = lookup(&state, &folder, TX_FEATURES_FILE, &tx_id)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("transaction {} not found in {}", tx_id, folder)))?;
+    let class = lookup(&state, &folder, TX_CLASSES_FILE, &tx_id)
+        .await?
+        .and_then(|record| record.get("class").and_then(|class| class.parse().ok()));
+
+    Ok(ApiResponse::ok(DatasetRecord { key: tx_id, class, fields }))
+}
+
+/// `GET /api/v1/address/{address}` - признаки и класс адреса из папки с данными
+#[get("/address/{address}")]
+pub async fn dataset_address(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<DatasetQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let address = path.into_inner();
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+
+    let fields = lookup(&state, &folder, WALLET_FEATURES_FILE, &address)
+        .await?
+        .ok_or_else(|| ApiError::NotFound(format!("address {} not found in {}", address, folder)))?;
+    let class = fields.get("class").and_then(|class| class.parse().ok());
+
+    Ok(ApiResponse::ok(DatasetRecord { key: address, class, fields }))
+}
+
+/// `GET /api/v1/graph` - граф адресов папки с данными, узлы раскрашены по классу,
+/// кластеру, компоненте или сообществу и масштабированы по метрике
+#[get("/graph")]
+pub async fn dataset_graph(
+    state: web::Data<AppState>,
+    query: web::Query<GraphQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(folder);
+    let options = ClusteringOptions::from_config(&state.config);
+    let (color_by, size_by) = (query.color_by, query.size_by);
+
+    let graphs = state.graphs.clone();
+
+    let graph = web::block(move || {
+        let graph = graphs.load(&path)?;
+        let mut data = address_graph_data(&graph);
+        style_nodes(&graph, &mut data, color_by, size_by, &options);
+        Ok::<_, SchemaError>(data)
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(ApiResponse::ok(graph))
+}
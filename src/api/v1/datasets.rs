@@ -0,0 +1,113 @@
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use actix_multipart::form::tempfile::TempFile;
+use actix_multipart::form::text::Text;
+use actix_multipart::form::MultipartForm;
+use actix_web::{delete, get, post, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::infrastructure::constants::MAX_DATASET_EXTRACTED_BYTES;
+use crate::infrastructure::dataset::dataset_manager::{
+    delete_folder, folder_info, import_archive, list_folders, validate_folder, FileReport,
+};
+
+/// Форма загрузки папки с данными (`multipart/form-data`)
+#[derive(MultipartForm)]
+pub struct DatasetUpload {
+    /// Имя новой папки
+    pub name: Text<String>,
+    /// Zip архив с CSV файлами датасета
+    pub file: TempFile,
+}
+
+/// Результат проверки схемы файлов папки
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatasetValidation {
+    pub name: String,
+    pub files: Vec<FileReport>,
+}
+
+fn data_dir(state: &AppState) -> PathBuf {
+    PathBuf::from(&state.config.data_dir)
+}
+
+/// `GET /api/v1/datasets` - папки с данными и их метаданные
+#[get("/datasets")]
+pub async fn list_datasets(state: web::Data<AppState>) -> Result<HttpResponse, ApiError> {
+    let data_dir = data_dir(&state);
+    let folders = web::block(move || list_folders(&data_dir))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(ApiResponse::ok(folders))
+}
+
+/// `GET /api/v1/datasets/{name}` - метаданные папки с данными
+#[get("/datasets/{name}")]
+pub async fn get_dataset(state: web::Data<AppState>, path: web::Path<String>) -> Result<HttpResponse, ApiError> {
+    let data_dir = data_dir(&state);
+    let folder = web::block(move || folder_info(&data_dir, &path))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(ApiResponse::ok(folder))
+}
+
+/// `POST /api/v1/datasets` - загрузка zip архива в новую папку
+///
+/// Поля формы: `name` - имя папки, `file` - архив. Папка создается только
+/// если все файлы архива прошли проверку схемы.
+#[post("/datasets")]
+pub async fn upload_dataset(
+    state: web::Data<AppState>,
+    form: MultipartForm<DatasetUpload>,
+) -> Result<HttpResponse, ApiError> {
+    let DatasetUpload { name, file } = form.into_inner();
+    let name = name.into_inner();
+    let folder_path = data_dir(&state).join(&name);
+    let data_dir = data_dir(&state);
+
+    let folder = web::block(move || {
+        let archive = BufReader::new(file.file.reopen()?);
+        import_archive(&data_dir, &name, archive, MAX_DATASET_EXTRACTED_BYTES)
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    // Индексы и граф удаленной ранее папки с тем же именем больше не действительны
+    state.datasets.forget_folder(&folder_path);
+    state.graphs.forget_folder(&folder_path);
+    Ok(ApiResponse::created(folder))
+}
+
+/// `POST /api/v1/datasets/{name}/validate` - проверка схемы файлов папки
+#[post("/datasets/{name}/validate")]
+pub async fn validate_dataset(state: web::Data<AppState>, path: web::Path<String>) -> Result<HttpResponse, ApiError> {
+    let data_dir = data_dir(&state);
+    let name = path.into_inner();
+    let files = web::block(move || {
+        folder_info(&data_dir, &name)?;
+        validate_folder(&data_dir.join(&name)).map(|files| DatasetValidation { name, files })
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(ApiResponse::ok(files))
+}
+
+/// `DELETE /api/v1/datasets/{name}` - удаление папки с данными
+#[delete("/datasets/{name}")]
+pub async fn delete_dataset(state: web::Data<AppState>, path: web::Path<String>) -> Result<HttpResponse, ApiError> {
+    let data_dir = data_dir(&state);
+    let folder_path = data_dir.join(path.as_str());
+
+    let folder = web::block(move || {
+        let folder = folder_info(&data_dir, &path)?;
+        delete_folder(&data_dir, &path).map(|_| folder)
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    state.datasets.forget_folder(&folder_path);
+    state.graphs.forget_folder(&folder_path);
+    Ok(ApiResponse::ok(folder))
+}
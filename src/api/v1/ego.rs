@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::resolve_folder;
+use crate::application::services::graph::ego_network::{ego_network, EgoNetwork, EgoOptions};
+use crate::application::services::graph::transaction_graph::NodeId;
+use crate::infrastructure::constants::{DEFAULT_EGO_HUB_DEGREE, MAX_EGO_RADIUS};
+
+/// Параметры запроса окрестности узла
+#[derive(Debug, Deserialize)]
+pub struct EgoQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// Радиус в ребрах (по умолчанию `DEFAULT_EGO_RADIUS`)
+    pub radius: Option<usize>,
+    /// Максимальное число узлов (по умолчанию `DEFAULT_EGO_MAX_NODES`)
+    pub max_nodes: Option<usize>,
+    /// Максимальное число ребер (по умолчанию `DEFAULT_EGO_MAX_EDGES`)
+    pub max_edges: Option<usize>,
+    /// Не раскрывать узлы с большой степенью
+    #[serde(default)]
+    pub collapse_hubs: bool,
+    /// Степень хаба (по умолчанию `DEFAULT_EGO_HUB_DEGREE`)
+    pub hub_degree: Option<usize>,
+}
+
+impl EgoQuery {
+    /// Параметры окрестности с проверкой радиуса и ограничений
+    pub fn options(&self) -> Result<EgoOptions, ApiError> {
+        let defaults = EgoOptions::default();
+        let radius = self.radius.unwrap_or(defaults.radius);
+        if radius > MAX_EGO_RADIUS {
+            return Err(ApiError::InvalidInput(format!("radius must be at most {}, got {}", MAX_EGO_RADIUS, radius)));
+        }
+        let max_nodes = self.max_nodes.unwrap_or(defaults.max_nodes);
+        let max_edges = self.max_edges.unwrap_or(defaults.max_edges);
+        let hub_degree = self.collapse_hubs.then(|| self.hub_degree.unwrap_or(DEFAULT_EGO_HUB_DEGREE));
+        if max_nodes == 0 || hub_degree == Some(0) {
+            return Err(ApiError::InvalidInput("max_nodes and hub_degree must be positive".to_string()));
+        }
+        Ok(EgoOptions { radius, max_nodes, max_edges, hub_degree })
+    }
+}
+
+/// Окрестность узла графа папки или `NotFound`, если узла нет в графе
+///
+/// # Arguments
+/// * `state` - Состояние приложения
+/// * `query` - Папка, радиус и ограничения окрестности
+/// * `center` - Центральный узел
+///
+/// # Returns
+/// `Result<EgoNetwork, ApiError>` - окрестность или ошибка
+pub async fn load_ego_network(
+    state: &web::Data<AppState>,
+    query: &EgoQuery,
+    center: NodeId,
+) -> Result<EgoNetwork, ApiError> {
+    let options = query.options()?;
+    let folder = resolve_folder(state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(&folder);
+    let node = center.clone();
+    let graphs = state.graphs.clone();
+
+    let ego = web::block(move || graphs.load(&path).map(|graph| ego_network(&graph, &node, &options)))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))??;
+    ego.ok_or_else(|| ApiError::NotFound(format!("{} not found in {}", center, folder)))
+}
+
+/// `GET /api/v1/ego/address/{address}` - окрестность адреса
+#[get("/ego/address/{address}")]
+pub async fn address_ego(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<EgoQuery>,
+) -> Result<HttpResponse, ApiError> {
+    Ok(ApiResponse::ok(load_ego_network(&state, &query, NodeId::address(path.into_inner())).await?))
+}
+
+/// `GET /api/v1/ego/tx/{tx_id}` - окрестность транзакции
+#[get("/ego/tx/{tx_id}")]
+pub async fn transaction_ego(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<EgoQuery>,
+) -> Result<HttpResponse, ApiError> {
+    Ok(ApiResponse::ok(load_ego_network(&state, &query, NodeId::transaction(path.into_inner())).await?))
+}
@@ -0,0 +1,50 @@
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::{parse_address, parse_txid};
+
+/// Параметры запросов к explorer'у
+#[derive(Debug, Deserialize)]
+pub struct ExplorerQuery {
+    /// Имя клиента explorer'а (по умолчанию `DEFAULT_EXPLORER`)
+    pub explorer: Option<String>,
+}
+
+/// `GET /api/v1/explorer/tx/{txid}` - транзакция из explorer'а
+#[get("/explorer/tx/{txid}")]
+pub async fn explorer_transaction(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ExplorerQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let txid = parse_txid(&path)?;
+    let client = state.explorer(query.explorer.as_deref())?;
+    Ok(ApiResponse::ok(client.get_transaction(&txid).await?))
+}
+
+/// `GET /api/v1/explorer/tx/{txid}/outspends` - статусы трат выходов транзакции
+#[get("/explorer/tx/{txid}/outspends")]
+pub async fn explorer_outspends(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ExplorerQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let txid = parse_txid(&path)?;
+    let client = state.explorer(query.explorer.as_deref())?;
+    Ok(ApiResponse::ok(client.get_outspends(&txid).await?))
+}
+
+/// `GET /api/v1/explorer/address/{address}` - статистика адреса из explorer'а
+#[get("/explorer/address/{address}")]
+pub async fn explorer_address(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<ExplorerQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let address = parse_address(&path)?;
+    let client = state.explorer(query.explorer.as_deref())?;
+    Ok(ApiResponse::ok(client.get_address_stats(&address).await?))
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::state::AppState;
+use crate::api::v1::tracing::{parse_start, TraceQuery};
+use crate::api::v1::validation::resolve_folder;
+use crate::application::services::clustering::address_clustering::ClusteringOptions;
+use crate::application::services::export::graph_export::{export_dataset_graph, export_trace, ExportFormat, ExportGraph};
+use crate::application::services::tracing::taint_tracing::trace_taint;
+
+/// Параметры экспорта графа
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// `graphml` (по умолчанию), `gexf`, `dot` или `cytoscape`
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Ответ с графом в виде файла для скачивания
+fn download(graph: &ExportGraph, format: ExportFormat, name: &str) -> HttpResponse {
+    let filename = format!("{}.{}", name.replace([':', '/', '\\'], "_"), format.extension());
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(filename)],
+        })
+        .body(graph.render(format))
+}
+
+/// `GET /api/v1/export` - граф папки с данными со всеми атрибутами в виде файла
+#[get("/export")]
+pub async fn export_dataset(
+    state: web::Data<AppState>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(&folder);
+    let options = ClusteringOptions::from_config(&state.config);
+
+    let graphs = state.graphs.clone();
+
+    let graph = web::block(move || graphs.load(&path).map(|graph| export_dataset_graph(&graph, &options)))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(download(&graph, query.format, &folder))
+}
+
+/// `GET /api/v1/export/trace/{start}` - подграф трассировки средств в виде файла
+///
+/// Принимает те же параметры, что и `GET /api/v1/trace/{start}`, и `format`.
+#[get("/export/trace/{start}")]
+pub async fn export_traced(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    trace_query: web::Query<TraceQuery>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let start = parse_start(&path)?;
+    let options = trace_query.options()?;
+    let client = state.explorer(trace_query.explorer.as_deref())?;
+
+    let trace = trace_taint(client, &start, options).await?;
+    let graph = export_trace(&trace, &ClusteringOptions::from_config(&state.config));
+    Ok(download(&graph, query.format, &format!("trace_{}", path.as_str())))
+}
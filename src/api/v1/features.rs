@@ -0,0 +1,79 @@
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::{parse_address, parse_txid};
+use crate::application::services::address::address_features::build_address_features;
+use crate::application::services::transaction::change_detection::extract_change;
+use crate::application::services::transaction::coinjoin_detection::{detect_coinjoin, CoinJoinMatch};
+use crate::application::services::transaction::extended_features::{extract_extended_features, ExtendedFeatures};
+use crate::application::services::transaction::transaction_info::TransactionFeatures;
+use crate::infrastructure::constants::MAX_TIME_STEP;
+
+/// Параметры извлечения признаков
+#[derive(Debug, Deserialize)]
+pub struct FeaturesQuery {
+    /// Имя клиента explorer'а (по умолчанию `DEFAULT_EXPLORER`)
+    pub explorer: Option<String>,
+    /// Временной шаг, которым ограничивается история адреса
+    pub time_step: Option<u8>,
+}
+
+/// Признаки транзакции, вычисленные по данным explorer'а
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransactionFeatureSet {
+    /// Базовые признаки (суммы в сатоши)
+    pub transaction_features: TransactionFeatures,
+    /// Расширенные признаки
+    pub extended_features: ExtendedFeatures,
+    /// Шаблон CoinJoin, если транзакция на него похожа
+    pub coinjoin: Option<CoinJoinMatch>,
+}
+
+/// `GET /api/v1/features/tx/{txid}` - признаки транзакции по данным explorer'а
+#[get("/features/tx/{txid}")]
+pub async fn transaction_features(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<FeaturesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let txid = parse_txid(&path)?;
+    let client = state.explorer(query.explorer.as_deref())?;
+
+    let tx = client.get_transaction(&txid).await?;
+    let transaction_features = TransactionFeatures::from_transaction(&tx)?;
+    let extended_features = extract_extended_features(client, &txid).await?;
+    let coinjoin = detect_coinjoin(&tx, state.config.cluster_coinjoin_min_equal_outputs);
+
+    Ok(ApiResponse::ok(TransactionFeatureSet { transaction_features, extended_features, coinjoin }))
+}
+
+/// `GET /api/v1/features/tx/{txid}/change` - голоса эвристик сдачи по выходам транзакции
+#[get("/features/tx/{txid}/change")]
+pub async fn transaction_change(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<FeaturesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let txid = parse_txid(&path)?;
+    let client = state.explorer(query.explorer.as_deref())?;
+    Ok(ApiResponse::ok(extract_change(client, &txid).await?))
+}
+
+/// `GET /api/v1/features/address/{address}` - признаки адреса по истории из explorer'а
+#[get("/features/address/{address}")]
+pub async fn address_features(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<FeaturesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let address = parse_address(&path)?;
+    if let Some(time_step) = query.time_step.filter(|step| !(1..=MAX_TIME_STEP).contains(step)) {
+        return Err(ApiError::InvalidInput(format!("time_step must be 1..={}, got {}", MAX_TIME_STEP, time_step)));
+    }
+    let client = state.explorer(query.explorer.as_deref())?;
+
+    Ok(ApiResponse::ok(build_address_features(client, &address, query.time_step).await?))
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::resolve_folder;
+use crate::application::services::graph::graph_metrics::{
+    compute_metrics, CommunityAlgorithm, GraphMetrics, NodeMetrics,
+};
+use crate::application::services::graph::transaction_graph::NodeId;
+
+/// Метрика для упорядочивания узлов в сводке
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RankBy {
+    Degree,
+    Betweenness,
+    #[default]
+    Pagerank,
+}
+
+/// Параметры запроса метрик графа
+#[derive(Debug, Deserialize)]
+pub struct MetricsQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// `louvain` (по умолчанию) или `label_propagation`
+    #[serde(default)]
+    pub communities: CommunityAlgorithm,
+    /// Метрика для списка узлов: `degree`, `betweenness` или `pagerank` (по умолчанию)
+    #[serde(default)]
+    pub rank_by: RankBy,
+    /// Число узлов в списке (по умолчанию 20)
+    pub limit: Option<usize>,
+}
+
+/// Сводка метрик графа папки с данными
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MetricsSummary {
+    pub folder: String,
+    pub nodes: usize,
+    pub edges: usize,
+    pub components: usize,
+    pub largest_component: usize,
+    pub algorithm: CommunityAlgorithm,
+    pub communities: usize,
+    pub largest_community: usize,
+    pub modularity: f64,
+    pub betweenness_sampled: bool,
+    /// Узлы с наибольшим значением метрики `rank_by`
+    pub top: Vec<NodeMetrics>,
+}
+
+/// Вычисляет метрики графа папки в пуле блокирующих задач
+async fn load_metrics(
+    state: &web::Data<AppState>,
+    query: &MetricsQuery,
+) -> Result<(String, usize, GraphMetrics), ApiError> {
+    let folder = resolve_folder(state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(&folder);
+    let algorithm = query.communities;
+    let graphs = state.graphs.clone();
+
+    let (edges, metrics) = web::block(move || {
+        graphs.load(&path).map(|graph| (graph.edge_count(), compute_metrics(&graph, algorithm)))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok((folder, edges, metrics))
+}
+
+/// `GET /api/v1/metrics` - сводка метрик графа: компоненты, сообщества и узлы с наибольшей центральностью
+#[get("/metrics")]
+pub async fn graph_metrics(
+    state: web::Data<AppState>,
+    query: web::Query<MetricsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (folder, edges, metrics) = load_metrics(&state, &query).await?;
+
+    let mut top: Vec<NodeMetrics> = metrics.nodes.clone();
+    let rank = |node: &NodeMetrics| match query.rank_by {
+        RankBy::Degree => node.degree() as f64,
+        RankBy::Betweenness => node.betweenness,
+        RankBy::Pagerank => node.pagerank,
+    };
+    top.sort_by(|a, b| rank(b).total_cmp(&rank(a)).then_with(|| a.id.cmp(&b.id)));
+    top.truncate(query.limit.unwrap_or(20));
+
+    Ok(ApiResponse::ok(MetricsSummary {
+        folder,
+        nodes: metrics.nodes.len(),
+        edges,
+        components: metrics.component_sizes.len(),
+        largest_component: metrics.component_sizes.first().copied().unwrap_or(0),
+        algorithm: metrics.algorithm,
+        communities: metrics.community_sizes.len(),
+        largest_community: metrics.community_sizes.first().copied().unwrap_or(0),
+        modularity: metrics.modularity,
+        betweenness_sampled: metrics.betweenness_sampled,
+        top,
+    }))
+}
+
+/// Метрики узла или `NotFound`, если узла нет в графе папки
+async fn node_metrics(state: web::Data<AppState>, query: &MetricsQuery, id: NodeId) -> Result<HttpResponse, ApiError> {
+    let (folder, _, metrics) = load_metrics(&state, query).await?;
+    let node = metrics
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| ApiError::NotFound(format!("{} not found in {}", id, folder)))?;
+    Ok(ApiResponse::ok(node))
+}
+
+/// `GET /api/v1/metrics/address/{address}` - метрики адреса
+#[get("/metrics/address/{address}")]
+pub async fn address_metrics(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<MetricsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    node_metrics(state, &query, NodeId::address(path.into_inner())).await
+}
+
+/// `GET /api/v1/metrics/tx/{tx_id}` - метрики транзакции
+#[get("/metrics/tx/{tx_id}")]
+pub async fn transaction_metrics(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<MetricsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    node_metrics(state, &query, NodeId::transaction(path.into_inner())).await
+}
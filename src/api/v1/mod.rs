@@ -0,0 +1,14 @@
+pub mod routes;
+pub mod validation;
+pub mod dataset;
+pub mod explorer;
+pub mod features;
+pub mod analysis;
+pub mod datasets;
+pub mod clusters;
+pub mod tracing;
+pub mod paths;
+pub mod metrics;
+pub mod risk;
+pub mod export;
+pub mod ego;
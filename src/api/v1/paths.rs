@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use actix_web::{get, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::{parse_address, resolve_folder};
+use crate::application::services::graph::path_finding::{
+    expand_between, k_shortest_paths, PathMode, PathOptions, PathSearch,
+};
+use crate::application::services::graph::transaction_graph::NodeId;
+use crate::infrastructure::constants::{DEFAULT_PATH_COUNT, DEFAULT_PATH_EXPANSION_DEPTH, MAX_PATH_COUNT, MAX_PATH_EXPANSION_DEPTH};
+
+/// Параметры поиска путей между адресами
+#[derive(Debug, Deserialize)]
+pub struct PathQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// Имя клиента explorer'а (по умолчанию `DEFAULT_EXPLORER`)
+    pub explorer: Option<String>,
+    /// Число кратчайших путей (по умолчанию `DEFAULT_PATH_COUNT`)
+    pub k: Option<usize>,
+    /// `directed` (по умолчанию) или `undirected`
+    #[serde(default)]
+    pub mode: PathMode,
+    /// Уровни истории адресов при расширении через explorer
+    /// (по умолчанию `DEFAULT_PATH_EXPANSION_DEPTH`)
+    pub depth: Option<usize>,
+}
+
+impl PathQuery {
+    fn options(&self) -> Result<PathOptions, ApiError> {
+        let k = self.k.unwrap_or(DEFAULT_PATH_COUNT);
+        if k == 0 || k > MAX_PATH_COUNT {
+            return Err(ApiError::InvalidInput(format!("k must be between 1 and {}, got {}", MAX_PATH_COUNT, k)));
+        }
+        Ok(PathOptions { k, mode: self.mode })
+    }
+}
+
+/// `GET /api/v1/paths/{from}/{to}` - кратчайшие пути между адресами в графе папки с данными
+#[get("/paths/{from}/{to}")]
+pub async fn dataset_paths(
+    state: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<PathQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (from, to) = path.into_inner();
+    let options = query.options()?;
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+    let folder_path = Path::new(&state.config.data_dir).join(&folder);
+
+    let (source, target) = (from.clone(), to.clone());
+    let graphs = state.graphs.clone();
+    let paths = web::block(move || {
+        let graph = graphs.load(&folder_path)?;
+        for address in [&source, &target] {
+            if graph.index_of(&NodeId::address(address.as_str())).is_none() {
+                return Err(ApiError::NotFound(format!("address {} not found in {}", address, folder)));
+            }
+        }
+        Ok(k_shortest_paths(&graph, &source, &target, options))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    Ok(ApiResponse::ok(PathSearch { from, to, mode: options.mode, paths, truncated: false }))
+}
+
+/// `GET /api/v1/explorer/paths/{from}/{to}` - кратчайшие пути между адресами
+/// в графе, расширенном по истории адресов из explorer'а
+#[get("/explorer/paths/{from}/{to}")]
+pub async fn explorer_paths(
+    state: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<PathQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let (from, to) = path.into_inner();
+    let (from, to) = (parse_address(&from)?, parse_address(&to)?);
+    let options = query.options()?;
+    let depth = query.depth.unwrap_or(DEFAULT_PATH_EXPANSION_DEPTH);
+    if depth > MAX_PATH_EXPANSION_DEPTH {
+        return Err(ApiError::InvalidInput(format!("depth must be at most {}, got {}", MAX_PATH_EXPANSION_DEPTH, depth)));
+    }
+    let client = state.explorer(query.explorer.as_deref())?;
+
+    let (graph, truncated) = expand_between(client, &from, &to, depth).await?;
+    let (source, target) = (from.clone(), to.clone());
+    let paths = web::block(move || k_shortest_paths(&graph, &source, &target, options))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    Ok(ApiResponse::ok(PathSearch { from, to, mode: options.mode, paths, truncated }))
+}
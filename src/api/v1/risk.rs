@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use actix_web::{get, web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::resolve_folder;
+use crate::application::services::graph::risk_propagation::{
+    explain_risk, propagate_risk, NodeRisk, PropagationMethod, RiskExplanation,
+};
+use crate::application::services::graph::transaction_graph::NodeId;
+
+/// Параметры запроса распространенного риска
+#[derive(Debug, Deserialize)]
+pub struct RiskQuery {
+    /// Папка с данными (по умолчанию `DEFAULT_DATA_FOLDER`)
+    pub folder: Option<String>,
+    /// `label_spreading` (по умолчанию) или `personalized_pagerank`
+    #[serde(default)]
+    pub method: PropagationMethod,
+    /// Минимальный риск узлов в списке (по умолчанию 0.5)
+    pub min_risk: Option<f64>,
+    /// Число узлов в списке (по умолчанию 20)
+    pub limit: Option<usize>,
+}
+
+/// Сводка распространенного риска по папке с данными
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RiskSummary {
+    pub folder: String,
+    pub method: PropagationMethod,
+    pub illicit_seeds: usize,
+    pub licit_seeds: usize,
+    /// Неразмеченные узлы, получившие риск
+    pub propagated: usize,
+    /// Неразмеченные узлы, до которых не дошел ни один класс
+    pub unreached: usize,
+    /// Неразмеченные узлы с наибольшим риском
+    pub top: Vec<NodeRisk>,
+}
+
+/// `GET /api/v1/risk` - неразмеченные транзакции и адреса с наибольшим распространенным риском
+#[get("/risk")]
+pub async fn risk_summary(
+    state: web::Data<AppState>,
+    query: web::Query<RiskQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(&folder);
+    let method = query.method;
+    let graphs = state.graphs.clone();
+
+    let scores = web::block(move || graphs.load(&path).map(|graph| propagate_risk(&graph, method)))
+        .await
+        .map_err(|e| ApiError::Internal(e.to_string()))??;
+
+    let min_risk = query.min_risk.unwrap_or(0.5);
+    let mut top: Vec<NodeRisk> = scores
+        .nodes
+        .iter()
+        .filter(|node| node.propagated && node.risk.is_some_and(|risk| risk >= min_risk))
+        .cloned()
+        .collect();
+    top.sort_by(|a, b| b.risk.unwrap_or(0.0).total_cmp(&a.risk.unwrap_or(0.0)).then_with(|| a.id.cmp(&b.id)));
+    top.truncate(query.limit.unwrap_or(20));
+
+    let unlabelled = || scores.nodes.iter().filter(|node| node.class.is_none());
+    Ok(ApiResponse::ok(RiskSummary {
+        folder,
+        method,
+        illicit_seeds: scores.illicit_seeds,
+        licit_seeds: scores.licit_seeds,
+        propagated: unlabelled().filter(|node| node.propagated).count(),
+        unreached: unlabelled().filter(|node| node.risk.is_none()).count(),
+        top,
+    }))
+}
+
+/// Риск узла с объяснением или `NotFound`, если узла нет в графе папки
+async fn node_risk(state: web::Data<AppState>, query: &RiskQuery, id: NodeId) -> Result<HttpResponse, ApiError> {
+    let folder = resolve_folder(&state, query.folder.as_deref())?;
+    let path = Path::new(&state.config.data_dir).join(&folder);
+    let method = query.method;
+    let node = id.clone();
+    let graphs = state.graphs.clone();
+
+    let explanation: Option<RiskExplanation> = web::block(move || {
+        graphs.load(&path).map(|graph| explain_risk(&graph, &propagate_risk(&graph, method), &node))
+    })
+    .await
+    .map_err(|e| ApiError::Internal(e.to_string()))??;
+    let explanation = explanation.ok_or_else(|| ApiError::NotFound(format!("{} not found in {}", id, folder)))?;
+    Ok(ApiResponse::ok(explanation))
+}
+
+/// `GET /api/v1/risk/address/{address}` - риск адреса и ближайшие размеченные соседи
+#[get("/risk/address/{address}")]
+pub async fn address_risk(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<RiskQuery>,
+) -> Result<HttpResponse, ApiError> {
+    node_risk(state, &query, NodeId::address(path.into_inner())).await
+}
+
+/// `GET /api/v1/risk/tx/{tx_id}` - риск транзакции и ближайшие размеченные соседи
+#[get("/risk/tx/{tx_id}")]
+pub async fn transaction_risk(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<RiskQuery>,
+) -> Result<HttpResponse, ApiError> {
+    node_risk(state, &query, NodeId::transaction(path.into_inner())).await
+}
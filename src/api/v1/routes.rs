@@ -0,0 +1,583 @@
+use actix_multipart::form::MultipartFormConfig;
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::v1::{
+    analysis, clusters, dataset, datasets, ego, explorer, export, features, metrics, paths, risk, tracing,
+};
+use crate::infrastructure::constants::MAX_DATASET_UPLOAD_BYTES;
+
+/// Регистрирует JSON API версии 1 под префиксом `/api/v1`
+///
+/// Ошибки разбора параметров пути и запроса, а также неизвестные пути
+/// внутри `/api/v1` возвращаются в формате [`ApiError`].
+///
+/// # Example
+/// ```no_run
+/// use actix_web::App;
+/// use diploma_software::api::v1::routes::configure;
+///
+/// let app = App::new().configure(configure);
+/// ```
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api/v1")
+            .app_data(web::PathConfig::default().error_handler(|err, _| ApiError::InvalidInput(err.to_string()).into()))
+            .app_data(web::QueryConfig::default().error_handler(|err, _| ApiError::InvalidInput(err.to_string()).into()))
+            .app_data(
+                MultipartFormConfig::default()
+                    .total_limit(MAX_DATASET_UPLOAD_BYTES)
+                    .error_handler(|err, _| ApiError::InvalidInput(err.to_string()).into()),
+            )
+            .service(dataset::dataset_transaction)
+            .service(dataset::dataset_address)
+            .service(dataset::dataset_graph)
+            .service(explorer::explorer_transaction)
+            .service(explorer::explorer_outspends)
+            .service(explorer::explorer_address)
+            .service(features::transaction_features)
+            .service(features::transaction_change)
+            .service(features::address_features)
+            .service(analysis::analyze_transaction)
+            .service(datasets::list_datasets)
+            .service(datasets::upload_dataset)
+            .service(datasets::get_dataset)
+            .service(datasets::validate_dataset)
+            .service(datasets::delete_dataset)
+            .service(clusters::list_clusters)
+            .service(clusters::address_cluster)
+            .service(clusters::get_cluster)
+            .service(tracing::trace)
+            .service(paths::dataset_paths)
+            .service(paths::explorer_paths)
+            .service(metrics::graph_metrics)
+            .service(metrics::address_metrics)
+            .service(metrics::transaction_metrics)
+            .service(risk::risk_summary)
+            .service(risk::address_risk)
+            .service(risk::transaction_risk)
+            .service(export::export_dataset)
+            .service(export::export_traced)
+            .service(ego::address_ego)
+            .service(ego::transaction_ego)
+            .default_service(web::to(unknown_endpoint)),
+    );
+}
+
+async fn unknown_endpoint(req: HttpRequest) -> HttpResponse {
+    ApiError::NotFound(format!("unknown endpoint: {} {}", req.method(), req.path())).error_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::errors::api_errors::ApiErrorResponse;
+    use crate::api::state::AppState;
+    use crate::infrastructure::config::Config;
+    use crate::infrastructure::dataset::dataset_manager::zip_archive;
+    use crate::infrastructure::dataset::dataset_store::DatasetStore;
+    use crate::services::explorer::backends::chain_backend::ChainBackend;
+    use crate::services::explorer::errors::explorer_errors::ExplorerError;
+    use crate::services::explorer::explorer_client::ExplorerClient;
+    use crate::services::explorer::explorer_registry::ExplorerRegistry;
+    use crate::services::explorer::models::chain::{
+        ChainAddressStats, ChainOutspend, ChainTransaction,
+    };
+    use crate::services::ml::ml_client::MLClient;
+    use crate::services::explorer::models::esplora::{
+        fixture_json, fixture_transaction, EsploraAddressStats, EsploraOutspend, BLOCK_1_COINBASE, SEGWIT_TX,
+    };
+    use actix_web::http::{header, StatusCode};
+    use actix_web::{test, App, HttpResponse, HttpServer};
+    use async_trait::async_trait;
+    use serde_json::Value;
+    use std::fs;
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const FOLDER: &str = "sample_folder";
+    const UNREACHABLE_ML: &str = "http://127.0.0.1:1";
+
+    /// Backend, отвечающий транзакциями из `tests/fixtures/esplora`
+    struct FixtureBackend;
+
+    #[async_trait]
+    impl ChainBackend for FixtureBackend {
+        fn name(&self) -> &'static str {
+            "fixtures"
+        }
+
+        async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+            [SEGWIT_TX, BLOCK_1_COINBASE]
+                .into_iter()
+                .map(fixture_transaction)
+                .find(|tx| tx.txid == txid)
+                .map(ChainTransaction::from)
+                .ok_or_else(|| ExplorerError::NotFound(format!("/tx/{}", txid)))
+        }
+
+        async fn get_outspends(&self, _txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+            let outspends: Vec<EsploraOutspend> = serde_json::from_str(&fixture_json("outspends.json"))?;
+            Ok(outspends.into_iter().map(ChainOutspend::from).collect())
+        }
+
+        async fn get_address_stats(&self, _address: &str) -> Result<ChainAddressStats, ExplorerError> {
+            let stats: EsploraAddressStats = serde_json::from_str(&fixture_json("address.json"))?;
+            Ok(stats.into())
+        }
+
+        async fn get_address_transactions(
+            &self,
+            _address: &str,
+            _max_pages: usize,
+        ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+            Ok(vec![fixture_transaction(SEGWIT_TX).into()])
+        }
+
+        async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+            Ok(840_010)
+        }
+    }
+
+    fn sample_data_dir(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("api_v1_{}_{}", name, std::process::id()));
+        let folder = root.join(FOLDER);
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&folder).expect("Should create data folder");
+        let files = [
+            ("elliptic_txs_features.csv", "txId,Time step,Local_feature_1\n101,7,0.5\n102,7,-0.25\n"),
+            ("elliptic_txs_classes.csv", "txId,class\n101,1\n102,unknown\n"),
+            ("wallets_features_classes_combined.csv", "address,Time step,class,num_txs_as_sender\n1A,7,2,3\n1B,7,1,0\n"),
+            ("AddrTx_edgelist.csv", "input_address,txId\n1A,101\n"),
+            ("TxAddr_edgelist.csv", "txId,output_address\n101,1B\n101,1C\n"),
+        ];
+        for (file, content) in files {
+            fs::write(folder.join(file), content).expect("Should write CSV");
+        }
+        root
+    }
+
+    fn state(data_dir: &std::path::Path, ml_url: &str) -> web::Data<AppState> {
+        let config = Config {
+            data_dir: data_dir.to_string_lossy().into_owned(),
+            default_data_folder: FOLDER.to_string(),
+            default_explorer: "fixtures".to_string(),
+            ..Config::from_env().expect("Valid config")
+        };
+        let mut explorers = ExplorerRegistry::new("fixtures");
+        explorers.insert("fixtures", ExplorerClient::with_backend(FixtureBackend));
+        let ml = MLClient::new(ml_url, Duration::from_secs(5)).expect("Should build ML client");
+        web::Data::new(AppState { config, explorers, ml, datasets: DatasetStore::new(), graphs: Arc::default() })
+    }
+
+    /// Выполняет GET запрос и возвращает статус и JSON тело
+    macro_rules! get_json {
+        ($app:expr, $uri:expr) => {{
+            let response = test::call_service(&$app, test::TestRequest::get().uri(&$uri).to_request()).await;
+            let status = response.status();
+            let body: Value = test::read_body_json(response).await;
+            (status, body)
+        }};
+    }
+
+    fn error_code(body: &Value) -> String {
+        let error: ApiErrorResponse = serde_json::from_value(body.clone()).expect("Error envelope");
+        assert!(!error.success);
+        error.error.code
+    }
+
+    #[actix_web::test]
+    async fn test_dataset_endpoints() {
+        let data_dir = sample_data_dir("dataset");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+
+        let (status, body) = get_json!(app, "/api/v1/tx/101");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["class"], "illicit");
+        assert_eq!(body["data"]["fields"]["Local_feature_1"], "0.5");
+
+        let (status, body) = get_json!(app, "/api/v1/address/1A?folder=sample_folder");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["class"], "licit");
+
+        let (status, body) = get_json!(app, "/api/v1/graph");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["edges"].as_array().map(Vec::len), Some(3));
+        assert_eq!(body["data"]["nodes"].as_array().map(Vec::len), Some(4));
+
+        let (status, body) = get_json!(app, "/api/v1/tx/999");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+
+        let (status, body) = get_json!(app, "/api/v1/tx/101?folder=..");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(app, "/api/v1/graph?folder=missing");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "DATASET_NOT_FOUND"));
+
+        let (status, body) = get_json!(app, "/api/v1/nothing");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_explorer_and_feature_endpoints() {
+        let data_dir = sample_data_dir("explorer");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+        let segwit = SEGWIT_TX;
+        let coinbase = BLOCK_1_COINBASE;
+
+        let (status, body) = get_json!(app, format!("/api/v1/explorer/tx/{}", segwit));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["fee"], 26_517);
+
+        let (status, body) = get_json!(app, format!("/api/v1/explorer/tx/{}/outspends", segwit));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"][1]["spent"], true);
+
+        let (status, body) = get_json!(app, format!("/api/v1/features/tx/{}", coinbase));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["transaction_features"]["transaction_fee"], 0);
+        assert_eq!(body["data"]["extended_features"]["num_coinbase_inputs"], 1);
+        assert!(body["data"]["coinjoin"].is_null());
+
+        // Выход 2 возвращается на адрес входов
+        let (status, body) = get_json!(app, format!("/api/v1/features/tx/{}/change", segwit));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["outputs"].as_array().map(Vec::len), Some(3));
+        assert_eq!(body["data"]["change_vout"], 2);
+
+        let (status, body) = get_json!(app, "/api/v1/features/address/bc1qll6gq9v38t9nttwek0r5kkn0wmedz3w2gshe0a");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["address"], "bc1qll6gq9v38t9nttwek0r5kkn0wmedz3w2gshe0a");
+
+        let (status, body) = get_json!(app, "/api/v1/explorer/tx/not-a-txid");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(app, format!("/api/v1/explorer/tx/{}", "ab".repeat(32)));
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+
+        let (status, body) = get_json!(app, format!("/api/v1/explorer/tx/{}?explorer=other", segwit));
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(app, "/api/v1/explorer/address/not-an-address");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(app, "/api/v1/features/address/1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa?time_step=x");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_trace_endpoint() {
+        let data_dir = sample_data_dir("trace");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+        let segwit = SEGWIT_TX;
+
+        let (status, body) = get_json!(app, format!("/api/v1/trace/{}:1?max_hops=0", segwit));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["edges"][0]["tainted"], 10_273_270);
+        assert_eq!(body["data"]["edges"][0]["spent_by"], "79f46438043db23e4449627a92097531995c57d6d60c438425ba61de13484e7a");
+
+        // Выход 1 оплачен входами пропорционально их суммам
+        let (status, body) = get_json!(app, format!("/api/v1/trace/{}:1?direction=backward&model=haircut&max_hops=1", segwit));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["transactions"].as_array().map(Vec::len), Some(1));
+        assert_eq!(body["data"]["edges"][1]["tainted"], 6_279_029);
+
+        let (status, body) = get_json!(app, format!("/api/v1/trace/{}:1?max_hops=50", segwit));
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(app, "/api/v1/trace/not-a-txid:0");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(app, format!("/api/v1/trace/{}:1?model=magic", segwit));
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_risk_endpoints() {
+        let data_dir = sample_data_dir("risk");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+
+        let (status, body) = get_json!(app, "/api/v1/risk");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((body["data"]["illicit_seeds"].as_u64(), body["data"]["licit_seeds"].as_u64()), (Some(2), Some(1)));
+        assert_eq!(body["data"]["propagated"], 1);
+        assert_eq!(body["data"]["top"][0]["id"], "1C");
+
+        let (status, body) = get_json!(app, "/api/v1/risk/address/1C?method=personalized_pagerank");
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["data"]["risk"].as_f64() > Some(0.5));
+        assert_eq!(body["data"]["neighbours"][0], serde_json::json!({"kind": "transaction", "id": "101", "class": "illicit", "distance": 1}));
+
+        let (status, body) = get_json!(app, "/api/v1/graph?color_by=risk");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["nodes"].as_array().map(Vec::len), Some(4));
+        assert_eq!(body["data"]["nodes"][3]["id"], "tx:101");
+
+        let (status, body) = get_json!(app, "/api/v1/risk/tx/999");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+        let (status, body) = get_json!(app, "/api/v1/risk?method=magic");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_export_endpoints() {
+        let data_dir = sample_data_dir("export");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+
+        let request = test::TestRequest::get().uri("/api/v1/export?format=gexf").to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let disposition = response.headers().get(header::CONTENT_DISPOSITION).and_then(|v| v.to_str().ok());
+        assert_eq!(disposition, Some(format!("attachment; filename=\"{}.gexf\"", FOLDER).as_str()));
+        let body = String::from_utf8(test::read_body(response).await.to_vec()).expect("UTF-8 body");
+        assert!(body.contains("<attvalue for=\"class\" value=\"illicit\"/>"));
+        assert_eq!(body.matches("<node id=").count(), 4);
+
+        let segwit = SEGWIT_TX;
+        let (status, body) = get_json!(app, format!("/api/v1/export/trace/{}:1?max_hops=0&format=cytoscape", segwit));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["elements"]["edges"][0]["data"]["tainted"], 10_273_270);
+
+        let (status, body) = get_json!(app, "/api/v1/export?format=csv");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_ego_endpoints() {
+        let data_dir = sample_data_dir("ego");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+
+        let (status, body) = get_json!(app, "/api/v1/ego/tx/101?radius=1");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["nodes"].as_array().map(Vec::len), Some(4));
+        assert_eq!(body["data"]["edges"].as_array().map(Vec::len), Some(3));
+        assert_eq!(body["data"]["nodes"][0], serde_json::json!({"kind": "transaction", "id": "101", "class": "illicit", "distance": 0}));
+
+        let (status, body) = get_json!(app, "/api/v1/ego/address/1A?collapse_hubs=true&hub_degree=2");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["nodes"].as_array().map(Vec::len), Some(2));
+        assert_eq!(body["data"]["hubs"][0], serde_json::json!({"kind": "transaction", "id": "101", "degree": 3, "hidden": 2}));
+
+        let (status, body) = get_json!(app, "/api/v1/ego/address/1B?max_nodes=1");
+        assert_eq!((status, body["data"]["truncated"].as_bool()), (StatusCode::OK, Some(true)));
+
+        let (status, body) = get_json!(app, "/api/v1/ego/address/1Z");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+        let (status, body) = get_json!(app, "/api/v1/ego/tx/101?radius=50");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_path_endpoints() {
+        let data_dir = sample_data_dir("paths");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+
+        let (status, body) = get_json!(app, "/api/v1/paths/1A/1C");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["paths"][0]["hops"], 1);
+        assert_eq!(body["data"]["paths"][0]["transactions"], serde_json::json!(["101"]));
+
+        // Получатели одной транзакции связаны только без учета направления
+        let (status, body) = get_json!(app, "/api/v1/paths/1B/1C");
+        assert_eq!((status, body["data"]["paths"].as_array().map(Vec::len)), (StatusCode::OK, Some(0)));
+        let (_, body) = get_json!(app, "/api/v1/paths/1B/1C?mode=undirected");
+        assert_eq!(body["data"]["paths"][0]["links"][0]["reversed"], true);
+
+        let (status, body) = get_json!(app, "/api/v1/paths/1A/1Z");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+        let (status, body) = get_json!(app, "/api/v1/paths/1A/1C?k=0");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+
+        let (status, body) = get_json!(
+            app,
+            "/api/v1/explorer/paths/bc1qxxkqkdljm7s2xnr9u47zpymp4yt0ad3df4hudm/bc1qll6gq9v38t9nttwek0r5kkn0wmedz3w2gshe0a"
+        );
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["paths"][0]["links"][1]["value"], 32_733_248);
+        assert_eq!(body["data"]["truncated"], false);
+
+        let (status, body) = get_json!(app, "/api/v1/explorer/paths/1A/1C");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    /// Запускает ML сервис, который считает незаконными транзакции с комиссией
+    fn spawn_ml_service() -> String {
+        async fn analyze(body: web::Json<Value>) -> HttpResponse {
+            let fee = body["transaction_features"]["transaction_fee"].as_f64().unwrap_or_default();
+            let (class, risk_score) = if fee > 0.0 { ("illicit", 0.87) } else { ("licit", 0.05) };
+            HttpResponse::Ok().json(serde_json::json!({
+                "success": true,
+                "transaction_id": body["transaction_id"],
+                "prediction": {"class": class, "confidence": 0.9, "risk_score": risk_score},
+                "explanation": "fee based mock",
+                "details": {
+                    "model_version": "test",
+                    "inference_time_ms": 1.0,
+                    "feature_importance": {"n_inputs": 0.1, "transaction_fee": 0.6}
+                }
+            }))
+        }
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!("http://{}", listener.local_addr().expect("Local addr"));
+        let server = HttpServer::new(|| App::new().route("/api/v1/analyze", web::post().to(analyze)))
+            .listen(listener)
+            .expect("Should listen")
+            .workers(1)
+            .run();
+        actix_web::rt::spawn(server);
+        url
+    }
+
+    #[actix_web::test]
+    async fn test_analyze_endpoint() {
+        let data_dir = sample_data_dir("analyze");
+        let ml_url = spawn_ml_service();
+        let app = test::init_service(App::new().app_data(state(&data_dir, &ml_url)).configure(configure)).await;
+        let coinbase = BLOCK_1_COINBASE;
+
+        let (status, body) = get_json!(app, format!("/api/v1/analyze/tx/{}", coinbase));
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["txid"], coinbase);
+        assert_eq!(body["data"]["extended_features"]["num_coinbase_inputs"], 1);
+        assert_eq!(body["data"]["verdict"]["class"], "licit");
+        assert_eq!(body["data"]["verdict"]["feature_importance"][0]["feature"], "transaction_fee");
+
+        let (status, body) = get_json!(app, format!("/api/v1/analyze/tx/{}", "ab".repeat(32)));
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+
+        let offline = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+        let (status, body) = get_json!(offline, format!("/api/v1/analyze/tx/{}", coinbase));
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::SERVICE_UNAVAILABLE, "ML_UNAVAILABLE"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    /// Формирует тело `multipart/form-data` с именем папки и архивом
+    fn upload_body(name: &str, archive: &[u8]) -> (String, Vec<u8>) {
+        let boundary = "dataset-boundary";
+        let mut body = format!(
+            "--{b}\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\n{name}\r\n\
+             --{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"data.zip\"\r\n\
+             Content-Type: application/zip\r\n\r\n",
+            b = boundary,
+            name = name
+        )
+        .into_bytes();
+        body.extend_from_slice(archive);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+        (format!("multipart/form-data; boundary={}", boundary), body)
+    }
+
+    #[actix_web::test]
+    async fn test_dataset_management_endpoints() {
+        let data_dir = sample_data_dir("datasets");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+        let archive = zip_archive(&[
+            ("elliptic_txs_features.csv", "txId,Time step,Local_feature_1\n201,3,0.5\n"),
+            ("elliptic_txs_classes.csv", "txId,class\n201,2\n"),
+        ]);
+
+        let (content_type, body) = upload_body("uploaded", &archive);
+        let request = test::TestRequest::post()
+            .uri("/api/v1/datasets")
+            .insert_header(("content-type", content_type))
+            .set_payload(body)
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let (status, body) = get_json!(app, "/api/v1/datasets");
+        assert_eq!(status, StatusCode::OK);
+        let mut names: Vec<&str> = body["data"].as_array().expect("List").iter().filter_map(|f| f["name"].as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, vec![FOLDER, "uploaded"]);
+
+        let (status, body) = get_json!(app, "/api/v1/tx/201?folder=uploaded");
+        assert_eq!((status, body["data"]["class"].as_str()), (StatusCode::OK, Some("licit")));
+
+        let request = test::TestRequest::post().uri("/api/v1/datasets/uploaded/validate").to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(body["data"]["files"].as_array().map(Vec::len), Some(2));
+
+        let (content_type, body) = upload_body("../escape", &archive);
+        let request = test::TestRequest::post()
+            .uri("/api/v1/datasets")
+            .insert_header(("content-type", content_type))
+            .set_payload(body)
+            .to_request();
+        let body: Value = test::call_and_read_body_json(&app, request).await;
+        assert_eq!(error_code(&body), "INVALID_INPUT");
+
+        let request = test::TestRequest::delete().uri("/api/v1/datasets/uploaded").to_request();
+        assert_eq!(test::call_service(&app, request).await.status(), StatusCode::OK);
+        let (status, body) = get_json!(app, "/api/v1/datasets/uploaded");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "DATASET_NOT_FOUND"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[actix_web::test]
+    async fn test_cluster_endpoints() {
+        let data_dir = sample_data_dir("clusters");
+        let folder = data_dir.join("clustered");
+        fs::create_dir_all(&folder).expect("Should create data folder");
+        fs::write(folder.join("AddrTx_edgelist.csv"), "input_address,txId\n1A,101\n1B,101\n1B,102\n1C,102\n1D,103\n1E,103\n1F,103\n")
+            .expect("Should write CSV");
+        fs::write(folder.join("TxAddr_edgelist.csv"), "txId,output_address\n101,1G\n102,1H\n103,1I\n").expect("Should write CSV");
+        let app = test::init_service(App::new().app_data(state(&data_dir, UNREACHABLE_ML)).configure(configure)).await;
+
+        let (status, body) = get_json!(app, "/api/v1/clusters?folder=clustered&max_inputs=2");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["excluded_transactions"], 1);
+        assert_eq!(body["data"]["clusters"], serde_json::json!([{"id": 0, "size": 3}]));
+        assert_eq!(body["data"]["total_addresses"], 9);
+
+        let (status, body) = get_json!(app, "/api/v1/clusters/0?folder=clustered&max_inputs=2");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["members"], serde_json::json!(["1A", "1B", "1C"]));
+
+        let (status, body) = get_json!(app, "/api/v1/clusters/address/1E?folder=clustered");
+        assert_eq!((status, body["data"]["size"].as_u64()), (StatusCode::OK, Some(3)));
+
+        let (status, body) = get_json!(app, "/api/v1/graph?folder=clustered&color_by=cluster");
+        assert_eq!(status, StatusCode::OK);
+        let fill = |id: &str| body["data"]["nodes"].as_array().and_then(|nodes| {
+            nodes.iter().find(|node| node["id"] == id).map(|node| node["normal"]["fill"].clone())
+        });
+        assert_eq!(fill("1A"), fill("1C"));
+        assert_ne!(fill("1A"), fill("1D"));
+        assert_eq!(fill("1G"), Some(Value::from("#FFFFFF")));
+
+        let (status, body) = get_json!(app, "/api/v1/graph?folder=clustered&color_by=community&size_by=degree");
+        assert_eq!(status, StatusCode::OK);
+        let height = |id: &str| body["data"]["nodes"].as_array().and_then(|nodes| {
+            nodes.iter().find(|node| node["id"] == id).and_then(|node| node["height"].as_f64())
+        });
+        assert!(height("1B") > height("1A"));
+
+        let (status, body) = get_json!(app, "/api/v1/metrics?folder=clustered&rank_by=degree&limit=1");
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!((body["data"]["nodes"].as_u64(), body["data"]["components"].as_u64()), (Some(12), Some(2)));
+        assert_eq!(body["data"]["top"][0]["id"], "103");
+
+        let (status, body) = get_json!(app, "/api/v1/metrics/address/1B?folder=clustered&communities=label_propagation");
+        assert_eq!((status, body["data"]["out_degree"].as_u64()), (StatusCode::OK, Some(2)));
+        let (status, body) = get_json!(app, "/api/v1/metrics/tx/999?folder=clustered");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+
+        let (status, body) = get_json!(app, "/api/v1/clusters/99?folder=clustered");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::NOT_FOUND, "NOT_FOUND"));
+
+        let (status, body) = get_json!(app, "/api/v1/graph?color_by=size");
+        assert_eq!((status, error_code(&body).as_str()), (StatusCode::BAD_REQUEST, "INVALID_INPUT"));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,68 @@
+use actix_web::{get, web, HttpResponse};
+use bitcoin::Amount;
+use serde::Deserialize;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::response::ApiResponse;
+use crate::api::state::AppState;
+use crate::api::v1::validation::{parse_address, parse_txid};
+use crate::application::services::tracing::taint_tracing::{
+    trace_taint, TaintModel, TraceDirection, TraceOptions, TraceStart,
+};
+use crate::infrastructure::constants::MAX_TRACE_HOPS;
+
+/// Параметры трассировки средств
+#[derive(Debug, Deserialize)]
+pub struct TraceQuery {
+    /// Имя клиента explorer'а (по умолчанию `DEFAULT_EXPLORER`)
+    pub explorer: Option<String>,
+    /// `forward` (по умолчанию) или `backward`
+    #[serde(default)]
+    pub direction: TraceDirection,
+    /// `poison`, `haircut` (по умолчанию) или `fifo`
+    #[serde(default)]
+    pub model: TaintModel,
+    /// Глубина в транзакциях (по умолчанию `DEFAULT_TRACE_MAX_HOPS`)
+    pub max_hops: Option<usize>,
+    /// Порог загрязненной суммы в сатоши, ниже которого выходы не отслеживаются
+    pub min_value: Option<u64>,
+}
+
+impl TraceQuery {
+    /// Параметры трассировки с проверкой глубины
+    pub(crate) fn options(&self) -> Result<TraceOptions, ApiError> {
+        let mut options = TraceOptions { direction: self.direction, model: self.model, ..TraceOptions::default() };
+        if let Some(max_hops) = self.max_hops {
+            if max_hops > MAX_TRACE_HOPS {
+                return Err(ApiError::InvalidInput(format!("max_hops must be at most {}, got {}", MAX_TRACE_HOPS, max_hops)));
+            }
+            options.max_hops = max_hops;
+        }
+        if let Some(min_value) = self.min_value {
+            options.min_value = Amount::from_sat(min_value);
+        }
+        Ok(options)
+    }
+}
+
+/// Проверяет начало трассировки: `txid:vout` или Bitcoin адрес
+pub(crate) fn parse_start(start: &str) -> Result<TraceStart, ApiError> {
+    match start.parse::<TraceStart>().map_err(ApiError::InvalidInput)? {
+        TraceStart::Output { txid, vout } => Ok(TraceStart::Output { txid: parse_txid(&txid)?, vout }),
+        TraceStart::Address(address) => Ok(TraceStart::Address(parse_address(&address)?)),
+    }
+}
+
+/// `GET /api/v1/trace/{start}` - движение средств от выхода `txid:vout` или адреса
+#[get("/trace/{start}")]
+pub async fn trace(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<TraceQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let start = parse_start(&path)?;
+    let options = query.options()?;
+    let client = state.explorer(query.explorer.as_deref())?;
+
+    Ok(ApiResponse::ok(trace_taint(client, &start, options).await?))
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::Address;
+
+use crate::api::errors::api_errors::ApiError;
+use crate::api::state::AppState;
+use crate::infrastructure::dataset::csv_lookup::is_safe_folder_name;
+
+/// Проверяет ID транзакции (64 hex символа) и приводит его к нижнему регистру
+pub fn parse_txid(txid: &str) -> Result<String, ApiError> {
+    if txid.len() == 64 && txid.bytes().all(|b| b.is_ascii_hexdigit()) {
+        Ok(txid.to_ascii_lowercase())
+    } else {
+        Err(ApiError::InvalidInput(format!("invalid txid: {}", txid)))
+    }
+}
+
+/// Проверяет, что строка — корректный Bitcoin адрес
+pub fn parse_address(address: &str) -> Result<String, ApiError> {
+    Address::<NetworkUnchecked>::from_str(address)
+        .map(|_| address.to_string())
+        .map_err(|e| ApiError::InvalidInput(format!("invalid address {}: {}", address, e)))
+}
+
+/// Возвращает имя папки с данными из запроса или папку по умолчанию
+///
+/// # Returns
+/// `Result<String, ApiError>` - имя папки, `InvalidInput` для небезопасного имени
+/// или `Dataset(NotFound)`, если папки нет в `DATA_DIR`
+pub fn resolve_folder(state: &AppState, folder: Option<&str>) -> Result<String, ApiError> {
+    let folder = folder.unwrap_or(&state.config.default_data_folder);
+    if !is_safe_folder_name(folder) {
+        return Err(ApiError::InvalidInput(format!("invalid data folder: {}", folder)));
+    }
+    if !Path::new(&state.config.data_dir).join(folder).is_dir() {
+        return Err(ApiError::Dataset(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("data folder {} not found", folder),
+        )));
+    }
+    Ok(folder.to_string())
+}
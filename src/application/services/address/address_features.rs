@@ -0,0 +1,493 @@
+use std::collections::{BTreeSet, HashMap};
+
+use bitcoin::Amount;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::transaction::extended_features::time_step_for_height;
+use crate::infrastructure::constants::MAX_ADDRESS_HISTORY_PAGES;
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::explorer_client::{get_or_init_client, ExplorerClient};
+use crate::services::explorer::models::chain::ChainTransaction;
+
+/// Число признаков адреса в датасете Elliptic++
+pub const ADDRESS_FEATURE_COUNT: usize = 55;
+
+/// Названия признаков в порядке колонок `wallets_features_classes_combined.csv`
+/// (после колонок `address`, `Time step` и `class`)
+pub const ADDRESS_FEATURE_COLUMNS: [&str; ADDRESS_FEATURE_COUNT] = [
+    "num_txs_as_sender",
+    "num_txs_as receiver",
+    "first_block_appeared_in",
+    "last_block_appeared_in",
+    "lifetime_in_blocks",
+    "total_txs",
+    "first_sent_block",
+    "first_received_block",
+    "num_timesteps_appeared_in",
+    "btc_transacted_total",
+    "btc_transacted_min",
+    "btc_transacted_max",
+    "btc_transacted_mean",
+    "btc_transacted_median",
+    "btc_sent_total",
+    "btc_sent_min",
+    "btc_sent_max",
+    "btc_sent_mean",
+    "btc_sent_median",
+    "btc_received_total",
+    "btc_received_min",
+    "btc_received_max",
+    "btc_received_mean",
+    "btc_received_median",
+    "fees_total",
+    "fees_min",
+    "fees_max",
+    "fees_mean",
+    "fees_median",
+    "fees_as_share_total",
+    "fees_as_share_min",
+    "fees_as_share_max",
+    "fees_as_share_mean",
+    "fees_as_share_median",
+    "blocks_btwn_txs_total",
+    "blocks_btwn_txs_min",
+    "blocks_btwn_txs_max",
+    "blocks_btwn_txs_mean",
+    "blocks_btwn_txs_median",
+    "blocks_btwn_input_txs_total",
+    "blocks_btwn_input_txs_min",
+    "blocks_btwn_input_txs_max",
+    "blocks_btwn_input_txs_mean",
+    "blocks_btwn_input_txs_median",
+    "blocks_btwn_output_txs_total",
+    "blocks_btwn_output_txs_min",
+    "blocks_btwn_output_txs_max",
+    "blocks_btwn_output_txs_mean",
+    "blocks_btwn_output_txs_median",
+    "num_addr_transacted_multiple",
+    "transacted_w_address_total",
+    "transacted_w_address_min",
+    "transacted_w_address_max",
+    "transacted_w_address_mean",
+    "transacted_w_address_median",
+];
+
+/// Признаки адреса в формате Elliptic++ (`wallets_features_classes_combined.csv`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AddressFeatures {
+    /// Bitcoin адрес
+    pub address: String,
+    /// Временной шаг Elliptic (1–49)
+    pub time_step: u8,
+    /// 55 признаков в порядке [`ADDRESS_FEATURE_COLUMNS`]
+    pub features: Vec<f64>,
+}
+
+impl AddressFeatures {
+    /// Вектор признаков для ML сервиса: временной шаг, затем 55 признаков
+    pub fn to_vector(&self) -> Vec<f64> {
+        std::iter::once(self.time_step as f64)
+            .chain(self.features.iter().copied())
+            .collect()
+    }
+
+    /// Строка в формате `wallets_features_classes_combined.csv`
+    ///
+    /// # Arguments
+    /// * `class` - Значение колонки `class` (для живых адресов обычно `"3"`)
+    pub fn to_csv_record(&self, class: &str) -> Vec<String> {
+        let mut record = vec![self.address.clone(), self.time_step.to_string(), class.to_string()];
+        record.extend(self.features.iter().map(|v| format!("{:?}", v)));
+        record
+    }
+
+    /// Значение признака по названию колонки
+    pub fn get(&self, column: &str) -> Option<f64> {
+        ADDRESS_FEATURE_COLUMNS
+            .iter()
+            .position(|c| *c == column)
+            .and_then(|i| self.features.get(i).copied())
+    }
+}
+
+/// Заголовок CSV файла, совместимый с `wallets_features_classes_combined.csv`
+pub fn address_features_csv_header() -> Vec<&'static str> {
+    let mut header = vec!["address", "Time step", "class"];
+    header.extend(ADDRESS_FEATURE_COLUMNS);
+    header
+}
+
+/// Участие адреса в одной транзакции
+struct Participation {
+    height: u64,
+    sent: Amount,
+    received: Amount,
+    fee: Amount,
+    /// Доля комиссии, приходящаяся на адрес, в BTC
+    fee_share: f64,
+    counterparties: Vec<String>,
+}
+
+/// Построитель признаков адреса по истории транзакций explorer'а
+///
+/// # Example
+/// ```no_run
+/// use diploma_software::application::services::address::address_features::AddressFeaturesBuilder;
+/// # fn example(history: Vec<diploma_software::services::explorer::models::chain::ChainTransaction>) {
+/// let features = AddressFeaturesBuilder::new("1JQgTsrc1ChRHn9pAnrXmuPnDVA4v6uQbf")
+///     .transactions(history)
+///     .time_step(47)
+///     .build();
+/// # }
+/// ```
+pub struct AddressFeaturesBuilder {
+    address: String,
+    transactions: Vec<ChainTransaction>,
+    time_step: Option<u8>,
+}
+
+impl AddressFeaturesBuilder {
+    /// Создает построитель для адреса
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            transactions: Vec::new(),
+            time_step: None,
+        }
+    }
+
+    /// Задает историю транзакций адреса
+    pub fn transactions(mut self, transactions: Vec<ChainTransaction>) -> Self {
+        self.transactions = transactions;
+        self
+    }
+
+    /// Ограничивает вычисление одним временным шагом, как в строках датасета
+    ///
+    /// Без ограничения используется вся история, а временным шагом
+    /// считается шаг последней транзакции.
+    pub fn time_step(mut self, time_step: u8) -> Self {
+        self.time_step = Some(time_step);
+        self
+    }
+
+    /// Вычисляет признаки адреса
+    pub fn build(self) -> AddressFeatures {
+        let address = self.address.as_str();
+        let mut participations: Vec<Participation> = self
+            .transactions
+            .iter()
+            .filter_map(|tx| participation(address, tx))
+            .collect();
+        participations.sort_by_key(|p| p.height);
+
+        let time_step = self.time_step.unwrap_or_else(|| {
+            participations
+                .last()
+                .map(|p| time_step_for_height(p.height))
+                .unwrap_or(1)
+        });
+        if self.time_step.is_some() {
+            participations.retain(|p| time_step_for_height(p.height) == time_step);
+        }
+
+        let sent: Vec<&Participation> = participations.iter().filter(|p| p.sent > Amount::ZERO).collect();
+        let received: Vec<&Participation> = participations.iter().filter(|p| p.received > Amount::ZERO).collect();
+
+        let first_block = participations.first().map(|p| p.height).unwrap_or(0);
+        let last_block = participations.last().map(|p| p.height).unwrap_or(0);
+        let time_steps: BTreeSet<u8> = participations
+            .iter()
+            .map(|p| time_step_for_height(p.height))
+            .collect();
+
+        let mut counterparty_counts: HashMap<&str, usize> = HashMap::new();
+        for p in &participations {
+            for counterparty in &p.counterparties {
+                *counterparty_counts.entry(counterparty.as_str()).or_default() += 1;
+            }
+        }
+        let transacted_w_address: Vec<f64> = counterparty_counts.values().map(|c| *c as f64).collect();
+
+        let mut features = vec![
+            sent.len() as f64,
+            received.len() as f64,
+            first_block as f64,
+            last_block as f64,
+            (last_block - first_block) as f64,
+            participations.len() as f64,
+            sent.first().map(|p| p.height).unwrap_or(0) as f64,
+            received.first().map(|p| p.height).unwrap_or(0) as f64,
+            time_steps.len() as f64,
+        ];
+        features.extend(summary(&participations.iter().map(|p| (p.sent + p.received).to_btc()).collect::<Vec<_>>()));
+        features.extend(summary(&sent.iter().map(|p| p.sent.to_btc()).collect::<Vec<_>>()));
+        features.extend(summary(&received.iter().map(|p| p.received.to_btc()).collect::<Vec<_>>()));
+        features.extend(summary(&participations.iter().map(|p| p.fee.to_btc()).collect::<Vec<_>>()));
+        features.extend(summary(&participations.iter().map(|p| p.fee_share).collect::<Vec<_>>()));
+        features.extend(summary(&block_gaps(participations.iter().map(|p| p.height))));
+        features.extend(summary(&block_gaps(sent.iter().map(|p| p.height))));
+        features.extend(summary(&block_gaps(received.iter().map(|p| p.height))));
+        features.push(counterparty_counts.values().filter(|c| **c > 1).count() as f64);
+        features.extend(summary(&transacted_w_address));
+
+        AddressFeatures {
+            address: self.address,
+            time_step,
+            features,
+        }
+    }
+}
+
+/// Описывает участие адреса в подтвержденной транзакции
+fn participation(address: &str, tx: &ChainTransaction) -> Option<Participation> {
+    let height = tx.status.block_height?;
+
+    let input_total = tx.input_value();
+    let sent: Amount = tx
+        .inputs
+        .iter()
+        .filter_map(|vin| vin.prevout.as_ref())
+        .filter(|p| p.address.as_deref() == Some(address))
+        .map(|p| Amount::from_sat(p.value))
+        .sum();
+    let received: Amount = tx
+        .outputs
+        .iter()
+        .filter(|v| v.address.as_deref() == Some(address))
+        .map(|v| Amount::from_sat(v.value))
+        .sum();
+    let is_sender = tx
+        .inputs
+        .iter()
+        .filter_map(|vin| vin.prevout.as_ref())
+        .any(|p| p.address.as_deref() == Some(address));
+    let is_receiver = tx.outputs.iter().any(|v| v.address.as_deref() == Some(address));
+    if !is_sender && !is_receiver {
+        return None;
+    }
+
+    // Контрагенты — адреса противоположной стороны транзакции
+    let mut counterparties: Vec<String> = Vec::new();
+    let mut push_counterparty = |candidate: Option<&str>| {
+        if let Some(candidate) = candidate {
+            if candidate != address && !counterparties.iter().any(|c| c == candidate) {
+                counterparties.push(candidate.to_string());
+            }
+        }
+    };
+    if is_sender {
+        tx.outputs.iter().for_each(|v| push_counterparty(v.address.as_deref()));
+    }
+    if is_receiver {
+        tx.inputs
+            .iter()
+            .filter_map(|vin| vin.prevout.as_ref())
+            .for_each(|p| push_counterparty(p.address.as_deref()));
+    }
+
+    let fee = Amount::from_sat(tx.fee.unwrap_or(0));
+    let own_value = sent.max(received);
+    let fee_share = if input_total > Amount::ZERO {
+        fee.to_btc() * own_value.to_sat() as f64 / input_total.to_sat() as f64
+    } else {
+        0.0
+    };
+
+    Some(Participation {
+        height,
+        sent,
+        received,
+        fee,
+        fee_share,
+        counterparties,
+    })
+}
+
+/// Интервалы в блоках между последовательными транзакциями
+fn block_gaps(heights: impl Iterator<Item = u64>) -> Vec<f64> {
+    let heights: Vec<u64> = heights.collect();
+    heights.windows(2).map(|w| (w[1] - w[0]) as f64).collect()
+}
+
+/// Сумма, минимум, максимум, среднее и медиана (нули для пустого набора)
+fn summary(values: &[f64]) -> [f64; 5] {
+    if values.is_empty() {
+        return [0.0; 5];
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let total: f64 = sorted.iter().sum();
+    let n = sorted.len();
+    let median = if n % 2 == 1 {
+        sorted[n / 2]
+    } else {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    };
+    [total, sorted[0], sorted[n - 1], total / n as f64, median]
+}
+
+/// Загружает историю адреса через explorer и вычисляет его признаки
+///
+/// # Arguments
+/// * `client` - Клиент Bitcoin explorer
+/// * `address` - Bitcoin адрес
+/// * `time_step` - Временной шаг, которым ограничивается история (если задан)
+///
+/// # Returns
+/// `Result<AddressFeatures, ExplorerError>` - признаки адреса или ошибка explorer'а
+pub async fn build_address_features(
+    client: &ExplorerClient,
+    address: &str,
+    time_step: Option<u8>,
+) -> Result<AddressFeatures, ExplorerError> {
+    let history = client
+        .get_address_transactions(address, MAX_ADDRESS_HISTORY_PAGES)
+        .await?;
+
+    let mut builder = AddressFeaturesBuilder::new(address).transactions(history);
+    if let Some(time_step) = time_step {
+        builder = builder.time_step(time_step);
+    }
+    Ok(builder.build())
+}
+
+/// Вычисляет признаки адреса через глобальный клиент explorer
+///
+/// # Arguments
+/// * `address` - Bitcoin адрес
+///
+/// # Returns
+/// `Result<AddressFeatures, Box<dyn std::error::Error>>` - признаки адреса
+pub async fn get_address_features(address: &str) -> Result<AddressFeatures, Box<dyn std::error::Error>> {
+    let client = get_or_init_client().await
+        .map_err(|e| format!("Failed to initialize explorer client: {}", e))?;
+    Ok(build_address_features(client, address, None).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::models::chain::{ChainInput, ChainOutput, ChainStatus};
+
+    fn vout(address: &str, value: u64) -> ChainOutput {
+        ChainOutput {
+            script_pubkey: String::new(),
+            address: Some(address.to_string()),
+            value,
+        }
+    }
+
+    fn tx(txid: &str, height: u64, inputs: &[(&str, u64)], outputs: &[(&str, u64)], fee: u64) -> ChainTransaction {
+        ChainTransaction {
+            txid: txid.to_string(),
+            inputs: inputs
+                .iter()
+                .map(|(address, value)| ChainInput {
+                    txid: "parent".to_string(),
+                    vout: 0,
+                    prevout: Some(vout(address, *value)),
+                    is_coinbase: false,
+                })
+                .collect(),
+            outputs: outputs.iter().map(|(address, value)| vout(address, *value)).collect(),
+            fee: Some(fee),
+            status: ChainStatus {
+                confirmed: true,
+                block_height: Some(height),
+                block_time: Some(1_500_000_000),
+            },
+        }
+    }
+
+    /// Транзакции из примера в `transaction_info.rs`
+    fn sample_history() -> Vec<ChainTransaction> {
+        vec![
+            tx(
+                "t1",
+                483_937,
+                &[("16HBDHsz3V8pW9nrFY29EsZRisXvfCyQCR", 13_821_900)],
+                &[
+                    ("15YYt1SAENYNzAPShaJD423KDEVnWmRrtX", 3_272_339),
+                    ("1JQgTsrc1ChRHn9pAnrXmuPnDVA4v6uQbf", 10_461_564),
+                ],
+                87_997,
+            ),
+            tx(
+                "t2",
+                483_939,
+                &[("15YYt1SAENYNzAPShaJD423KDEVnWmRrtX", 3_272_339)],
+                &[("1Recipient", 2_000_000), ("1Change", 1_184_343)],
+                87_996,
+            ),
+        ]
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-8, "{} != {}", actual, expected);
+    }
+
+    #[test]
+    fn test_feature_layout() {
+        assert_eq!(ADDRESS_FEATURE_COLUMNS.len(), 55);
+        assert_eq!(address_features_csv_header().len(), 58);
+
+        let features = AddressFeaturesBuilder::new("1JQgTsrc1ChRHn9pAnrXmuPnDVA4v6uQbf")
+            .transactions(sample_history())
+            .build();
+        assert_eq!(features.features.len(), ADDRESS_FEATURE_COUNT);
+        assert_eq!(features.to_vector().len(), ADDRESS_FEATURE_COUNT + 1);
+        assert_eq!(features.to_csv_record("3").len(), 58);
+    }
+
+    #[test]
+    fn test_sender_only_address_matches_dataset_row() {
+        let features = AddressFeaturesBuilder::new("16HBDHsz3V8pW9nrFY29EsZRisXvfCyQCR")
+            .transactions(sample_history())
+            .time_step(47)
+            .build();
+
+        let expected_head = [1.0, 0.0, 483937.0, 483937.0, 0.0, 1.0, 483937.0, 0.0, 1.0];
+        assert_eq!(&features.features[..9], &expected_head);
+        assert_close(features.get("btc_sent_total").unwrap(), 0.138219);
+        assert_close(features.get("btc_received_total").unwrap(), 0.0);
+        assert_close(features.get("fees_as_share_max").unwrap(), 0.00087997);
+        assert_close(features.get("transacted_w_address_total").unwrap(), 2.0);
+        assert_close(features.get("transacted_w_address_mean").unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_sender_and_receiver_address_matches_dataset_row() {
+        let features = AddressFeaturesBuilder::new("15YYt1SAENYNzAPShaJD423KDEVnWmRrtX")
+            .transactions(sample_history())
+            .build();
+
+        assert_eq!(features.time_step, 47);
+        let expected_head = [1.0, 1.0, 483937.0, 483939.0, 2.0, 2.0, 483939.0, 483937.0, 1.0];
+        assert_eq!(&features.features[..9], &expected_head);
+        assert_close(features.get("btc_transacted_total").unwrap(), 0.06544678);
+        assert_close(features.get("fees_total").unwrap(), 0.00175993);
+        assert_close(features.get("fees_min").unwrap(), 0.00087996);
+        assert_close(features.get("fees_as_share_min").unwrap(), 0.000208333163300993);
+        assert_close(features.get("blocks_btwn_txs_total").unwrap(), 2.0);
+        assert_close(features.get("blocks_btwn_input_txs_total").unwrap(), 0.0);
+        assert_close(features.get("transacted_w_address_total").unwrap(), 3.0);
+        assert_close(features.get("num_addr_transacted_multiple").unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_receiver_fee_share() {
+        let features = AddressFeaturesBuilder::new("1JQgTsrc1ChRHn9pAnrXmuPnDVA4v6uQbf")
+            .transactions(sample_history())
+            .build();
+
+        assert_close(features.get("fees_as_share_total").unwrap(), 0.000666034515738067);
+        assert_close(features.get("first_sent_block").unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_summary() {
+        assert_eq!(summary(&[]), [0.0; 5]);
+        assert_eq!(summary(&[3.0, 1.0, 2.0, 6.0]), [12.0, 1.0, 6.0, 3.0, 2.5]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::transaction::extended_features::{extract_extended_features, ExtendedFeatures};
+use crate::application::services::transaction::transaction_info::TransactionFeatures;
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::explorer_client::ExplorerClient;
+use crate::services::ml::errors::ml_errors::MLError;
+use crate::services::ml::ml_client::MLClient;
+use crate::services::ml::models::{MLRequest, MLResponse, PredictionClass};
+
+/// Вклад признака в предсказание
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeatureImportance {
+    pub feature: String,
+    pub importance: f64,
+}
+
+/// Вердикт модели по транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Verdict {
+    /// Класс транзакции
+    pub class: PredictionClass,
+    /// Уверенность модели (0.0 - 1.0)
+    pub confidence: f64,
+    /// Оценка риска (0.0 - 1.0)
+    pub risk_score: f64,
+    /// Текстовое объяснение
+    pub explanation: String,
+    /// Версия модели
+    pub model_version: String,
+    /// Важность признаков по убыванию
+    pub feature_importance: Vec<FeatureImportance>,
+}
+
+impl From<MLResponse> for Verdict {
+    fn from(response: MLResponse) -> Self {
+        let mut feature_importance: Vec<FeatureImportance> = response
+            .details
+            .feature_importance
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(feature, importance)| FeatureImportance { feature, importance })
+            .collect();
+        feature_importance.sort_by(|a, b| {
+            b.importance.total_cmp(&a.importance).then_with(|| a.feature.cmp(&b.feature))
+        });
+
+        Verdict {
+            class: response.prediction.class,
+            confidence: response.prediction.confidence,
+            risk_score: response.prediction.risk_score,
+            explanation: response.explanation,
+            model_version: response.details.model_version,
+            feature_importance,
+        }
+    }
+}
+
+/// Признаки транзакции, полученные из explorer'а
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransactionEvidence {
+    /// ID транзакции
+    pub txid: String,
+    /// Базовые признаки (суммы в сатоши)
+    pub transaction_features: TransactionFeatures,
+    /// Расширенные признаки
+    pub extended_features: ExtendedFeatures,
+}
+
+impl TransactionEvidence {
+    /// Признаки в виде «название — значение» для отображения
+    ///
+    /// Суммы выводятся в BTC, остальные значения — как есть.
+    pub fn feature_rows(&self) -> BTreeMap<String, String> {
+        let basic = &self.transaction_features;
+        let mut rows = BTreeMap::from([
+            ("n_inputs".to_string(), basic.n_inputs.to_string()),
+            ("n_outputs".to_string(), basic.n_outputs.to_string()),
+            ("input_value_sum".to_string(), basic.input_value_sum.to_string()),
+            ("output_value_sum".to_string(), basic.output_value_sum.to_string()),
+            ("transaction_fee".to_string(), basic.transaction_fee.to_string()),
+            ("avg_input_value".to_string(), format!("{} BTC", basic.avg_input_value)),
+            ("avg_output_value".to_string(), format!("{} BTC", basic.avg_output_value)),
+        ]);
+        if let Ok(serde_json::Value::Object(extended)) = serde_json::to_value(&self.extended_features) {
+            rows.extend(extended.into_iter().map(|(name, value)| (name, value.to_string())));
+        }
+        rows
+    }
+}
+
+/// Результат анализа транзакции: признаки и вердикт модели
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransactionAnalysis {
+    #[serde(flatten)]
+    pub evidence: TransactionEvidence,
+    pub verdict: Verdict,
+}
+
+/// Получает транзакцию через explorer и вычисляет ее признаки
+///
+/// # Arguments
+/// * `client` - Клиент Bitcoin explorer
+/// * `txid` - ID транзакции
+///
+/// # Returns
+/// `Result<TransactionEvidence, ExplorerError>` - признаки или ошибка explorer'а
+pub async fn collect_evidence(client: &ExplorerClient, txid: &str) -> Result<TransactionEvidence, ExplorerError> {
+    let tx = client.get_transaction(txid).await?;
+    let transaction_features = TransactionFeatures::from_transaction(&tx)?;
+    let extended_features = extract_extended_features(client, txid).await?;
+
+    Ok(TransactionEvidence {
+        txid: tx.txid,
+        transaction_features,
+        extended_features,
+    })
+}
+
+/// Запрашивает вердикт ML сервиса по собранным признакам
+///
+/// # Arguments
+/// * `ml` - Клиент ML сервиса
+/// * `evidence` - Признаки транзакции
+///
+/// # Returns
+/// `Result<Verdict, MLError>` - вердикт или ошибка ML сервиса
+pub async fn request_verdict(ml: &MLClient, evidence: &TransactionEvidence) -> Result<Verdict, MLError> {
+    let request = MLRequest::new(evidence.txid.clone(), &evidence.transaction_features)
+        .with_extended_features(evidence.extended_features.clone());
+    Ok(ml.analyze_transaction(&request).await?.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::ml::models::{Prediction, ResponseDetails};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_verdict_sorts_feature_importance() {
+        let response = MLResponse {
+            success: true,
+            transaction_id: "tx".to_string(),
+            prediction: Prediction { class: PredictionClass::Illicit, confidence: 0.9, risk_score: 0.8 },
+            explanation: "mixing".to_string(),
+            details: ResponseDetails {
+                model_version: "1.0.0".to_string(),
+                inference_time_ms: 1.0,
+                feature_importance: Some(HashMap::from([
+                    ("n_inputs".to_string(), 0.1),
+                    ("transaction_fee".to_string(), 0.3),
+                    ("outputs_address_entropy".to_string(), 0.2),
+                ])),
+            },
+        };
+
+        let verdict = Verdict::from(response);
+        let order: Vec<&str> = verdict.feature_importance.iter().map(|f| f.feature.as_str()).collect();
+        assert_eq!(order, vec!["transaction_fee", "outputs_address_entropy", "n_inputs"]);
+        assert_eq!(verdict.risk_score, 0.8);
+    }
+}
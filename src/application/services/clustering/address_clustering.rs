@@ -0,0 +1,332 @@
+use std::collections::HashMap;
+
+use bitcoin::Amount;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::clustering::union_find::UnionFind;
+use crate::application::services::graph::transaction_graph::{EdgeDirection, NodeId, TransactionGraph};
+use crate::application::services::transaction::change_detection::detect_change;
+use crate::application::services::transaction::coinjoin_detection::detect_coinjoin;
+use crate::infrastructure::config::Config;
+use crate::infrastructure::constants::DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS;
+use crate::services::explorer::models::chain::{ChainAddressStats, ChainInput, ChainOutput, ChainStatus, ChainTransaction};
+
+/// Параметры кластеризации адресов
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClusteringOptions {
+    /// Не объединять адреса входов транзакций, похожих на CoinJoin
+    pub exclude_coinjoin: bool,
+    /// Число выходов с одинаковой суммой, начиная с которого транзакция похожа на CoinJoin
+    pub coinjoin_min_equal_outputs: usize,
+    /// Транзакции с большим числом адресов входов считаются похожими на CoinJoin
+    pub max_input_addresses: Option<usize>,
+    /// Добавлять в кластер отправителя выход-сдачу ([`detect_change`])
+    pub use_change: bool,
+}
+
+impl Default for ClusteringOptions {
+    fn default() -> Self {
+        ClusteringOptions {
+            exclude_coinjoin: true,
+            coinjoin_min_equal_outputs: DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS,
+            max_input_addresses: None,
+            use_change: false,
+        }
+    }
+}
+
+impl ClusteringOptions {
+    /// Параметры из конфигурации (`CLUSTER_*`)
+    pub fn from_config(config: &Config) -> Self {
+        ClusteringOptions {
+            exclude_coinjoin: config.cluster_exclude_coinjoin,
+            coinjoin_min_equal_outputs: config.cluster_coinjoin_min_equal_outputs,
+            max_input_addresses: config.cluster_max_input_addresses,
+            use_change: config.cluster_change_heuristics,
+        }
+    }
+}
+
+/// Кластер адресов одного предполагаемого владельца
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cluster {
+    pub id: usize,
+    pub size: usize,
+    /// Адреса кластера в лексикографическом порядке
+    pub members: Vec<String>,
+}
+
+/// Результат кластеризации адресов графа
+///
+/// ID кластеров детерминированы: кластеры упорядочены по убыванию размера,
+/// при равном размере — по первому адресу. Адреса, не тратившиеся вместе
+/// с другими, образуют кластеры из одного адреса.
+#[derive(Debug, Clone, Default)]
+pub struct AddressClusters {
+    pub clusters: Vec<Cluster>,
+    /// Транзакции, исключенные фильтром CoinJoin
+    pub excluded_transactions: Vec<String>,
+    by_address: HashMap<String, usize>,
+}
+
+impl AddressClusters {
+    /// Кластер по ID
+    pub fn get(&self, id: usize) -> Option<&Cluster> {
+        self.clusters.get(id)
+    }
+
+    /// Кластер, в который входит адрес
+    pub fn cluster_of(&self, address: &str) -> Option<&Cluster> {
+        self.by_address.get(address).and_then(|&id| self.get(id))
+    }
+
+    /// Число кластеров, включая одноэлементные
+    pub fn len(&self) -> usize {
+        self.clusters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clusters.is_empty()
+    }
+}
+
+/// Проверяет, похожа ли транзакция на CoinJoin
+///
+/// Транзакция похожа на CoinJoin, если у нее несколько адресов входов и не
+/// меньше `coinjoin_min_equal_outputs` выходов с одинаковой суммой, если ее
+/// распознает [`detect_coinjoin`] с тем же порогом, либо если адресов входов больше
+/// `max_input_addresses`. В датасете Elliptic сумм нет, поэтому для него
+/// работает только ограничение числа входов.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+/// * `tx` - Узел-транзакция
+/// * `options` - Пороги фильтра
+///
+/// # Returns
+/// `bool` - true, если адреса входов транзакции не следует объединять
+pub fn is_coinjoin_like(graph: &TransactionGraph, tx: NodeIndex, options: &ClusteringOptions) -> bool {
+    let inputs = graph.input_addresses(tx).count();
+    if options.max_input_addresses.is_some_and(|max| inputs > max) {
+        return true;
+    }
+    if inputs < 2 {
+        return false;
+    }
+
+    let mut equal_outputs: HashMap<Amount, usize> = HashMap::new();
+    for edge in graph.edges(tx, Direction::Outgoing) {
+        if edge.weight().direction != EdgeDirection::Output {
+            continue;
+        }
+        if let Some(value) = edge.weight().value {
+            *equal_outputs.entry(value).or_default() += 1;
+        }
+    }
+    equal_outputs.values().any(|&count| count >= options.coinjoin_min_equal_outputs)
+        || graph_transaction(graph, tx).is_some_and(|(chain_tx, _)| detect_coinjoin(&chain_tx, options.coinjoin_min_equal_outputs).is_some())
+}
+
+/// Восстанавливает транзакцию и статистику адресов ее выходов по графу
+///
+/// Возвращает `None`, если у ребер транзакции нет сумм (датасет Elliptic):
+/// без сумм эвристики сдачи неприменимы.
+fn graph_transaction(graph: &TransactionGraph, tx: NodeIndex) -> Option<(ChainTransaction, HashMap<String, ChainAddressStats>)> {
+    let petgraph = graph.graph();
+    let output = |address: NodeIndex, value: Option<Amount>| {
+        value.map(|value| ChainOutput {
+            script_pubkey: String::new(),
+            address: Some(petgraph[address].id.key().to_string()),
+            value: value.to_sat(),
+        })
+    };
+
+    let mut inputs = Vec::new();
+    for edge in graph.edges(tx, Direction::Incoming).filter(|edge| edge.weight().direction == EdgeDirection::Input) {
+        let prevout = output(edge.source(), edge.weight().value)?;
+        inputs.push(ChainInput { txid: String::new(), vout: 0, prevout: Some(prevout), is_coinbase: false });
+    }
+    let mut outputs = Vec::new();
+    let mut address_stats = HashMap::new();
+    for edge in graph.edges(tx, Direction::Outgoing).filter(|edge| edge.weight().direction == EdgeDirection::Output) {
+        let address = edge.target();
+        outputs.push(output(address, edge.weight().value)?);
+
+        let funded_txo_count = graph.receiving_transactions(address).count() as u64;
+        let spent_txo_count = graph.spending_transactions(address).count() as u64;
+        let key = petgraph[address].id.key().to_string();
+        address_stats.insert(
+            key.clone(),
+            ChainAddressStats { address: key, funded_txo_count, spent_txo_count, tx_count: funded_txo_count + spent_txo_count },
+        );
+    }
+
+    let tx = ChainTransaction {
+        txid: petgraph[tx].id.key().to_string(),
+        inputs,
+        outputs,
+        fee: None,
+        status: ChainStatus::default(),
+    };
+    Some((tx, address_stats))
+}
+
+/// Кластеризует адреса по эвристике общего владения входами
+///
+/// Все адреса входов одной транзакции считаются принадлежащими одному
+/// владельцу и объединяются в кластер; при `exclude_coinjoin` транзакции,
+/// похожие на CoinJoin ([`is_coinjoin_like`]), адреса не объединяют.
+/// При `use_change` к кластеру входов добавляется адрес сдачи, если у
+/// ребер графа есть суммы.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+/// * `options` - Параметры кластеризации
+///
+/// # Returns
+/// `AddressClusters` - кластеры всех адресов графа
+pub fn cluster_addresses(graph: &TransactionGraph, options: &ClusteringOptions) -> AddressClusters {
+    let petgraph = graph.graph();
+    let addresses: Vec<NodeIndex> = petgraph.node_indices().filter(|&i| petgraph[i].id.is_address()).collect();
+    let position: HashMap<NodeIndex, usize> = addresses.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+
+    let mut sets = UnionFind::new(addresses.len());
+    let mut excluded_transactions = Vec::new();
+    for tx in petgraph.node_indices().filter(|&i| petgraph[i].id.is_transaction()) {
+        if options.exclude_coinjoin && is_coinjoin_like(graph, tx, options) {
+            excluded_transactions.push(petgraph[tx].id.key().to_string());
+            continue;
+        }
+        let mut inputs = graph.input_addresses(tx).map(|address| position[&address]);
+        let Some(first) = inputs.next() else {
+            continue;
+        };
+        for other in inputs {
+            sets.union(first, other);
+        }
+
+        if options.use_change {
+            let change = graph_transaction(graph, tx)
+                .and_then(|(chain_tx, address_stats)| detect_change(&chain_tx, Some(&address_stats)).change_output().cloned());
+            let change = change
+                .and_then(|output| output.address)
+                .and_then(|address| graph.index_of(&NodeId::address(address)));
+            if let Some(change) = change {
+                sets.union(first, position[&change]);
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<String>> = HashMap::new();
+    for (i, &node) in addresses.iter().enumerate() {
+        groups.entry(sets.find(i)).or_default().push(petgraph[node].id.key().to_string());
+    }
+    let mut members: Vec<Vec<String>> = groups
+        .into_values()
+        .map(|mut members| {
+            members.sort_unstable();
+            members
+        })
+        .collect();
+    members.sort_unstable_by(|a, b| b.len().cmp(&a.len()).then_with(|| a[0].cmp(&b[0])));
+
+    let mut by_address = HashMap::with_capacity(addresses.len());
+    let clusters = members
+        .into_iter()
+        .enumerate()
+        .map(|(id, members)| {
+            for address in &members {
+                by_address.insert(address.clone(), id);
+            }
+            Cluster { id, size: members.len(), members }
+        })
+        .collect();
+    excluded_transactions.sort_unstable();
+
+    AddressClusters { clusters, excluded_transactions, by_address }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::graph::transaction_graph::GraphEdge;
+
+    fn spend(graph: &mut TransactionGraph, tx: &str, inputs: &[&str], outputs: &[(&str, u64)]) {
+        for input in inputs {
+            graph.add_edge(NodeId::address(*input), NodeId::transaction(tx), GraphEdge::new(EdgeDirection::Input, None));
+        }
+        for (output, value) in outputs {
+            graph.add_edge(
+                NodeId::transaction(tx),
+                NodeId::address(*output),
+                GraphEdge::new(EdgeDirection::Output, Some(Amount::from_sat(*value))),
+            );
+        }
+    }
+
+    #[test]
+    fn test_co_spent_inputs_share_cluster() {
+        let mut graph = TransactionGraph::new();
+        spend(&mut graph, "t1", &["a", "b"], &[("x", 10)]);
+        spend(&mut graph, "t2", &["b", "c"], &[("y", 10)]);
+        // CoinJoin: три одинаковых выхода, входы не объединяются
+        spend(&mut graph, "cj", &["c", "d", "e"], &[("p", 5), ("q", 5), ("r", 5)]);
+
+        let clusters = cluster_addresses(&graph, &ClusteringOptions::default());
+        assert_eq!(clusters.excluded_transactions, vec!["cj"]);
+        let first = clusters.get(0).expect("Largest cluster");
+        assert_eq!(first.members, vec!["a", "b", "c"]);
+        assert_eq!(clusters.cluster_of("d").map(|c| c.size), Some(1));
+        assert_eq!(clusters.len(), 1 + 7);
+
+        let options = ClusteringOptions { exclude_coinjoin: false, ..ClusteringOptions::default() };
+        let clusters = cluster_addresses(&graph, &options);
+        assert!(clusters.excluded_transactions.is_empty());
+        assert_eq!(clusters.cluster_of("e").map(|c| c.id), Some(0));
+        assert_eq!(clusters.get(0).map(|c| c.size), Some(5));
+
+        let options = ClusteringOptions { max_input_addresses: Some(1), ..ClusteringOptions::default() };
+        assert_eq!(cluster_addresses(&graph, &options).excluded_transactions, vec!["cj", "t1", "t2"]);
+    }
+
+    #[test]
+    fn test_coinjoin_threshold_above_default() {
+        let mut graph = TransactionGraph::new();
+        let value = |sats| Some(Amount::from_sat(sats));
+        for input in ["a", "b", "c"] {
+            graph.add_edge(NodeId::address(input), NodeId::transaction("cj"), GraphEdge::new(EdgeDirection::Input, value(60_000)));
+        }
+        for output in ["p", "q", "r"] {
+            graph.add_edge(NodeId::transaction("cj"), NodeId::address(output), GraphEdge::new(EdgeDirection::Output, value(50_000)));
+        }
+
+        let clusters = cluster_addresses(&graph, &ClusteringOptions::default());
+        assert_eq!(clusters.excluded_transactions, vec!["cj"]);
+
+        // Порог 4: три одинаковых выхода уже не CoinJoin, в том числе для detect_coinjoin
+        let options = ClusteringOptions { coinjoin_min_equal_outputs: 4, ..ClusteringOptions::default() };
+        let clusters = cluster_addresses(&graph, &options);
+        assert!(clusters.excluded_transactions.is_empty());
+        assert_eq!(clusters.cluster_of("c").map(|c| c.size), Some(3));
+    }
+
+    #[test]
+    fn test_change_output_joins_sender_cluster() {
+        let mut graph = TransactionGraph::new();
+        let value = |sats| Some(Amount::from_sat(sats));
+        graph.add_edge(NodeId::address("f"), NodeId::transaction("t"), GraphEdge::new(EdgeDirection::Input, value(1_000_000)));
+        // Круглая сумма 0.005 BTC — платеж, 490 000 сатоши — сдача
+        graph.add_edge(NodeId::transaction("t"), NodeId::address("g"), GraphEdge::new(EdgeDirection::Output, value(500_000)));
+        graph.add_edge(NodeId::transaction("t"), NodeId::address("h"), GraphEdge::new(EdgeDirection::Output, value(490_000)));
+
+        let clusters = cluster_addresses(&graph, &ClusteringOptions::default());
+        assert_eq!(clusters.get(0).map(|c| c.size), Some(1));
+
+        let options = ClusteringOptions { use_change: true, ..ClusteringOptions::default() };
+        let clusters = cluster_addresses(&graph, &options);
+        assert_eq!(clusters.get(0).map(|c| c.members.clone()), Some(vec!["f".to_string(), "h".to_string()]));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod union_find;
+pub mod address_clustering;
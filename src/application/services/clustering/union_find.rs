@@ -0,0 +1,81 @@
+/// Система непересекающихся множеств над элементами `0..len`
+///
+/// Сжатие путей и объединение по размеру дают почти константное время
+/// операций, поэтому кластеризация всего датасета линейна по числу ребер.
+#[derive(Debug, Clone, Default)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Создает `len` одноэлементных множеств
+    pub fn new(len: usize) -> Self {
+        UnionFind { parent: (0..len).collect(), size: vec![1; len] }
+    }
+
+    /// Число элементов
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Представитель множества элемента
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Сжатие пути: все элементы пути указывают сразу на корень
+        let mut current = element;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Объединяет множества двух элементов
+    ///
+    /// # Returns
+    /// `bool` - true, если элементы были в разных множествах
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) = if self.size[a] >= self.size[b] { (a, b) } else { (b, a) };
+        self.parent[small] = large;
+        self.size[large] += self.size[small];
+        true
+    }
+
+    /// Размер множества элемента
+    pub fn set_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_merges_sets() {
+        let mut sets = UnionFind::new(5);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(3, 4));
+        assert!(sets.union(1, 4));
+        assert!(!sets.union(0, 3));
+
+        assert_eq!(sets.find(0), sets.find(4));
+        assert_ne!(sets.find(0), sets.find(2));
+        assert_eq!(sets.set_size(3), 4);
+        assert_eq!(sets.set_size(2), 1);
+    }
+}
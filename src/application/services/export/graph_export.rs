@@ -0,0 +1,512 @@
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use bitcoin::Amount;
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::application::services::clustering::address_clustering::{cluster_addresses, AddressClusters, ClusteringOptions};
+use crate::application::services::graph::risk_propagation::{propagate_risk, PropagationMethod, RiskScores};
+use crate::application::services::graph::transaction_graph::{EdgeDirection, GraphEdge, NodeId, TransactionGraph};
+use crate::application::services::tracing::taint_tracing::TaintTrace;
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+use crate::infrastructure::dataset::schema::Class;
+
+/// Формат экспорта графа
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// GraphML (Gephi, yEd, NetworkX)
+    #[default]
+    Graphml,
+    /// GEXF 1.3 (Gephi)
+    Gexf,
+    /// Graphviz DOT
+    Dot,
+    /// Cytoscape.js JSON (`elements`)
+    Cytoscape,
+}
+
+impl ExportFormat {
+    /// Расширение файла
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Graphml => "graphml",
+            ExportFormat::Gexf => "gexf",
+            ExportFormat::Dot => "dot",
+            ExportFormat::Cytoscape => "cyjs",
+        }
+    }
+
+    /// MIME тип содержимого
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Graphml | ExportFormat::Gexf => "application/xml; charset=utf-8",
+            ExportFormat::Dot => "text/vnd.graphviz; charset=utf-8",
+            ExportFormat::Cytoscape => "application/json",
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "graphml" => Ok(ExportFormat::Graphml),
+            "gexf" => Ok(ExportFormat::Gexf),
+            "dot" | "gv" => Ok(ExportFormat::Dot),
+            "cytoscape" | "cyjs" => Ok(ExportFormat::Cytoscape),
+            other => Err(format!("unknown export format {}: expected graphml, gexf, dot or cytoscape", other)),
+        }
+    }
+}
+
+/// Узел экспортируемого графа
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportNode {
+    pub id: NodeId,
+    pub class: Option<Class>,
+    /// Временной шаг датасета Elliptic (для адреса — первый)
+    pub time_step: Option<u8>,
+    /// Время блока (unix timestamp) для транзакций из explorer'а
+    pub timestamp: Option<u64>,
+    /// Кластер общего владения (только адреса)
+    pub cluster: Option<usize>,
+    /// Распространенный риск
+    pub risk: Option<f64>,
+}
+
+/// Ребро экспортируемого графа
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportEdge {
+    pub source: NodeId,
+    pub target: NodeId,
+    pub direction: EdgeDirection,
+    pub value: Option<Amount>,
+    /// Загрязненная сумма (граф трассировки)
+    pub tainted: Option<Amount>,
+}
+
+/// Граф с атрибутами узлов и ребер для экспорта во внешние инструменты
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExportGraph {
+    pub nodes: Vec<ExportNode>,
+    pub edges: Vec<ExportEdge>,
+}
+
+/// Значение атрибута
+enum Attribute {
+    Text(String),
+    Integer(u64),
+    Float(f64),
+}
+
+impl fmt::Display for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Attribute::Text(text) => write!(f, "{}", text),
+            Attribute::Integer(value) => write!(f, "{}", value),
+            Attribute::Float(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl Attribute {
+    fn to_json(&self) -> Value {
+        match self {
+            Attribute::Text(text) => Value::from(text.as_str()),
+            Attribute::Integer(value) => Value::from(*value),
+            Attribute::Float(value) => Value::from(*value),
+        }
+    }
+}
+
+/// Атрибуты узлов и их типы (одинаковые имена типов в GraphML и GEXF)
+const NODE_ATTRIBUTES: [(&str, &str); 6] = [
+    ("kind", "string"),
+    ("class", "string"),
+    ("time_step", "long"),
+    ("timestamp", "long"),
+    ("cluster", "long"),
+    ("risk", "double"),
+];
+
+/// Атрибуты ребер и их типы
+const EDGE_ATTRIBUTES: [(&str, &str); 3] = [("direction", "string"), ("value", "long"), ("tainted", "long")];
+
+impl ExportNode {
+    fn new(id: NodeId, class: Option<Class>) -> Self {
+        ExportNode { id, class, time_step: None, timestamp: None, cluster: None, risk: None }
+    }
+
+    /// Заданные атрибуты узла
+    fn attributes(&self) -> Vec<(&'static str, Attribute)> {
+        let kind = if self.id.is_address() { "address" } else { "transaction" };
+        [
+            Some(("kind", Attribute::Text(kind.to_string()))),
+            self.class.map(|class| ("class", Attribute::Text(class.to_string()))),
+            self.time_step.map(|step| ("time_step", Attribute::Integer(step as u64))),
+            self.timestamp.map(|time| ("timestamp", Attribute::Integer(time))),
+            self.cluster.map(|cluster| ("cluster", Attribute::Integer(cluster as u64))),
+            self.risk.map(|risk| ("risk", Attribute::Float(risk))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl ExportEdge {
+    /// Заданные атрибуты ребра
+    fn attributes(&self) -> Vec<(&'static str, Attribute)> {
+        let direction = match self.direction {
+            EdgeDirection::Input => "input",
+            EdgeDirection::Output => "output",
+            EdgeDirection::Flow => "flow",
+        };
+        [
+            Some(("direction", Attribute::Text(direction.to_string()))),
+            self.value.map(|value| ("value", Attribute::Integer(value.to_sat()))),
+            self.tainted.map(|tainted| ("tainted", Attribute::Integer(tainted.to_sat()))),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+impl ExportGraph {
+    /// Узлы, ребра, классы и суммы графа адресов и транзакций
+    pub fn from_graph(graph: &TransactionGraph) -> Self {
+        let petgraph = graph.graph();
+        let nodes = petgraph.node_weights().map(|node| ExportNode::new(node.id.clone(), node.class)).collect();
+        let edges = petgraph
+            .edge_references()
+            .map(|edge| ExportEdge {
+                source: petgraph[edge.source()].id.clone(),
+                target: petgraph[edge.target()].id.clone(),
+                direction: edge.weight().direction,
+                value: edge.weight().value,
+                tainted: None,
+            })
+            .collect();
+        ExportGraph { nodes, edges }
+    }
+
+    /// Проставляет адресам кластеры общего владения
+    pub fn set_clusters(&mut self, clusters: &AddressClusters) {
+        for node in self.nodes.iter_mut().filter(|node| node.id.is_address()) {
+            node.cluster = clusters.cluster_of(node.id.key()).map(|cluster| cluster.id);
+        }
+    }
+
+    /// Проставляет узлам распространенный риск
+    pub fn set_risk(&mut self, scores: &RiskScores) {
+        for node in &mut self.nodes {
+            node.risk = scores.get(&node.id).and_then(|node| node.risk);
+        }
+    }
+
+    /// Сериализует граф в заданном формате
+    pub fn render(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Graphml => self.to_graphml(),
+            ExportFormat::Gexf => self.to_gexf(),
+            ExportFormat::Dot => self.to_dot(),
+            ExportFormat::Cytoscape => self.to_cytoscape(),
+        }
+    }
+
+    fn to_graphml(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        for (scope, attributes) in [("node", &NODE_ATTRIBUTES[..]), ("edge", &EDGE_ATTRIBUTES[..])] {
+            for (name, kind) in attributes {
+                let _ = writeln!(out, "  <key id=\"{0}\" for=\"{1}\" attr.name=\"{0}\" attr.type=\"{2}\"/>", name, scope, kind);
+            }
+        }
+        out.push_str("  <graph id=\"G\" edgedefault=\"directed\">\n");
+        for node in &self.nodes {
+            let _ = writeln!(out, "    <node id=\"{}\">", xml_escape(&node.id.to_string()));
+            for (name, value) in node.attributes() {
+                let _ = writeln!(out, "      <data key=\"{}\">{}</data>", name, xml_escape(&value.to_string()));
+            }
+            out.push_str("    </node>\n");
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+                i,
+                xml_escape(&edge.source.to_string()),
+                xml_escape(&edge.target.to_string())
+            );
+            for (name, value) in edge.attributes() {
+                let _ = writeln!(out, "      <data key=\"{}\">{}</data>", name, xml_escape(&value.to_string()));
+            }
+            out.push_str("    </edge>\n");
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    fn to_gexf(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str("<gexf xmlns=\"http://gexf.net/1.3\" version=\"1.3\">\n");
+        out.push_str("  <graph defaultedgetype=\"directed\" mode=\"static\">\n");
+        for (scope, attributes) in [("node", &NODE_ATTRIBUTES[..]), ("edge", &EDGE_ATTRIBUTES[..])] {
+            let _ = writeln!(out, "    <attributes class=\"{}\">", scope);
+            for (name, kind) in attributes {
+                let _ = writeln!(out, "      <attribute id=\"{0}\" title=\"{0}\" type=\"{1}\"/>", name, kind);
+            }
+            out.push_str("    </attributes>\n");
+        }
+        let attvalues = |out: &mut String, attributes: Vec<(&str, Attribute)>| {
+            out.push_str("        <attvalues>\n");
+            for (name, value) in attributes {
+                let _ = writeln!(out, "          <attvalue for=\"{}\" value=\"{}\"/>", name, xml_escape(&value.to_string()));
+            }
+            out.push_str("        </attvalues>\n");
+        };
+
+        out.push_str("    <nodes>\n");
+        for node in &self.nodes {
+            let _ = writeln!(
+                out,
+                "      <node id=\"{}\" label=\"{}\">",
+                xml_escape(&node.id.to_string()),
+                xml_escape(node.id.key())
+            );
+            attvalues(&mut out, node.attributes());
+            out.push_str("      </node>\n");
+        }
+        out.push_str("    </nodes>\n    <edges>\n");
+        for (i, edge) in self.edges.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "      <edge id=\"{}\" source=\"{}\" target=\"{}\">",
+                i,
+                xml_escape(&edge.source.to_string()),
+                xml_escape(&edge.target.to_string())
+            );
+            attvalues(&mut out, edge.attributes());
+            out.push_str("      </edge>\n");
+        }
+        out.push_str("    </edges>\n  </graph>\n</gexf>\n");
+        out
+    }
+
+    fn to_dot(&self) -> String {
+        let attributes = |attributes: Vec<(&str, Attribute)>| -> Vec<String> {
+            attributes
+                .into_iter()
+                .map(|(name, value)| match value {
+                    Attribute::Text(text) => format!("{}={}", name, dot_quote(&text)),
+                    other => format!("{}={}", name, other),
+                })
+                .collect()
+        };
+
+        let mut out = String::from("digraph transactions {\n");
+        for node in &self.nodes {
+            let shape = if node.id.is_address() { "ellipse" } else { "box" };
+            let mut list = vec![format!("label={}", dot_quote(node.id.key())), format!("shape={}", shape)];
+            list.extend(attributes(node.attributes()));
+            let _ = writeln!(out, "  {} [{}];", dot_quote(&node.id.to_string()), list.join(", "));
+        }
+        for edge in &self.edges {
+            let _ = writeln!(
+                out,
+                "  {} -> {} [{}];",
+                dot_quote(&edge.source.to_string()),
+                dot_quote(&edge.target.to_string()),
+                attributes(edge.attributes()).join(", ")
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn to_cytoscape(&self) -> String {
+        let data = |base: Vec<(&str, Value)>, attributes: Vec<(&str, Attribute)>| -> Value {
+            let mut data: Map<String, Value> = base.into_iter().map(|(name, value)| (name.to_string(), value)).collect();
+            for (name, value) in attributes {
+                data.insert(name.to_string(), value.to_json());
+            }
+            json!({ "data": data })
+        };
+
+        let nodes: Vec<Value> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let base = vec![("id", Value::from(node.id.to_string())), ("label", Value::from(node.id.key()))];
+                data(base, node.attributes())
+            })
+            .collect();
+        let edges: Vec<Value> = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(i, edge)| {
+                let base = vec![
+                    ("id", Value::from(format!("e{}", i))),
+                    ("source", Value::from(edge.source.to_string())),
+                    ("target", Value::from(edge.target.to_string())),
+                ];
+                data(base, edge.attributes())
+            })
+            .collect();
+        json!({ "elements": { "nodes": nodes, "edges": edges } }).to_string()
+    }
+}
+
+/// Экранирует строку для XML текста и атрибутов
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Строка DOT в кавычках
+fn dot_quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Граф папки с данными со всеми атрибутами: классы, временные шаги,
+/// кластеры общего владения и распространенный риск
+///
+/// # Arguments
+/// * `folder` - Путь к папке с CSV файлами
+/// * `clustering` - Параметры кластеризации
+///
+/// # Returns
+/// `Result<ExportGraph, SchemaError>` - граф или ошибка чтения/схемы файлов
+pub fn export_folder(folder: &Path, clustering: &ClusteringOptions) -> Result<ExportGraph, SchemaError> {
+    Ok(export_dataset_graph(&TransactionGraph::load(folder)?, clustering))
+}
+
+/// Загруженный граф папки с данными со всеми атрибутами
+///
+/// # Arguments
+/// * `graph` - Граф, загруженный [`TransactionGraph::load`]
+/// * `clustering` - Параметры кластеризации
+///
+/// # Returns
+/// `ExportGraph` - граф для экспорта
+pub fn export_dataset_graph(graph: &TransactionGraph, clustering: &ClusteringOptions) -> ExportGraph {
+    let mut export = ExportGraph::from_graph(graph);
+    export.set_clusters(&cluster_addresses(graph, clustering));
+    export.set_risk(&propagate_risk(graph, PropagationMethod::default()));
+    for node in &mut export.nodes {
+        node.time_step = graph.time_step(&node.id);
+    }
+    export
+}
+
+/// Подграф трассировки средств: выходы транзакций на адреса и траты этих
+/// выходов, с суммами, загрязненными суммами, временем блоков и кластерами
+///
+/// # Arguments
+/// * `trace` - Результат трассировки
+/// * `clustering` - Параметры кластеризации адресов подграфа
+///
+/// # Returns
+/// `ExportGraph` - граф для экспорта
+pub fn export_trace(trace: &TaintTrace, clustering: &ClusteringOptions) -> ExportGraph {
+    let mut graph = TransactionGraph::new();
+    let mut tainted: HashMap<(NodeId, NodeId), Amount> = HashMap::new();
+    for edge in &trace.edges {
+        let tx = NodeId::transaction(&edge.txid);
+        graph.add_node(tx.clone());
+        let Some(address) = edge.address.as_ref().map(NodeId::address) else {
+            // Выход без адреса: связь с тратящей транзакцией напрямую
+            if let Some(spender) = &edge.spent_by {
+                graph.add_edge(tx, NodeId::transaction(spender), GraphEdge::new(EdgeDirection::Flow, Some(edge.value)));
+            }
+            continue;
+        };
+        graph.add_edge(tx.clone(), address.clone(), GraphEdge::new(EdgeDirection::Output, Some(edge.value)));
+        *tainted.entry((tx, address.clone())).or_insert(Amount::ZERO) += edge.tainted;
+        if let Some(spender) = &edge.spent_by {
+            let spender = NodeId::transaction(spender);
+            graph.add_edge(address.clone(), spender.clone(), GraphEdge::new(EdgeDirection::Input, Some(edge.value)));
+            *tainted.entry((address, spender)).or_insert(Amount::ZERO) += edge.tainted;
+        }
+    }
+
+    let mut export = ExportGraph::from_graph(&graph);
+    export.set_clusters(&cluster_addresses(&graph, clustering));
+    let times: HashMap<NodeId, u64> = trace
+        .transactions
+        .iter()
+        .filter_map(|tx| tx.block_time.map(|time| (NodeId::transaction(&tx.txid), time)))
+        .collect();
+    for node in &mut export.nodes {
+        node.timestamp = times.get(&node.id).copied();
+    }
+    for edge in &mut export.edges {
+        edge.tainted = tainted.get(&(edge.source.clone(), edge.target.clone())).copied();
+    }
+    export
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_graph() -> ExportGraph {
+        let mut graph = TransactionGraph::new();
+        graph.add_edge(NodeId::address("1A&B"), NodeId::transaction("101"), GraphEdge::new(EdgeDirection::Input, None));
+        graph.add_edge(
+            NodeId::transaction("101"),
+            NodeId::address("1C"),
+            GraphEdge::new(EdgeDirection::Output, Some(Amount::from_sat(5000))),
+        );
+        graph.set_class(&NodeId::transaction("101"), Class::Illicit);
+        let mut export = ExportGraph::from_graph(&graph);
+        export.nodes[1].time_step = Some(7);
+        export
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let graph = sample_graph();
+
+        let graphml = graph.render(ExportFormat::Graphml);
+        assert!(graphml.contains("<node id=\"address:1A&amp;B\">"));
+        assert!(graphml.contains("<data key=\"class\">illicit</data>"));
+        assert!(graphml.contains("<data key=\"value\">5000</data>"));
+
+        let gexf = graph.render(ExportFormat::Gexf);
+        assert!(gexf.contains("<attvalue for=\"time_step\" value=\"7\"/>"));
+        assert_eq!(gexf.matches("<edge id=").count(), 2);
+
+        let dot = graph.render(ExportFormat::Dot);
+        assert!(dot.contains("\"tx:101\" -> \"address:1C\" [direction=\"output\", value=5000];"));
+
+        let cytoscape: Value = serde_json::from_str(&graph.render(ExportFormat::Cytoscape)).expect("Valid JSON");
+        assert_eq!(cytoscape["elements"]["nodes"][1]["data"]["class"], "illicit");
+        assert_eq!(cytoscape["elements"]["edges"][1]["data"]["source"], "tx:101");
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("GraphML".parse::<ExportFormat>(), Ok(ExportFormat::Graphml));
+        assert_eq!("cyjs".parse::<ExportFormat>(), Ok(ExportFormat::Cytoscape));
+        assert!("csv".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::{HashMap, VecDeque};
+
+use bitcoin::Amount;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::graph::transaction_graph::{EdgeDirection, GraphEdge, NodeId, TransactionGraph};
+use crate::infrastructure::constants::{DEFAULT_EGO_MAX_EDGES, DEFAULT_EGO_MAX_NODES, DEFAULT_EGO_RADIUS};
+use crate::infrastructure::dataset::schema::Class;
+
+/// Параметры выделения окрестности узла
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EgoOptions {
+    /// Радиус окрестности в ребрах графа адресов и транзакций
+    pub radius: usize,
+    /// Максимальное число узлов; ближние узлы добавляются первыми
+    pub max_nodes: usize,
+    /// Максимальное число ребер
+    pub max_edges: usize,
+    /// Степень, начиная с которой узел (кроме центра) не раскрывается;
+    /// `None` — раскрывать все узлы
+    pub hub_degree: Option<usize>,
+}
+
+impl Default for EgoOptions {
+    fn default() -> Self {
+        EgoOptions {
+            radius: DEFAULT_EGO_RADIUS,
+            max_nodes: DEFAULT_EGO_MAX_NODES,
+            max_edges: DEFAULT_EGO_MAX_EDGES,
+            hub_degree: None,
+        }
+    }
+}
+
+/// Узел окрестности
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EgoNode {
+    #[serde(flatten)]
+    pub id: NodeId,
+    pub class: Option<Class>,
+    /// Расстояние от центра в ребрах
+    pub distance: usize,
+}
+
+/// Ребро окрестности
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EgoEdge {
+    pub from: NodeId,
+    pub to: NodeId,
+    pub direction: EdgeDirection,
+    /// Сумма перевода, если известна
+    #[serde(with = "bitcoin::amount::serde::as_sat::opt")]
+    pub value: Option<Amount>,
+}
+
+/// Свернутый хаб: узел с большой степенью, соседи которого не раскрыты
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CollapsedHub {
+    #[serde(flatten)]
+    pub id: NodeId,
+    /// Степень узла в полном графе
+    pub degree: usize,
+    /// Число соседей, не попавших в окрестность
+    pub hidden: usize,
+}
+
+/// Окрестность узла графа
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EgoNetwork {
+    pub center: NodeId,
+    pub radius: usize,
+    /// Узлы в порядке удаления от центра
+    pub nodes: Vec<EgoNode>,
+    pub edges: Vec<EgoEdge>,
+    pub hubs: Vec<CollapsedHub>,
+    /// Окрестность обрезана ограничением числа узлов или ребер
+    pub truncated: bool,
+}
+
+impl EgoNetwork {
+    /// Окрестность в виде графа адресов и транзакций с классами узлов
+    pub fn subgraph(&self) -> TransactionGraph {
+        let mut graph = TransactionGraph::new();
+        for node in &self.nodes {
+            graph.add_node(node.id.clone());
+            if let Some(class) = node.class {
+                graph.set_class(&node.id, class);
+            }
+        }
+        for edge in &self.edges {
+            graph.add_edge(edge.from.clone(), edge.to.clone(), GraphEdge::new(edge.direction, edge.value));
+        }
+        graph
+    }
+}
+
+/// Степень узла: число входящих и исходящих ребер
+fn degree(graph: &TransactionGraph, node: NodeIndex) -> usize {
+    let petgraph = graph.graph();
+    petgraph.edges_directed(node, Direction::Incoming).count() + petgraph.edges_directed(node, Direction::Outgoing).count()
+}
+
+/// Выделяет окрестность узла: узлы на расстоянии не больше `radius` ребер
+/// без учета направления и ребра между ними
+///
+/// Обход идет в ширину, поэтому при ограничении числа узлов остаются
+/// ближайшие к центру. Узлы со степенью от `hub_degree` попадают в
+/// окрестность, но их соседи через них не добавляются.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+/// * `center` - Центральный узел
+/// * `options` - Радиус, ограничения и порог хабов
+///
+/// # Returns
+/// `Option<EgoNetwork>` - окрестность или `None`, если узла нет в графе
+pub fn ego_network(graph: &TransactionGraph, center: &NodeId, options: &EgoOptions) -> Option<EgoNetwork> {
+    let start = graph.index_of(center)?;
+    let petgraph = graph.graph();
+
+    let mut distances: HashMap<NodeIndex, usize> = HashMap::from([(start, 0)]);
+    let mut order = vec![start];
+    let mut hubs = Vec::new();
+    let mut truncated = false;
+    let mut queue = VecDeque::from([start]);
+    'bfs: while let Some(current) = queue.pop_front() {
+        let distance = distances[&current];
+        if distance == options.radius {
+            continue;
+        }
+        let current_degree = degree(graph, current);
+        if current != start && options.hub_degree.is_some_and(|hub_degree| current_degree >= hub_degree) {
+            hubs.push((current, current_degree));
+            continue;
+        }
+        for next in petgraph.neighbors_undirected(current) {
+            if distances.contains_key(&next) {
+                continue;
+            }
+            if order.len() >= options.max_nodes {
+                truncated = true;
+                break 'bfs;
+            }
+            distances.insert(next, distance + 1);
+            order.push(next);
+            queue.push_back(next);
+        }
+    }
+
+    let mut edges = Vec::new();
+    'edges: for &node in &order {
+        for edge in petgraph.edges_directed(node, Direction::Outgoing) {
+            if !distances.contains_key(&edge.target()) {
+                continue;
+            }
+            // Ребро показывается, если оба конца в окрестности, даже если
+            // обход по нему не шел (например, между двумя хабами)
+            if edges.len() >= options.max_edges {
+                truncated = true;
+                break 'edges;
+            }
+            edges.push(EgoEdge {
+                from: petgraph[node].id.clone(),
+                to: petgraph[edge.target()].id.clone(),
+                direction: edge.weight().direction,
+                value: edge.weight().value,
+            });
+        }
+    }
+
+    let hubs = hubs
+        .into_iter()
+        .map(|(hub, degree)| {
+            let shown = petgraph.neighbors_undirected(hub).filter(|next| distances.contains_key(next)).count();
+            CollapsedHub { id: petgraph[hub].id.clone(), degree, hidden: degree.saturating_sub(shown) }
+        })
+        .collect();
+    let nodes = order
+        .iter()
+        .map(|&node| EgoNode { id: petgraph[node].id.clone(), class: petgraph[node].class, distance: distances[&node] })
+        .collect();
+    Some(EgoNetwork { center: center.clone(), radius: options.radius, nodes, edges, hubs, truncated })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1A → t1 → 1B → t2 → 1C..1G; t2 — хаб степени 6
+    fn sample_graph() -> TransactionGraph {
+        let mut graph = TransactionGraph::new();
+        graph.add_edge(NodeId::address("1A"), NodeId::transaction("t1"), GraphEdge::new(EdgeDirection::Input, None));
+        graph.add_edge(NodeId::transaction("t1"), NodeId::address("1B"), GraphEdge::new(EdgeDirection::Output, None));
+        graph.add_edge(NodeId::address("1B"), NodeId::transaction("t2"), GraphEdge::new(EdgeDirection::Input, None));
+        for output in ["1C", "1D", "1E", "1F", "1G"] {
+            let value = Some(Amount::from_sat(1000));
+            graph.add_edge(NodeId::transaction("t2"), NodeId::address(output), GraphEdge::new(EdgeDirection::Output, value));
+        }
+        graph.set_class(&NodeId::transaction("t1"), Class::Illicit);
+        graph
+    }
+
+    #[test]
+    fn test_ego_network_radius_and_hubs() {
+        let graph = sample_graph();
+        let center = NodeId::address("1B");
+
+        let ego = ego_network(&graph, &center, &EgoOptions { radius: 1, ..EgoOptions::default() }).expect("Center exists");
+        let ids: Vec<&str> = ego.nodes.iter().map(|node| node.id.key()).collect();
+        assert_eq!(ids, ["1B", "t2", "t1"]);
+        assert_eq!(ego.nodes[2].class, Some(Class::Illicit));
+        assert_eq!(ego.edges.len(), 2);
+
+        let ego = ego_network(&graph, &center, &EgoOptions::default()).expect("Center exists");
+        assert_eq!((ego.nodes.len(), ego.edges.len(), ego.truncated), (9, 8, false));
+        assert_eq!(ego.subgraph().edge_count(), 8);
+
+        let options = EgoOptions { hub_degree: Some(5), ..EgoOptions::default() };
+        let ego = ego_network(&graph, &center, &options).expect("Center exists");
+        assert_eq!(ego.nodes.len(), 4);
+        assert_eq!(ego.hubs, vec![CollapsedHub { id: NodeId::transaction("t2"), degree: 6, hidden: 5 }]);
+
+        let options = EgoOptions { max_nodes: 2, ..EgoOptions::default() };
+        let ego = ego_network(&graph, &center, &options).expect("Center exists");
+        assert_eq!((ego.nodes.len(), ego.truncated), (2, true));
+
+        assert!(ego_network(&graph, &NodeId::address("1Z"), &EgoOptions::default()).is_none());
+    }
+}
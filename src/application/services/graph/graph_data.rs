@@ -0,0 +1,459 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::clustering::address_clustering::{cluster_addresses, AddressClusters, ClusteringOptions};
+use crate::application::services::graph::ego_network::EgoNetwork;
+use crate::application::services::graph::graph_metrics::{compute_selected_metrics, CommunityAlgorithm, GraphMetrics, MetricSelection};
+use crate::application::services::graph::path_finding::AddressPath;
+use crate::application::services::graph::risk_propagation::{propagate_risk, PropagationMethod, RiskScores};
+use crate::application::services::graph::transaction_graph::{NodeId, TransactionGraph};
+use crate::infrastructure::constants::{
+    EGO_CENTER_COLOR, EGO_HUB_COLOR, NODE_MAX_SIZE, NODE_MIN_SIZE, PATH_HIGHLIGHT_COLOR,
+};
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+use crate::infrastructure::dataset::schema::Class;
+
+/// Стиль узла в формате AnyChart
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NormalNode {
+    pub fill: String,
+}
+
+/// Узел графа: адрес или транзакция
+///
+/// ID узла-адреса — сам адрес, узла-транзакции — `tx:{ID транзакции}`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Node {
+    pub id: String,
+    /// Размер узла; по умолчанию — размер AnyChart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<f64>,
+    pub normal: NormalNode,
+}
+
+impl Node {
+    /// Узел графа адресов и транзакций, соответствующий узлу AnyChart
+    pub fn node_id(&self) -> NodeId {
+        match self.id.strip_prefix("tx:") {
+            Some(tx_id) => NodeId::transaction(tx_id),
+            None => NodeId::address(self.id.as_str()),
+        }
+    }
+}
+
+/// ID узла AnyChart для узла графа адресов и транзакций
+fn chart_id(id: &NodeId) -> String {
+    match id {
+        NodeId::Address(address) => address.clone(),
+        NodeId::Transaction(_) => id.to_string(),
+    }
+}
+
+/// Ребро графа: вход или выход транзакции `id`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub id: String,
+    pub normal: NormalEdge,
+}
+
+/// Стиль ребра в формате AnyChart
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NormalEdge {
+    pub stroke: StrokeEdge,
+}
+
+/// Цвет и толщина линии ребра
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StrokeEdge {
+    pub color: String,
+    /// Толщина линии; по умолчанию — толщина AnyChart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thickness: Option<u32>,
+}
+
+/// Данные графа адресов и транзакций для страницы анализа
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct GraphData {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+/// Способ раскраски узлов-адресов
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeColoring {
+    /// По классу адреса из датасета
+    #[default]
+    Class,
+    /// По кластеру общего владения
+    Cluster,
+    /// По компоненте связности графа
+    Component,
+    /// По сообществу (Louvain)
+    Community,
+    /// По риску, распространенному от размеченных узлов
+    Risk,
+}
+
+/// Метрика, задающая размер узлов-адресов
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeSizing {
+    /// Одинаковый размер
+    #[default]
+    Uniform,
+    /// Степень узла
+    Degree,
+    /// Посредническая центральность
+    Betweenness,
+    /// PageRank
+    Pagerank,
+}
+
+/// Строит граф адресов по папке с данными
+///
+/// Узлы — адреса и транзакции из `AddrTx_edgelist.csv` и `TxAddr_edgelist.csv`,
+/// ребра соединяют адреса входов с транзакцией и транзакцию с адресами ее
+/// выходов. Цвета берутся из классов транзакций и адресов.
+///
+/// # Arguments
+/// * `folder` - Путь к папке с CSV файлами
+///
+/// # Returns
+/// `Result<GraphData, SchemaError>` - узлы и ребра или ошибка чтения/схемы файлов
+pub fn build_graph_data(folder: &Path) -> Result<GraphData, SchemaError> {
+    Ok(address_graph_data(&TransactionGraph::load(folder)?))
+}
+
+/// Строит граф адресов по папке с данными с раскраской узлов по кластерам
+///
+/// # Arguments
+/// * `folder` - Путь к папке с CSV файлами
+/// * `options` - Параметры кластеризации
+///
+/// # Returns
+/// `Result<GraphData, SchemaError>` - узлы и ребра или ошибка чтения/схемы файлов
+pub fn build_cluster_graph_data(folder: &Path, options: &ClusteringOptions) -> Result<GraphData, SchemaError> {
+    build_styled_graph_data(folder, NodeColoring::Cluster, NodeSizing::Uniform, options)
+}
+
+/// Строит граф адресов по папке с данными с раскраской и размерами узлов
+///
+/// # Arguments
+/// * `folder` - Путь к папке с CSV файлами
+/// * `color_by` - Способ раскраски узлов
+/// * `size_by` - Метрика размера узлов
+/// * `clustering` - Параметры кластеризации для `color_by = cluster`
+///
+/// # Returns
+/// `Result<GraphData, SchemaError>` - узлы и ребра или ошибка чтения/схемы файлов
+pub fn build_styled_graph_data(
+    folder: &Path,
+    color_by: NodeColoring,
+    size_by: NodeSizing,
+    clustering: &ClusteringOptions,
+) -> Result<GraphData, SchemaError> {
+    let graph = TransactionGraph::load(folder)?;
+    let mut data = address_graph_data(&graph);
+    style_nodes(&graph, &mut data, color_by, size_by, clustering);
+    Ok(data)
+}
+
+/// Раскрашивает узлы и задает их размеры по метрикам графа
+///
+/// Вычисляются только метрики, нужные для выбранных раскраски и размера:
+/// посредническая центральность и PageRank — для размера по ним, сообщества —
+/// для раскраски по сообществам.
+pub fn style_nodes(
+    graph: &TransactionGraph,
+    data: &mut GraphData,
+    color_by: NodeColoring,
+    size_by: NodeSizing,
+    clustering: &ClusteringOptions,
+) {
+    let needs_metrics =
+        matches!(color_by, NodeColoring::Component | NodeColoring::Community) || size_by != NodeSizing::Uniform;
+    let selection = MetricSelection {
+        pagerank: size_by == NodeSizing::Pagerank,
+        betweenness: size_by == NodeSizing::Betweenness,
+        communities: (color_by == NodeColoring::Community).then(CommunityAlgorithm::default),
+    };
+    let metrics = needs_metrics.then(|| compute_selected_metrics(graph, selection));
+
+    match (color_by, &metrics) {
+        (NodeColoring::Cluster, _) => color_nodes_by_cluster(data, &cluster_addresses(graph, clustering)),
+        (NodeColoring::Component, Some(metrics)) => {
+            color_nodes_by_group(data, |id| metrics.get(id).map(|node| (node.component, metrics.component_sizes[node.component])))
+        }
+        (NodeColoring::Community, Some(metrics)) => {
+            color_nodes_by_group(data, |id| metrics.get(id).map(|node| (node.community, metrics.community_sizes[node.community])))
+        }
+        (NodeColoring::Risk, _) => color_nodes_by_risk(data, &propagate_risk(graph, PropagationMethod::default())),
+        _ => {}
+    }
+    if let Some(metrics) = &metrics {
+        size_nodes(data, metrics, size_by);
+    }
+}
+
+/// Переводит граф адресов и транзакций в формат AnyChart
+///
+/// Переводы идут через узел транзакции, поэтому число ребер линейно
+/// по числу входов и выходов, а не равно их произведению. Ребра
+/// транзакция → транзакция не показываются.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+///
+/// # Returns
+/// `GraphData` - адреса, транзакции с адресами и ребра «вход → транзакция → выход»
+pub fn address_graph_data(graph: &TransactionGraph) -> GraphData {
+    let petgraph = graph.graph();
+    let mut nodes: Vec<Node> = graph.addresses().map(|node| {
+        let fill_color = match node.class {
+            Some(Class::Unknown) => "#00FF00", // Зеленый
+            Some(Class::Licit) => "#CCCCCC",   // Серый
+            _ => "#FFFFFF",                    // Обычный белый
+        };
+
+        Node { id: node.id.key().to_string(), height: None, normal: NormalNode { fill: String::from(fill_color) } }
+    }).collect();
+    let mut edges = Vec::new();
+
+    for tx in petgraph.node_indices().filter(|&i| petgraph[i].id.is_transaction()) {
+        let tx_node = &petgraph[tx];
+        let fill_color = match tx_node.class.unwrap_or(Class::Unknown) {
+            Class::Unknown => "#00FF00", // Зеленый
+            Class::Licit => "#FF0000",   // Красный
+            Class::Illicit => "#CCCCCC", // Серый по умолчанию
+        };
+        let tx_id = chart_id(&tx_node.id);
+        let transfers: Vec<(String, String)> = graph
+            .input_addresses(tx)
+            .map(|from| (petgraph[from].id.key().to_string(), tx_id.clone()))
+            .chain(graph.output_addresses(tx).map(|to| (tx_id.clone(), petgraph[to].id.key().to_string())))
+            .collect();
+        if transfers.is_empty() {
+            continue;
+        }
+
+        nodes.push(Node { id: tx_id.clone(), height: None, normal: NormalNode { fill: String::from(fill_color) } });
+        for (from, to) in transfers {
+            edges.push(Edge {
+                from,
+                to,
+                id: tx_node.id.key().to_string(),
+                normal: NormalEdge { stroke: StrokeEdge { color: String::from(fill_color), thickness: None }},
+            });
+        }
+    }
+
+    GraphData { nodes, edges }
+}
+
+/// Перекрашивает узлы в цвета их кластеров
+///
+/// Адреса из кластеров с одним адресом остаются белыми, чтобы выделялись
+/// только группы адресов общего владельца; цвета транзакций не меняются.
+pub fn color_nodes_by_cluster(data: &mut GraphData, clusters: &AddressClusters) {
+    for node in data.nodes.iter_mut().filter(|node| node.node_id().is_address()) {
+        node.normal.fill = match clusters.cluster_of(&node.id) {
+            Some(cluster) if cluster.size > 1 => cluster_color(cluster.id),
+            _ => String::from("#FFFFFF"),
+        };
+    }
+}
+
+/// Перекрашивает узлы по риску: от зеленого (licit) к красному (illicit);
+/// узлы без риска остаются белыми
+pub fn color_nodes_by_risk(data: &mut GraphData, scores: &RiskScores) {
+    for node in &mut data.nodes {
+        node.normal.fill = match scores.get(&node.node_id()).and_then(|node| node.risk) {
+            Some(risk) => risk_color(risk),
+            None => String::from("#FFFFFF"),
+        };
+    }
+}
+
+/// Цвет риска в формате `#RRGGBB`
+pub fn risk_color(risk: f64) -> String {
+    let risk = risk.clamp(0.0, 1.0);
+    let (low, high) = ([0x2E, 0xCC, 0x71], [0xE7, 0x4C, 0x3C]);
+    let channel = |i: usize| (low[i] as f64 + (high[i] as f64 - low[i] as f64) * risk).round() as u8;
+    format!("#{:02X}{:02X}{:02X}", channel(0), channel(1), channel(2))
+}
+
+/// Перекрашивает узлы в цвета групп `(номер, размер)`; одиночные узлы белые
+fn color_nodes_by_group(data: &mut GraphData, group_of: impl Fn(&NodeId) -> Option<(usize, usize)>) {
+    for node in &mut data.nodes {
+        node.normal.fill = match group_of(&node.node_id()) {
+            Some((id, size)) if size > 1 => cluster_color(id),
+            _ => String::from("#FFFFFF"),
+        };
+    }
+}
+
+/// Задает размеры узлов пропорционально метрике
+///
+/// Размер линейно растет от `NODE_MIN_SIZE` у наименьшего значения метрики
+/// среди узлов до `NODE_MAX_SIZE` у наибольшего.
+pub fn size_nodes(data: &mut GraphData, metrics: &GraphMetrics, size_by: NodeSizing) {
+    let value = |node: &Node| -> Option<f64> {
+        let node = metrics.get(&node.node_id())?;
+        match size_by {
+            NodeSizing::Uniform => None,
+            NodeSizing::Degree => Some(node.degree() as f64),
+            NodeSizing::Betweenness => Some(node.betweenness),
+            NodeSizing::Pagerank => Some(node.pagerank),
+        }
+    };
+    let values: Vec<Option<f64>> = data.nodes.iter().map(value).collect();
+    let (min, max) = values
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| (min.min(value), max.max(value)));
+
+    for (node, value) in data.nodes.iter_mut().zip(values) {
+        node.height = value.map(|value| {
+            let share = if max > min { (value - min) / (max - min) } else { 0.0 };
+            NODE_MIN_SIZE + share * (NODE_MAX_SIZE - NODE_MIN_SIZE)
+        });
+    }
+}
+
+/// Выделяет на графе узлы и ребра найденных путей
+///
+/// Ребра транзакция → транзакция на графе не показываются, поэтому такие
+/// шаги пути выделяются только узлами.
+pub fn highlight_paths(data: &mut GraphData, paths: &[AddressPath]) {
+    let mut nodes = HashSet::new();
+    let mut steps = HashSet::new();
+    for path in paths {
+        nodes.extend(path.nodes.iter().map(chart_id));
+        for step in path.nodes.windows(2) {
+            let (from, to) = (chart_id(&step[0]), chart_id(&step[1]));
+            steps.insert((to.clone(), from.clone()));
+            steps.insert((from, to));
+        }
+    }
+
+    for node in data.nodes.iter_mut().filter(|node| nodes.contains(&node.id)) {
+        node.normal.fill = String::from(PATH_HIGHLIGHT_COLOR);
+    }
+    for edge in &mut data.edges {
+        if steps.contains(&(edge.from.clone(), edge.to.clone())) {
+            edge.normal.stroke = StrokeEdge { color: String::from(PATH_HIGHLIGHT_COLOR), thickness: Some(4) };
+        }
+    }
+}
+
+/// Выделяет центр окрестности и свернутые хабы
+///
+/// Центр и ребра центра-транзакции выделяются `EGO_CENTER_COLOR`,
+/// свернутые хабы и ребра хабов-транзакций — `EGO_HUB_COLOR`.
+pub fn highlight_ego(data: &mut GraphData, ego: &EgoNetwork) {
+    let hubs: HashSet<&NodeId> = ego.hubs.iter().map(|hub| &hub.id).collect();
+    let color_of = |id: &NodeId| {
+        if *id == ego.center {
+            Some(EGO_CENTER_COLOR)
+        } else if hubs.contains(id) {
+            Some(EGO_HUB_COLOR)
+        } else {
+            None
+        }
+    };
+
+    for node in &mut data.nodes {
+        if let Some(color) = color_of(&node.node_id()) {
+            node.normal.fill = String::from(color);
+        }
+    }
+    for edge in &mut data.edges {
+        if let Some(color) = color_of(&NodeId::transaction(&edge.id)) {
+            edge.normal.stroke = StrokeEdge { color: String::from(color), thickness: Some(3) };
+        }
+    }
+}
+
+/// Цвет кластера в формате `#RRGGBB`
+///
+/// Оттенок смещается на золотой угол для каждого следующего ID, поэтому
+/// соседние (самые крупные) кластеры получают хорошо различимые цвета.
+pub fn cluster_color(id: usize) -> String {
+    let hue = (id as f64 * 137.508) % 360.0;
+    let (saturation, lightness) = (0.65, 0.55);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0_f64).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let channel = |value: f64| ((value + m) * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}", channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_colors_are_distinct() {
+        assert_eq!(cluster_color(0), "#D74242");
+        let colors: std::collections::HashSet<String> = (0..20).map(cluster_color).collect();
+        assert_eq!(colors.len(), 20);
+        assert_eq!((risk_color(0.0), risk_color(1.0)), (String::from("#2ECC71"), String::from("#E74C3C")));
+    }
+
+    #[test]
+    fn test_highlight_paths() {
+        use crate::application::services::graph::path_finding::{k_shortest_paths, PathOptions};
+        use crate::application::services::graph::transaction_graph::{EdgeDirection, GraphEdge};
+
+        let mut graph = TransactionGraph::new();
+        graph.add_edge(NodeId::address("1A"), NodeId::transaction("t1"), GraphEdge::new(EdgeDirection::Input, None));
+        for address in ["1B", "1C"] {
+            graph.add_edge(NodeId::transaction("t1"), NodeId::address(address), GraphEdge::new(EdgeDirection::Output, None));
+        }
+        let mut data = address_graph_data(&graph);
+        highlight_paths(&mut data, &k_shortest_paths(&graph, "1A", "1B", PathOptions::default()));
+
+        let highlighted: Vec<(&str, &str)> = data
+            .edges
+            .iter()
+            .filter(|edge| edge.normal.stroke.thickness.is_some())
+            .map(|edge| (edge.from.as_str(), edge.to.as_str()))
+            .collect();
+        assert_eq!(highlighted, vec![("1A", "tx:t1"), ("tx:t1", "1B")]);
+        let fill = |id: &str| data.nodes.iter().find(|node| node.id == id).map(|node| node.normal.fill.as_str());
+        assert_eq!((fill("1A"), fill("tx:t1"), fill("1C")), (Some(PATH_HIGHLIGHT_COLOR), Some(PATH_HIGHLIGHT_COLOR), Some("#FFFFFF")));
+    }
+
+    #[test]
+    fn test_transfers_go_through_transaction_node() {
+        use crate::application::services::graph::transaction_graph::{EdgeDirection, GraphEdge};
+
+        // 50 входов и 50 выходов: 100 ребер вместо 2500 пар «вход → выход»
+        let mut graph = TransactionGraph::new();
+        for i in 0..50 {
+            graph.add_edge(NodeId::address(format!("in{}", i)), NodeId::transaction("t"), GraphEdge::new(EdgeDirection::Input, None));
+            graph.add_edge(NodeId::transaction("t"), NodeId::address(format!("out{}", i)), GraphEdge::new(EdgeDirection::Output, None));
+        }
+        graph.set_class(&NodeId::transaction("t"), Class::Illicit);
+
+        let data = address_graph_data(&graph);
+        assert_eq!((data.nodes.len(), data.edges.len()), (101, 100));
+        let tx = data.nodes.iter().find(|node| node.id == "tx:t").expect("Transaction node");
+        assert_eq!((tx.node_id(), tx.normal.fill.as_str()), (NodeId::transaction("t"), "#CCCCCC"));
+        assert!(data.edges.iter().all(|edge| edge.id == "t" && (edge.from == "tx:t") != (edge.to == "tx:t")));
+    }
+}
@@ -0,0 +1,531 @@
+use std::collections::{HashMap, VecDeque};
+
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::clustering::union_find::UnionFind;
+use crate::application::services::graph::transaction_graph::{NodeId, TransactionGraph};
+use crate::infrastructure::constants::{
+    BETWEENNESS_EXACT_MAX_NODES, BETWEENNESS_SAMPLE_SOURCES, LABEL_PROPAGATION_MAX_ITERATIONS, PAGERANK_DAMPING,
+    PAGERANK_MAX_ITERATIONS, PAGERANK_TOLERANCE,
+};
+
+/// Алгоритм поиска сообществ
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommunityAlgorithm {
+    /// Louvain: жадная максимизация модулярности с укрупнением графа
+    #[default]
+    Louvain,
+    /// Распространение меток: быстрее, но менее устойчиво
+    LabelPropagation,
+}
+
+/// Набор вычисляемых метрик
+///
+/// Степени и компоненты связности дешевы и вычисляются всегда; невычисленные
+/// центральности равны нулю, а без поиска сообществ все узлы попадают в сообщество 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricSelection {
+    pub pagerank: bool,
+    pub betweenness: bool,
+    /// Алгоритм поиска сообществ; `None` — сообщества не ищутся
+    pub communities: Option<CommunityAlgorithm>,
+}
+
+impl MetricSelection {
+    /// Все метрики с указанным алгоритмом поиска сообществ
+    pub fn all(algorithm: CommunityAlgorithm) -> Self {
+        MetricSelection {
+            pagerank: true,
+            betweenness: true,
+            communities: Some(algorithm),
+        }
+    }
+}
+
+/// Метрики узла графа адресов и транзакций
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeMetrics {
+    #[serde(flatten)]
+    pub id: NodeId,
+    pub in_degree: usize,
+    pub out_degree: usize,
+    /// Нормированная посредническая центральность по направленным путям
+    pub betweenness: f64,
+    pub pagerank: f64,
+    /// Компонента слабой связности (0 — крупнейшая)
+    pub component: usize,
+    /// Сообщество (0 — крупнейшее)
+    pub community: usize,
+}
+
+impl NodeMetrics {
+    pub fn degree(&self) -> usize {
+        self.in_degree + self.out_degree
+    }
+}
+
+/// Метрики всех узлов графа и сводка по компонентам и сообществам
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphMetrics {
+    /// Метрики в порядке узлов графа
+    pub nodes: Vec<NodeMetrics>,
+    /// Размеры компонент связности, от крупной к мелкой
+    pub component_sizes: Vec<usize>,
+    pub algorithm: CommunityAlgorithm,
+    /// Размеры сообществ, от крупного к мелкому
+    pub community_sizes: Vec<usize>,
+    /// Модулярность разбиения на сообщества
+    pub modularity: f64,
+    /// Посредническая центральность оценена по выборке источников
+    pub betweenness_sampled: bool,
+    #[serde(skip)]
+    by_node: HashMap<NodeId, usize>,
+}
+
+impl GraphMetrics {
+    /// Метрики узла по ID
+    pub fn get(&self, id: &NodeId) -> Option<&NodeMetrics> {
+        self.by_node.get(id).map(|&index| &self.nodes[index])
+    }
+}
+
+/// Вычисляет метрики графа: степени, посредническую центральность,
+/// PageRank, компоненты связности и сообщества
+///
+/// Степени, PageRank и посредническая центральность считаются по
+/// направленным ребрам (движению средств), компоненты и сообщества — без
+/// учета направления. На графах больше `BETWEENNESS_EXACT_MAX_NODES` узлов
+/// посредническая центральность оценивается по `BETWEENNESS_SAMPLE_SOURCES`
+/// источникам.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+/// * `algorithm` - Алгоритм поиска сообществ
+///
+/// # Returns
+/// `GraphMetrics` - метрики узлов и сводка по графу
+pub fn compute_metrics(graph: &TransactionGraph, algorithm: CommunityAlgorithm) -> GraphMetrics {
+    compute_selected_metrics(graph, MetricSelection::all(algorithm))
+}
+
+/// Вычисляет только выбранные метрики графа
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+/// * `selection` - Метрики, которые нужно вычислить
+///
+/// # Returns
+/// `GraphMetrics` - метрики узлов, невыбранные метрики равны нулю
+pub fn compute_selected_metrics(graph: &TransactionGraph, selection: MetricSelection) -> GraphMetrics {
+    let petgraph = graph.graph();
+    let node_count = petgraph.node_count();
+    let successors = (selection.pagerank || selection.betweenness).then(|| successors(graph));
+    let adjacency = undirected_adjacency(graph);
+
+    let pagerank = match (&successors, selection.pagerank) {
+        (Some(successors), true) => pagerank(successors),
+        _ => vec![0.0; node_count],
+    };
+    let (betweenness, betweenness_sampled) = match (&successors, selection.betweenness) {
+        (Some(successors), true) => betweenness(successors),
+        _ => (vec![0.0; node_count], false),
+    };
+    let (components, component_sizes) = relabel_by_size(&connected_components(&adjacency));
+    let algorithm = selection.communities.unwrap_or_default();
+    let (communities, community_sizes, modularity) = match selection.communities {
+        Some(algorithm) => {
+            let communities = match algorithm {
+                CommunityAlgorithm::Louvain => louvain(&adjacency),
+                CommunityAlgorithm::LabelPropagation => label_propagation(&adjacency),
+            };
+            let modularity = modularity(&adjacency, &communities);
+            let (communities, community_sizes) = relabel_by_size(&communities);
+            (communities, community_sizes, modularity)
+        }
+        None => (vec![0; node_count], Vec::new(), 0.0),
+    };
+
+    let nodes: Vec<NodeMetrics> = petgraph
+        .node_indices()
+        .map(|index| {
+            let i = index.index();
+            NodeMetrics {
+                id: petgraph[index].id.clone(),
+                in_degree: petgraph.edges_directed(index, Direction::Incoming).count(),
+                out_degree: petgraph.edges_directed(index, Direction::Outgoing).count(),
+                betweenness: betweenness[i],
+                pagerank: pagerank[i],
+                component: components[i],
+                community: communities[i],
+            }
+        })
+        .collect();
+    let by_node = nodes.iter().enumerate().map(|(i, node)| (node.id.clone(), i)).collect();
+
+    GraphMetrics {
+        nodes,
+        component_sizes,
+        algorithm,
+        community_sizes,
+        modularity,
+        betweenness_sampled,
+        by_node,
+    }
+}
+
+/// Уникальные последователи узлов по направленным ребрам
+fn successors(graph: &TransactionGraph) -> Vec<Vec<usize>> {
+    let petgraph = graph.graph();
+    petgraph
+        .node_indices()
+        .map(|index| {
+            let mut next: Vec<usize> = petgraph.neighbors_directed(index, Direction::Outgoing).map(|n| n.index()).collect();
+            next.sort_unstable();
+            next.dedup();
+            next
+        })
+        .collect()
+}
+
+/// Взвешенные соседи без учета направления; вес — число ребер между узлами
+pub(crate) fn undirected_adjacency(graph: &TransactionGraph) -> Vec<Vec<(usize, f64)>> {
+    let petgraph = graph.graph();
+    let mut adjacency: Vec<HashMap<usize, f64>> = vec![HashMap::new(); petgraph.node_count()];
+    for edge in petgraph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        *adjacency[a].entry(b).or_default() += 1.0;
+        *adjacency[b].entry(a).or_default() += 1.0;
+    }
+    adjacency
+        .into_iter()
+        .map(|neighbors| {
+            let mut neighbors: Vec<(usize, f64)> = neighbors.into_iter().collect();
+            neighbors.sort_unstable_by_key(|&(node, _)| node);
+            neighbors
+        })
+        .collect()
+}
+
+/// PageRank степенным методом; ранг узлов без исходящих ребер делится поровну
+fn pagerank(successors: &[Vec<usize>]) -> Vec<f64> {
+    let n = successors.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let base = (1.0 - PAGERANK_DAMPING) / n as f64;
+    let mut rank = vec![1.0 / n as f64; n];
+    for _ in 0..PAGERANK_MAX_ITERATIONS {
+        let dangling: f64 = (0..n).filter(|&i| successors[i].is_empty()).map(|i| rank[i]).sum();
+        let mut next = vec![base + PAGERANK_DAMPING * dangling / n as f64; n];
+        for (node, targets) in successors.iter().enumerate().filter(|(_, targets)| !targets.is_empty()) {
+            let share = PAGERANK_DAMPING * rank[node] / targets.len() as f64;
+            for &target in targets {
+                next[target] += share;
+            }
+        }
+        let delta: f64 = rank.iter().zip(&next).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < PAGERANK_TOLERANCE {
+            break;
+        }
+    }
+    rank
+}
+
+/// Посредническая центральность (алгоритм Брандеса), нормированная на
+/// `(n - 1)(n - 2)`; для больших графов — по равномерной выборке источников
+fn betweenness(successors: &[Vec<usize>]) -> (Vec<f64>, bool) {
+    let n = successors.len();
+    let mut centrality = vec![0.0; n];
+    if n < 3 {
+        return (centrality, false);
+    }
+    let sampled = n > BETWEENNESS_EXACT_MAX_NODES;
+    let sources: Vec<usize> = if sampled {
+        (0..BETWEENNESS_SAMPLE_SOURCES).map(|i| i * n / BETWEENNESS_SAMPLE_SOURCES).collect()
+    } else {
+        (0..n).collect()
+    };
+
+    let mut order = Vec::with_capacity(n);
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    let mut paths = vec![0.0_f64; n];
+    let mut distance = vec![usize::MAX; n];
+    let mut dependency = vec![0.0_f64; n];
+    for &source in &sources {
+        order.clear();
+        for i in 0..n {
+            predecessors[i].clear();
+            paths[i] = 0.0;
+            distance[i] = usize::MAX;
+            dependency[i] = 0.0;
+        }
+        paths[source] = 1.0;
+        distance[source] = 0;
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &next in &successors[node] {
+                if distance[next] == usize::MAX {
+                    distance[next] = distance[node] + 1;
+                    queue.push_back(next);
+                }
+                if distance[next] == distance[node] + 1 {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+        for &node in order.iter().rev() {
+            for &previous in &predecessors[node] {
+                dependency[previous] += paths[previous] / paths[node] * (1.0 + dependency[node]);
+            }
+            if node != source {
+                centrality[node] += dependency[node];
+            }
+        }
+    }
+
+    let scale = n as f64 / sources.len() as f64 / ((n - 1) * (n - 2)) as f64;
+    for value in &mut centrality {
+        *value *= scale;
+    }
+    (centrality, sampled)
+}
+
+/// Компоненты слабой связности: представитель множества для каждого узла
+fn connected_components(adjacency: &[Vec<(usize, f64)>]) -> Vec<usize> {
+    let mut sets = UnionFind::new(adjacency.len());
+    for (node, neighbors) in adjacency.iter().enumerate() {
+        for &(neighbor, _) in neighbors {
+            sets.union(node, neighbor);
+        }
+    }
+    (0..adjacency.len()).map(|node| sets.find(node)).collect()
+}
+
+/// Louvain: узлы переходят в соседнее сообщество с наибольшим приростом
+/// модулярности, затем сообщества сворачиваются в узлы, пока разбиение меняется
+fn louvain(adjacency: &[Vec<(usize, f64)>]) -> Vec<usize> {
+    let mut membership: Vec<usize> = (0..adjacency.len()).collect();
+    let mut level: Vec<Vec<(usize, f64)>> = adjacency.to_vec();
+    loop {
+        let (assignment, moved) = louvain_pass(&level);
+        if !moved {
+            return membership;
+        }
+        let (assignment, sizes) = relabel_by_size(&assignment);
+        for community in &mut membership {
+            *community = assignment[*community];
+        }
+
+        let mut aggregated: Vec<HashMap<usize, f64>> = vec![HashMap::new(); sizes.len()];
+        for (node, neighbors) in level.iter().enumerate() {
+            for &(neighbor, weight) in neighbors {
+                *aggregated[assignment[node]].entry(assignment[neighbor]).or_default() += weight;
+            }
+        }
+        level = aggregated
+            .into_iter()
+            .map(|neighbors| {
+                let mut neighbors: Vec<(usize, f64)> = neighbors.into_iter().collect();
+                neighbors.sort_unstable_by_key(|&(node, _)| node);
+                neighbors
+            })
+            .collect();
+    }
+}
+
+/// Один уровень Louvain: перемещения узлов, пока модулярность растет
+fn louvain_pass(adjacency: &[Vec<(usize, f64)>]) -> (Vec<usize>, bool) {
+    let n = adjacency.len();
+    let degree: Vec<f64> = adjacency.iter().map(|neighbors| neighbors.iter().map(|(_, weight)| weight).sum()).collect();
+    let total: f64 = degree.iter().sum();
+    let mut community: Vec<usize> = (0..n).collect();
+    if total == 0.0 {
+        return (community, false);
+    }
+    let mut community_degree = degree.clone();
+
+    let mut moved = false;
+    loop {
+        let mut improved = false;
+        for node in 0..n {
+            let current = community[node];
+            let mut links: HashMap<usize, f64> = HashMap::new();
+            for &(neighbor, weight) in adjacency[node].iter().filter(|&&(neighbor, _)| neighbor != node) {
+                *links.entry(community[neighbor]).or_default() += weight;
+            }
+            community_degree[current] -= degree[node];
+
+            let gain = |target: usize| {
+                links.get(&target).copied().unwrap_or(0.0) - community_degree[target] * degree[node] / total
+            };
+            let mut best = (current, gain(current));
+            let mut candidates: Vec<usize> = links.keys().copied().collect();
+            candidates.sort_unstable();
+            for target in candidates {
+                let value = gain(target);
+                if value > best.1 + 1e-12 {
+                    best = (target, value);
+                }
+            }
+
+            community_degree[best.0] += degree[node];
+            if best.0 != current {
+                community[node] = best.0;
+                improved = true;
+                moved = true;
+            }
+        }
+        if !improved {
+            return (community, moved);
+        }
+    }
+}
+
+/// Распространение меток: узел принимает метку с наибольшим весом среди
+/// соседей (при равенстве — свою или наименьшую)
+fn label_propagation(adjacency: &[Vec<(usize, f64)>]) -> Vec<usize> {
+    let mut labels: Vec<usize> = (0..adjacency.len()).collect();
+    for _ in 0..LABEL_PROPAGATION_MAX_ITERATIONS {
+        let mut changed = false;
+        for node in 0..adjacency.len() {
+            let mut weights: HashMap<usize, f64> = HashMap::new();
+            for &(neighbor, weight) in adjacency[node].iter().filter(|&&(neighbor, _)| neighbor != node) {
+                *weights.entry(labels[neighbor]).or_default() += weight;
+            }
+            let Some(max) = weights.values().copied().reduce(f64::max) else {
+                continue;
+            };
+            if weights.get(&labels[node]) == Some(&max) {
+                continue;
+            }
+            let best = weights.iter().filter(|&(_, &weight)| weight == max).map(|(&label, _)| label).min();
+            if let Some(best) = best {
+                labels[node] = best;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    labels
+}
+
+/// Модулярность разбиения графа
+fn modularity(adjacency: &[Vec<(usize, f64)>], communities: &[usize]) -> f64 {
+    let total: f64 = adjacency.iter().flatten().map(|(_, weight)| weight).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    let mut inner: HashMap<usize, f64> = HashMap::new();
+    let mut degree: HashMap<usize, f64> = HashMap::new();
+    for (node, neighbors) in adjacency.iter().enumerate() {
+        for &(neighbor, weight) in neighbors {
+            *degree.entry(communities[node]).or_default() += weight;
+            if communities[node] == communities[neighbor] {
+                *inner.entry(communities[node]).or_default() += weight;
+            }
+        }
+    }
+    degree
+        .iter()
+        .map(|(community, degree)| inner.get(community).copied().unwrap_or(0.0) / total - (degree / total).powi(2))
+        .sum()
+}
+
+/// Перенумеровывает группы по убыванию размера (при равенстве — по первому узлу)
+///
+/// # Returns
+/// `(Vec<usize>, Vec<usize>)` - новые номера групп узлов и размеры групп
+fn relabel_by_size(groups: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let mut members: HashMap<usize, (usize, usize)> = HashMap::new();
+    for (node, &group) in groups.iter().enumerate() {
+        members.entry(group).or_insert((0, node)).0 += 1;
+    }
+    let mut order: Vec<(usize, (usize, usize))> = members.into_iter().collect();
+    order.sort_unstable_by(|(_, (a_size, a_first)), (_, (b_size, b_first))| {
+        b_size.cmp(a_size).then_with(|| a_first.cmp(b_first))
+    });
+    let ids: HashMap<usize, usize> = order.iter().enumerate().map(|(id, &(group, _))| (group, id)).collect();
+    (groups.iter().map(|group| ids[group]).collect(), order.into_iter().map(|(_, (size, _))| size).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::graph::transaction_graph::{EdgeDirection, GraphEdge};
+
+    /// Две группы адресов, связанные одной транзакцией t0, и отдельная пара 1X → t9
+    fn sample_graph() -> TransactionGraph {
+        let mut graph = TransactionGraph::new();
+        let mut add = |from: NodeId, to: NodeId, direction| graph.add_edge(from, to, GraphEdge::new(direction, None));
+        for (tx, addresses) in [("t1", ["1A", "1B", "1C"]), ("t2", ["1D", "1E", "1F"])] {
+            for address in addresses {
+                add(NodeId::address(address), NodeId::transaction(tx), EdgeDirection::Input);
+                add(NodeId::transaction(tx), NodeId::address(format!("{}o", address)), EdgeDirection::Output);
+            }
+        }
+        add(NodeId::address("1Ao"), NodeId::transaction("t0"), EdgeDirection::Input);
+        add(NodeId::transaction("t0"), NodeId::address("1D"), EdgeDirection::Output);
+        add(NodeId::address("1X"), NodeId::transaction("t9"), EdgeDirection::Input);
+        graph
+    }
+
+    #[test]
+    fn test_centrality_and_components() {
+        let graph = sample_graph();
+        let metrics = compute_metrics(&graph, CommunityAlgorithm::Louvain);
+        let node = |id: NodeId| metrics.get(&id).expect("Node metrics");
+
+        assert_eq!(metrics.component_sizes, vec![15, 2]);
+        assert_eq!(node(NodeId::address("1X")).component, 1);
+        assert_eq!(node(NodeId::transaction("t1")).degree(), 6);
+
+        // Через t0 проходят пути от 5 узлов первой группы к 5 узлам второй
+        assert!((node(NodeId::transaction("t0")).betweenness - 25.0 / (16.0 * 15.0)).abs() < 1e-12);
+        assert_eq!(node(NodeId::address("1Bo")).betweenness, 0.0);
+        assert!(!metrics.betweenness_sampled);
+
+        let ranks: f64 = metrics.nodes.iter().map(|node| node.pagerank).sum();
+        assert!((ranks - 1.0).abs() < 1e-9);
+        assert!(node(NodeId::address("1D")).pagerank > node(NodeId::address("1E")).pagerank);
+    }
+
+    #[test]
+    fn test_selected_metrics_skip_the_rest() {
+        let graph = sample_graph();
+        let selection = MetricSelection { pagerank: false, betweenness: true, communities: None };
+        let metrics = compute_selected_metrics(&graph, selection);
+        let full = compute_metrics(&graph, CommunityAlgorithm::Louvain);
+
+        assert_eq!(metrics.component_sizes, full.component_sizes);
+        assert!(metrics.community_sizes.is_empty());
+        for (node, expected) in metrics.nodes.iter().zip(&full.nodes) {
+            assert_eq!(node.betweenness, expected.betweenness);
+            assert_eq!((node.component, node.degree()), (expected.component, expected.degree()));
+            assert_eq!((node.pagerank, node.community), (0.0, 0));
+        }
+    }
+
+    #[test]
+    fn test_communities_split_groups() {
+        let graph = sample_graph();
+        for algorithm in [CommunityAlgorithm::Louvain, CommunityAlgorithm::LabelPropagation] {
+            let metrics = compute_metrics(&graph, algorithm);
+            let community = |id: &str| metrics.get(&NodeId::address(id)).map(|node| node.community);
+
+            assert_eq!(community("1A"), community("1C"), "{:?}", algorithm);
+            assert_eq!(community("1E"), community("1Fo"), "{:?}", algorithm);
+            assert_ne!(community("1B"), community("1E"), "{:?}", algorithm);
+            assert_ne!(community("1X"), community("1A"), "{:?}", algorithm);
+            assert!(metrics.modularity > 0.3, "{:?}: {}", algorithm, metrics.modularity);
+        }
+    }
+}
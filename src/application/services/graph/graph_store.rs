@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::application::services::graph::transaction_graph::TransactionGraph;
+use crate::infrastructure::dataset::dataset_store::normalize;
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+
+/// Размер и время изменения файла; `None`, если файла нет
+type FileStamp = Option<(u64, Option<SystemTime>)>;
+
+struct LoadedGraph {
+    stamps: Vec<FileStamp>,
+    graph: Arc<TransactionGraph>,
+}
+
+/// Хранилище графов папок с данными
+///
+/// Граф папки строится при первом обращении и переиспользуется всеми
+/// запросами. Перед выдачей проверяются размер и время изменения файлов,
+/// из которых строится граф ([`TransactionGraph::SOURCE_FILES`]), поэтому
+/// после изменения папки граф перестраивается автоматически.
+///
+/// Как и в [`DatasetStore`](crate::infrastructure::dataset::dataset_store::DatasetStore),
+/// графы хранятся по абсолютному пути папки.
+#[derive(Default)]
+pub struct GraphStore {
+    graphs: Mutex<HashMap<PathBuf, LoadedGraph>>,
+}
+
+impl GraphStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Возвращает актуальный граф папки, строя его при необходимости
+    ///
+    /// # Arguments
+    /// * `folder` - Путь к папке с CSV файлами
+    ///
+    /// # Returns
+    /// `Result<Arc<TransactionGraph>, SchemaError>` - граф или ошибка чтения/схемы файлов
+    pub fn load(&self, folder: &Path) -> Result<Arc<TransactionGraph>, SchemaError> {
+        let key = normalize(folder);
+        // Отметки снимаются до чтения: файл, измененный во время загрузки, перечитается при следующем запросе
+        let stamps = stamps(folder);
+        if let Some(loaded) = self.lock().get(&key).filter(|loaded| loaded.stamps == stamps) {
+            return Ok(loaded.graph.clone());
+        }
+
+        // Граф строится без блокировки хранилища, чтобы не задерживать запросы к другим папкам
+        let graph = Arc::new(TransactionGraph::load(folder)?);
+        log::debug!("dataset graph loaded: {}", folder.display());
+        self.lock().insert(key, LoadedGraph { stamps, graph: graph.clone() });
+        Ok(graph)
+    }
+
+    /// Удаляет граф папки, например после удаления папки
+    pub fn forget_folder(&self, folder: &Path) {
+        self.lock().remove(&normalize(folder));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, LoadedGraph>> {
+        self.graphs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn stamps(folder: &Path) -> Vec<FileStamp> {
+    TransactionGraph::SOURCE_FILES
+        .iter()
+        .map(|file| {
+            fs::metadata(folder.join(file))
+                .ok()
+                .map(|metadata| (metadata.len(), metadata.modified().ok()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::graph::transaction_graph::NodeId;
+    use crate::infrastructure::constants::{ADDR_TX_EDGELIST_FILE, TX_ADDR_EDGELIST_FILE};
+
+    #[test]
+    fn test_graph_is_reused_until_files_change() {
+        let folder = PathBuf::from(format!("target/graph_store_{}", std::process::id()));
+        fs::create_dir_all(&folder).expect("Should create folder");
+        fs::write(folder.join(ADDR_TX_EDGELIST_FILE), "input_address,txId\n1A,101\n").expect("Should write CSV");
+        fs::write(folder.join(TX_ADDR_EDGELIST_FILE), "txId,output_address\n101,1B\n").expect("Should write CSV");
+
+        let store = GraphStore::new();
+        let first = store.load(&folder).expect("Should load");
+        let absolute = std::env::current_dir().expect("Current dir").join(&folder);
+        assert!(Arc::ptr_eq(&first, &store.load(&absolute).expect("Should load")));
+
+        fs::write(folder.join(TX_ADDR_EDGELIST_FILE), "txId,output_address\n101,1B\n101,1C\n").expect("Should rewrite CSV");
+        let reloaded = store.load(&folder).expect("Should load");
+        assert!(!Arc::ptr_eq(&first, &reloaded));
+        assert!(reloaded.index_of(&NodeId::address("1C")).is_some());
+
+        store.forget_folder(&absolute);
+        assert!(store.lock().is_empty());
+        fs::remove_dir_all(&folder).expect("Should remove folder");
+    }
+}
@@ -0,0 +1,7 @@
+pub mod ego_network;
+pub mod graph_data;
+pub mod graph_metrics;
+pub mod graph_store;
+pub mod path_finding;
+pub mod risk_propagation;
+pub mod transaction_graph;
@@ -0,0 +1,319 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use bitcoin::Amount;
+use petgraph::graph::NodeIndex;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::graph::transaction_graph::{EdgeDirection, NodeId, TransactionGraph};
+use crate::infrastructure::constants::{
+    DEFAULT_PATH_COUNT, MAX_PATH_EXPANSION_TRANSACTIONS, PATH_ADDRESS_HISTORY_PAGES,
+};
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::explorer_client::ExplorerClient;
+
+/// Учет направления ребер при поиске пути
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathMode {
+    /// Только по движению средств: адрес → транзакция → адрес
+    #[default]
+    Directed,
+    /// Связь в любом направлении (общая транзакция, получение от общего отправителя)
+    Undirected,
+}
+
+/// Параметры поиска путей
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathOptions {
+    /// Число кратчайших путей
+    pub k: usize,
+    pub mode: PathMode,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        PathOptions { k: DEFAULT_PATH_COUNT, mode: PathMode::default() }
+    }
+}
+
+/// Ребро пути в порядке обхода
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathLink {
+    pub from: NodeId,
+    pub to: NodeId,
+    /// Тип ребра графа
+    pub direction: EdgeDirection,
+    /// Ребро пройдено против движения средств (только в режиме `undirected`)
+    pub reversed: bool,
+    /// Сумма перевода, если известна
+    #[serde(with = "bitcoin::amount::serde::as_sat::opt")]
+    pub value: Option<Amount>,
+}
+
+/// Путь между двумя адресами
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressPath {
+    /// Число транзакций на пути
+    pub hops: usize,
+    /// Промежуточные транзакции в порядке обхода
+    pub transactions: Vec<String>,
+    /// Все узлы пути, от начального адреса до конечного
+    pub nodes: Vec<NodeId>,
+    pub links: Vec<PathLink>,
+}
+
+/// Результат поиска путей между адресами
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathSearch {
+    pub from: String,
+    pub to: String,
+    pub mode: PathMode,
+    /// Пути от кратчайшего к длинному; пустой список — адреса не связаны
+    pub paths: Vec<AddressPath>,
+    /// Расширение графа через explorer остановлено по лимиту транзакций
+    pub truncated: bool,
+}
+
+/// Находит до `k` кратчайших простых путей между адресами (алгоритм Йена)
+///
+/// Длина пути — число ребер графа адресов и транзакций, поэтому пути с
+/// меньшим числом транзакций идут первыми. Ребра транзакция → транзакция
+/// тоже участвуют: в датасете Elliptic они связывают транзакции, у которых
+/// нет общих адресов в файлах.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций
+/// * `from` - Начальный адрес
+/// * `to` - Конечный адрес
+/// * `options` - Число путей и учет направления ребер
+///
+/// # Returns
+/// `Vec<AddressPath>` - пути от кратчайшего к длинному; пустой, если адресов
+/// нет в графе или они не связаны
+pub fn k_shortest_paths(graph: &TransactionGraph, from: &str, to: &str, options: PathOptions) -> Vec<AddressPath> {
+    let (Some(source), Some(target)) = (graph.index_of(&NodeId::address(from)), graph.index_of(&NodeId::address(to)))
+    else {
+        return Vec::new();
+    };
+    if source == target || options.k == 0 {
+        return Vec::new();
+    }
+    let Some(first) = shortest_path(graph, source, target, options.mode, &HashSet::new(), &HashSet::new()) else {
+        return Vec::new();
+    };
+
+    let mut found: Vec<Vec<NodeIndex>> = vec![first];
+    // Кандидаты упорядочены по длине, затем по узлам — результат детерминирован
+    let mut candidates: BTreeSet<(usize, Vec<NodeIndex>)> = BTreeSet::new();
+    while found.len() < options.k {
+        let previous = &found[found.len() - 1];
+        for i in 0..previous.len() - 1 {
+            let root = &previous[..=i];
+            let banned_links: HashSet<(NodeIndex, NodeIndex)> = found
+                .iter()
+                .filter(|path| path.len() > i + 1 && &path[..=i] == root)
+                .map(|path| (path[i], path[i + 1]))
+                .collect();
+            let banned_nodes: HashSet<NodeIndex> = root[..i].iter().copied().collect();
+
+            if let Some(spur) = shortest_path(graph, previous[i], target, options.mode, &banned_nodes, &banned_links) {
+                let candidate: Vec<NodeIndex> = root[..i].iter().copied().chain(spur).collect();
+                if !found.contains(&candidate) {
+                    candidates.insert((candidate.len(), candidate));
+                }
+            }
+        }
+        match candidates.pop_first() {
+            Some((_, path)) => found.push(path),
+            None => break,
+        }
+    }
+
+    found.iter().map(|path| describe_path(graph, path)).collect()
+}
+
+/// Соседи узла с учетом режима поиска
+fn neighbors(graph: &TransactionGraph, node: NodeIndex, mode: PathMode) -> Vec<NodeIndex> {
+    let mut neighbors: Vec<NodeIndex> = graph.graph().neighbors_directed(node, Direction::Outgoing).collect();
+    if mode == PathMode::Undirected {
+        neighbors.extend(graph.graph().neighbors_directed(node, Direction::Incoming));
+    }
+    neighbors
+}
+
+/// Кратчайший путь поиском в ширину в обход запрещенных узлов и ребер
+fn shortest_path(
+    graph: &TransactionGraph,
+    source: NodeIndex,
+    target: NodeIndex,
+    mode: PathMode,
+    banned_nodes: &HashSet<NodeIndex>,
+    banned_links: &HashSet<(NodeIndex, NodeIndex)>,
+) -> Option<Vec<NodeIndex>> {
+    let mut parents: HashMap<NodeIndex, NodeIndex> = HashMap::new();
+    let mut queue = VecDeque::from([source]);
+    while let Some(node) = queue.pop_front() {
+        if node == target {
+            let mut path = vec![target];
+            while let Some(&parent) = parents.get(path.last()?) {
+                path.push(parent);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in neighbors(graph, node, mode) {
+            if next == source
+                || parents.contains_key(&next)
+                || banned_nodes.contains(&next)
+                || banned_links.contains(&(node, next))
+            {
+                continue;
+            }
+            parents.insert(next, node);
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+/// Переводит путь из индексов petgraph в узлы, транзакции и суммы
+fn describe_path(graph: &TransactionGraph, path: &[NodeIndex]) -> AddressPath {
+    let petgraph = graph.graph();
+    let links = path
+        .windows(2)
+        .filter_map(|pair| {
+            let (from, to) = (pair[0], pair[1]);
+            // Обратное ребро встречается только в пути режима `undirected`
+            let (edge, reversed) = petgraph
+                .edges_connecting(from, to)
+                .next()
+                .map(|edge| (*edge.weight(), false))
+                .or_else(|| petgraph.edges_connecting(to, from).next().map(|edge| (*edge.weight(), true)))?;
+            Some(PathLink {
+                from: petgraph[from].id.clone(),
+                to: petgraph[to].id.clone(),
+                direction: edge.direction,
+                reversed,
+                value: edge.value,
+            })
+        })
+        .collect();
+    let nodes: Vec<NodeId> = path.iter().map(|&index| petgraph[index].id.clone()).collect();
+    let transactions: Vec<String> =
+        nodes.iter().filter(|node| node.is_transaction()).map(|node| node.key().to_string()).collect();
+
+    AddressPath { hops: transactions.len(), transactions, nodes, links }
+}
+
+/// Строит граф вокруг двух адресов по их истории в explorer'е
+///
+/// На каждом уровне запрашивается история адресов фронтира, транзакции
+/// добавляются в граф, а их новые адреса образуют следующий фронтир.
+/// Расширение прекращается, как только адреса оказываются связаны (без
+/// учета направления), после `depth` уровней или по достижении
+/// `MAX_PATH_EXPANSION_TRANSACTIONS` транзакций.
+///
+/// # Arguments
+/// * `client` - Клиент explorer'а
+/// * `from` - Первый адрес
+/// * `to` - Второй адрес
+/// * `depth` - Число уровней истории адресов
+///
+/// # Returns
+/// `Result<(TransactionGraph, bool), ExplorerError>` - граф и признак остановки по лимиту
+pub async fn expand_between(
+    client: &ExplorerClient,
+    from: &str,
+    to: &str,
+    depth: usize,
+) -> Result<(TransactionGraph, bool), ExplorerError> {
+    let mut graph = TransactionGraph::new();
+    graph.add_node(NodeId::address(from));
+    graph.add_node(NodeId::address(to));
+
+    let mut seen_addresses: HashSet<String> = HashSet::from([from.to_string(), to.to_string()]);
+    let mut seen_transactions: HashSet<String> = HashSet::new();
+    let mut frontier: Vec<String> = vec![from.to_string(), to.to_string()];
+
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for address in &frontier {
+            for tx in client.get_address_transactions(address, PATH_ADDRESS_HISTORY_PAGES).await? {
+                if !seen_transactions.insert(tx.txid.clone()) {
+                    continue;
+                }
+                if seen_transactions.len() > MAX_PATH_EXPANSION_TRANSACTIONS {
+                    return Ok((graph, true));
+                }
+                graph.add_chain_transaction(&tx);
+                let addresses = tx
+                    .inputs
+                    .iter()
+                    .filter_map(|input| input.prevout.as_ref())
+                    .chain(&tx.outputs)
+                    .filter_map(|output| output.address.clone());
+                for address in addresses {
+                    if seen_addresses.insert(address.clone()) {
+                        next.push(address);
+                    }
+                }
+            }
+        }
+
+        let connected = k_shortest_paths(&graph, from, to, PathOptions { k: 1, mode: PathMode::Undirected });
+        if !connected.is_empty() || next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    Ok((graph, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::graph::transaction_graph::GraphEdge;
+
+    fn link(graph: &mut TransactionGraph, address: &str, tx: &str, value: u64) {
+        graph.add_edge(NodeId::address(address), NodeId::transaction(tx), GraphEdge::new(EdgeDirection::Input, Some(Amount::from_sat(value))));
+    }
+
+    fn pay(graph: &mut TransactionGraph, tx: &str, address: &str, value: u64) {
+        graph.add_edge(NodeId::transaction(tx), NodeId::address(address), GraphEdge::new(EdgeDirection::Output, Some(Amount::from_sat(value))));
+    }
+
+    /// A → t1 → B → t2 → C и обход A → t3 → D → t4 → E → t5 → C
+    fn sample_graph() -> TransactionGraph {
+        let mut graph = TransactionGraph::new();
+        link(&mut graph, "A", "t1", 100);
+        pay(&mut graph, "t1", "B", 90);
+        link(&mut graph, "B", "t2", 90);
+        pay(&mut graph, "t2", "C", 80);
+        link(&mut graph, "A", "t3", 50);
+        pay(&mut graph, "t3", "D", 40);
+        link(&mut graph, "D", "t4", 40);
+        pay(&mut graph, "t4", "E", 30);
+        link(&mut graph, "E", "t5", 30);
+        pay(&mut graph, "t5", "C", 20);
+        graph
+    }
+
+    #[test]
+    fn test_k_shortest_paths_in_order() {
+        let graph = sample_graph();
+        let paths = k_shortest_paths(&graph, "A", "C", PathOptions { k: 5, mode: PathMode::Directed });
+
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths[0].hops, 2);
+        assert_eq!(paths[0].transactions, vec!["t1", "t2"]);
+        assert_eq!(paths[0].links[1].value, Some(Amount::from_sat(90)));
+        assert_eq!(paths[1].transactions, vec!["t3", "t4", "t5"]);
+
+        // Против движения средств путь есть только без учета направления
+        assert!(k_shortest_paths(&graph, "C", "A", PathOptions::default()).is_empty());
+        let reverse = k_shortest_paths(&graph, "C", "A", PathOptions { k: 1, mode: PathMode::Undirected });
+        assert_eq!(reverse[0].transactions, vec!["t2", "t1"]);
+        assert!(reverse[0].links.iter().all(|link| link.reversed));
+    }
+}
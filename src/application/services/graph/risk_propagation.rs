@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::graph::graph_metrics::undirected_adjacency;
+use crate::application::services::graph::transaction_graph::{NodeId, TransactionGraph};
+use crate::infrastructure::constants::{
+    RISK_EXPLAIN_MAX_DEPTH, RISK_EXPLAIN_NEIGHBOURS, RISK_PROPAGATION_ALPHA, RISK_PROPAGATION_MAX_ITERATIONS,
+    RISK_PROPAGATION_TOLERANCE,
+};
+use crate::infrastructure::dataset::schema::Class;
+
+/// Метод распространения классов на неразмеченные узлы
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropagationMethod {
+    /// Итеративное распространение меток с симметричной нормировкой
+    #[default]
+    LabelSpreading,
+    /// Персонализированный PageRank с возвратом к размеченным узлам
+    PersonalizedPagerank,
+}
+
+/// Риск узла
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeRisk {
+    #[serde(flatten)]
+    pub id: NodeId,
+    /// Известный класс узла (`illicit` или `licit`), если он размечен
+    pub class: Option<Class>,
+    /// Риск от 0 (licit) до 1 (illicit); `None`, если до узла не дошел ни один класс
+    pub risk: Option<f64>,
+    /// Риск получен распространением, а не из разметки
+    pub propagated: bool,
+}
+
+/// Размеченный узел рядом с узлом, объясняющий его риск
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabelledNeighbour {
+    #[serde(flatten)]
+    pub id: NodeId,
+    pub class: Class,
+    /// Расстояние в ребрах графа адресов и транзакций
+    pub distance: usize,
+}
+
+/// Риск узла с ближайшими размеченными соседями
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskExplanation {
+    #[serde(flatten)]
+    pub node: NodeRisk,
+    pub neighbours: Vec<LabelledNeighbour>,
+}
+
+/// Риски всех узлов графа
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskScores {
+    pub method: PropagationMethod,
+    pub illicit_seeds: usize,
+    pub licit_seeds: usize,
+    /// Риски в порядке узлов графа
+    pub nodes: Vec<NodeRisk>,
+    #[serde(skip)]
+    by_node: HashMap<NodeId, usize>,
+}
+
+impl RiskScores {
+    /// Риск узла по ID
+    pub fn get(&self, id: &NodeId) -> Option<&NodeRisk> {
+        self.by_node.get(id).map(|&index| &self.nodes[index])
+    }
+}
+
+/// Известный класс узла, если это `illicit` или `licit`
+fn seed_class(graph: &TransactionGraph, index: NodeIndex) -> Option<Class> {
+    graph.graph()[index].class.filter(|class| *class != Class::Unknown)
+}
+
+/// Распространяет классы размеченных транзакций и адресов на неразмеченные
+///
+/// Классы illicit и licit распространяются по графу без учета направления
+/// ребер независимо; масса каждого класса делится на число его узлов, чтобы
+/// редкий класс illicit не терялся среди licit. Риск неразмеченного узла —
+/// доля illicit в дошедшей до него массе.
+///
+/// # Arguments
+/// * `graph` - Граф адресов и транзакций с классами из датасета
+/// * `method` - Метод распространения
+///
+/// # Returns
+/// `RiskScores` - риск каждого узла
+pub fn propagate_risk(graph: &TransactionGraph, method: PropagationMethod) -> RiskScores {
+    let petgraph = graph.graph();
+    let adjacency = undirected_adjacency(graph);
+    let classes: Vec<Option<Class>> = petgraph.node_indices().map(|index| seed_class(graph, index)).collect();
+
+    let seeds = |class: Class| -> Vec<f64> {
+        let count = classes.iter().filter(|known| **known == Some(class)).count();
+        classes.iter().map(|known| if *known == Some(class) { 1.0 / count as f64 } else { 0.0 }).collect()
+    };
+    let (illicit, licit) = (seeds(Class::Illicit), seeds(Class::Licit));
+    let (illicit, licit) = match method {
+        PropagationMethod::LabelSpreading => (label_spreading(&adjacency, &illicit), label_spreading(&adjacency, &licit)),
+        PropagationMethod::PersonalizedPagerank => {
+            (personalized_pagerank(&adjacency, &illicit), personalized_pagerank(&adjacency, &licit))
+        }
+    };
+
+    let nodes: Vec<NodeRisk> = petgraph
+        .node_weights()
+        .enumerate()
+        .map(|(i, node)| {
+            let class = classes[i];
+            let risk = match class {
+                Some(Class::Illicit) => Some(1.0),
+                Some(_) => Some(0.0),
+                None if illicit[i] + licit[i] > 0.0 => Some(illicit[i] / (illicit[i] + licit[i])),
+                None => None,
+            };
+            NodeRisk { id: node.id.clone(), class, risk, propagated: class.is_none() && risk.is_some() }
+        })
+        .collect();
+    let by_node = nodes.iter().enumerate().map(|(i, node)| (node.id.clone(), i)).collect();
+
+    RiskScores {
+        method,
+        illicit_seeds: classes.iter().filter(|class| **class == Some(Class::Illicit)).count(),
+        licit_seeds: classes.iter().filter(|class| **class == Some(Class::Licit)).count(),
+        nodes,
+        by_node,
+    }
+}
+
+/// Итерации `F = alpha * step(F) + (1 - alpha) * Y` до сходимости
+fn iterate(seeds: &[f64], step: impl Fn(&[f64]) -> Vec<f64>) -> Vec<f64> {
+    let mut values = seeds.to_vec();
+    if seeds.iter().all(|&seed| seed == 0.0) {
+        return values;
+    }
+    for _ in 0..RISK_PROPAGATION_MAX_ITERATIONS {
+        let next: Vec<f64> = step(&values)
+            .into_iter()
+            .zip(seeds)
+            .map(|(spread, seed)| RISK_PROPAGATION_ALPHA * spread + (1.0 - RISK_PROPAGATION_ALPHA) * seed)
+            .collect();
+        let delta = values.iter().zip(&next).map(|(a, b)| (a - b).abs()).fold(0.0, f64::max);
+        values = next;
+        if delta < RISK_PROPAGATION_TOLERANCE {
+            break;
+        }
+    }
+    values
+}
+
+/// Распространение меток: `S = D^-1/2 W D^-1/2`
+fn label_spreading(adjacency: &[Vec<(usize, f64)>], seeds: &[f64]) -> Vec<f64> {
+    let degree: Vec<f64> = adjacency.iter().map(|neighbors| neighbors.iter().map(|(_, weight)| weight).sum()).collect();
+    iterate(seeds, |values| {
+        adjacency
+            .iter()
+            .enumerate()
+            .map(|(node, neighbors)| {
+                neighbors
+                    .iter()
+                    .map(|&(neighbor, weight)| weight * values[neighbor] / (degree[node] * degree[neighbor]).sqrt())
+                    .sum()
+            })
+            .collect()
+    })
+}
+
+/// Персонализированный PageRank: случайное блуждание по `W / deg` с
+/// возвратом к размеченным узлам; масса изолированных узлов тоже возвращается
+fn personalized_pagerank(adjacency: &[Vec<(usize, f64)>], seeds: &[f64]) -> Vec<f64> {
+    let degree: Vec<f64> = adjacency.iter().map(|neighbors| neighbors.iter().map(|(_, weight)| weight).sum()).collect();
+    iterate(seeds, |values| {
+        let mut next = vec![0.0; values.len()];
+        let mut stranded = 0.0;
+        for (node, neighbors) in adjacency.iter().enumerate() {
+            if degree[node] == 0.0 {
+                stranded += values[node];
+                continue;
+            }
+            for &(neighbor, weight) in neighbors {
+                next[neighbor] += values[node] * weight / degree[node];
+            }
+        }
+        for (value, seed) in next.iter_mut().zip(seeds) {
+            *value += stranded * seed;
+        }
+        next
+    })
+}
+
+/// Объясняет риск узла его ближайшими размеченными соседями
+///
+/// Соседи ищутся поиском в ширину без учета направления ребер, не дальше
+/// `RISK_EXPLAIN_MAX_DEPTH` ребер; возвращаются до `RISK_EXPLAIN_NEIGHBOURS`
+/// ближайших.
+///
+/// # Arguments
+/// * `graph` - Граф, по которому вычислены риски
+/// * `scores` - Риски узлов
+/// * `id` - Узел
+///
+/// # Returns
+/// `Option<RiskExplanation>` - риск и соседи или `None`, если узла нет в графе
+pub fn explain_risk(graph: &TransactionGraph, scores: &RiskScores, id: &NodeId) -> Option<RiskExplanation> {
+    let node = scores.get(id)?.clone();
+    let start = graph.index_of(id)?;
+    let petgraph = graph.graph();
+
+    let mut neighbours = Vec::new();
+    let mut visited = HashSet::from([start]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((current, distance)) = queue.pop_front() {
+        if neighbours.len() >= RISK_EXPLAIN_NEIGHBOURS {
+            break;
+        }
+        if current != start {
+            if let Some(class) = seed_class(graph, current) {
+                neighbours.push(LabelledNeighbour { id: petgraph[current].id.clone(), class, distance });
+            }
+        }
+        if distance == RISK_EXPLAIN_MAX_DEPTH {
+            continue;
+        }
+        for next in petgraph.neighbors_undirected(current) {
+            if visited.insert(next) {
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    Some(RiskExplanation { node, neighbours })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::graph::transaction_graph::{EdgeDirection, GraphEdge};
+
+    /// illicit t1 → 1A → t2 → 1B → t3 (licit), 1A и 1B не размечены; 1Z изолирован
+    fn sample_graph() -> TransactionGraph {
+        let mut graph = TransactionGraph::new();
+        let edges = [
+            (NodeId::transaction("t1"), NodeId::address("1A"), EdgeDirection::Output),
+            (NodeId::address("1A"), NodeId::transaction("t2"), EdgeDirection::Input),
+            (NodeId::transaction("t2"), NodeId::address("1B"), EdgeDirection::Output),
+            (NodeId::address("1B"), NodeId::transaction("t3"), EdgeDirection::Input),
+        ];
+        for (from, to, direction) in edges {
+            graph.add_edge(from, to, GraphEdge::new(direction, None));
+        }
+        graph.add_node(NodeId::address("1Z"));
+        graph.set_class(&NodeId::transaction("t1"), Class::Illicit);
+        graph.set_class(&NodeId::transaction("t2"), Class::Unknown);
+        graph.set_class(&NodeId::transaction("t3"), Class::Licit);
+        graph
+    }
+
+    #[test]
+    fn test_risk_decreases_with_distance_from_illicit() {
+        let graph = sample_graph();
+        for method in [PropagationMethod::LabelSpreading, PropagationMethod::PersonalizedPagerank] {
+            let scores = propagate_risk(&graph, method);
+            let risk = |id: NodeId| scores.get(&id).and_then(|node| node.risk);
+
+            assert_eq!((scores.illicit_seeds, scores.licit_seeds), (1, 1));
+            assert_eq!(risk(NodeId::transaction("t1")), Some(1.0));
+            let (a, t2, b) = (risk(NodeId::address("1A")), risk(NodeId::transaction("t2")), risk(NodeId::address("1B")));
+            assert!(a > t2 && t2 > b, "{:?}: {:?} {:?} {:?}", method, a, t2, b);
+            assert!((t2.unwrap_or_default() - 0.5).abs() < 1e-9, "{:?}", method);
+            assert_eq!(risk(NodeId::address("1Z")), None);
+        }
+    }
+
+    #[test]
+    fn test_explain_risk_lists_nearest_labels() {
+        let graph = sample_graph();
+        let scores = propagate_risk(&graph, PropagationMethod::default());
+        let explanation = explain_risk(&graph, &scores, &NodeId::address("1A")).expect("Node in graph");
+
+        assert!(explanation.node.propagated);
+        let neighbours: Vec<(&str, Class, usize)> =
+            explanation.neighbours.iter().map(|n| (n.id.key(), n.class, n.distance)).collect();
+        assert_eq!(neighbours, vec![("t1", Class::Illicit, 1), ("t3", Class::Licit, 3)]);
+        assert!(explain_risk(&graph, &scores, &NodeId::address("1Q")).is_none());
+    }
+}
@@ -0,0 +1,408 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use bitcoin::Amount;
+use petgraph::graph::{DiGraph, EdgeReference, NodeIndex};
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+use crate::infrastructure::dataset::schema::{
+    AddrTx, Class, CsvRecord, RecordReader, TxAddr, TxClass, TxEdge, TxTimeStep, WalletFeatures,
+};
+use crate::services::explorer::models::chain::ChainTransaction;
+
+/// Идентификатор узла графа: адрес или транзакция
+///
+/// ID транзакций Elliptic — анонимизированные числа, у транзакций из
+/// explorer'а — txid, поэтому ID хранится строкой.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "id", rename_all = "lowercase")]
+pub enum NodeId {
+    Address(String),
+    Transaction(String),
+}
+
+impl NodeId {
+    /// Узел-адрес
+    pub fn address(address: impl Into<String>) -> Self {
+        NodeId::Address(address.into())
+    }
+
+    /// Узел-транзакция
+    pub fn transaction(tx_id: impl ToString) -> Self {
+        NodeId::Transaction(tx_id.to_string())
+    }
+
+    /// Адрес или ID транзакции без типа узла
+    pub fn key(&self) -> &str {
+        match self {
+            NodeId::Address(key) | NodeId::Transaction(key) => key,
+        }
+    }
+
+    pub fn is_address(&self) -> bool {
+        matches!(self, NodeId::Address(_))
+    }
+
+    pub fn is_transaction(&self) -> bool {
+        matches!(self, NodeId::Transaction(_))
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeId::Address(address) => write!(f, "address:{}", address),
+            NodeId::Transaction(tx_id) => write!(f, "tx:{}", tx_id),
+        }
+    }
+}
+
+/// Узел графа
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphNode {
+    #[serde(flatten)]
+    pub id: NodeId,
+    /// Класс из датасета, если известен
+    pub class: Option<Class>,
+}
+
+/// Направление ребра
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeDirection {
+    /// Адрес → транзакция: адрес тратится во входе транзакции
+    Input,
+    /// Транзакция → адрес: выход транзакции на адрес
+    Output,
+    /// Транзакция → транзакция: выход первой тратится второй
+    Flow,
+}
+
+/// Ребро графа
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub direction: EdgeDirection,
+    /// Сумма перевода, если известна (в датасете Elliptic сумм нет)
+    #[serde(default, with = "bitcoin::amount::serde::as_sat::opt")]
+    pub value: Option<Amount>,
+}
+
+impl GraphEdge {
+    pub fn new(direction: EdgeDirection, value: Option<Amount>) -> Self {
+        GraphEdge { direction, value }
+    }
+}
+
+/// Двудольный граф адресов и транзакций с ребрами транзакция → транзакция
+///
+/// Основа для кластеризации, трассировки и экспорта: узлы типизированы
+/// ([`NodeId`]), ребра хранят направление и сумму. Повторное ребро того же
+/// направления между теми же узлами не создается — суммы складываются.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionGraph {
+    graph: DiGraph<GraphNode, GraphEdge>,
+    index: HashMap<NodeId, NodeIndex>,
+    /// Временные шаги транзакций из `elliptic_txs_features.csv`
+    tx_time_steps: HashMap<String, u8>,
+    /// Первые временные шаги адресов из `wallets_features_classes_combined.csv`
+    address_time_steps: HashMap<String, u8>,
+}
+
+impl TransactionGraph {
+    /// Файлы папки с данными, из которых строится граф
+    pub const SOURCE_FILES: [&'static str; 6] =
+        [AddrTx::FILE, TxAddr::FILE, TxEdge::FILE, TxClass::FILE, TxTimeStep::FILE, WalletFeatures::FILE];
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Загружает граф из папки с данными
+    ///
+    /// Обязательны `AddrTx_edgelist.csv` и `TxAddr_edgelist.csv`; ребра
+    /// `elliptic_txs_edgelist.csv`, классы и временные шаги транзакций и
+    /// адресов добавляются, если файлы есть в папке.
+    ///
+    /// # Arguments
+    /// * `folder` - Путь к папке с CSV файлами
+    ///
+    /// # Returns
+    /// `Result<Self, SchemaError>` - граф или ошибка чтения/схемы файлов
+    pub fn load(folder: &Path) -> Result<Self, SchemaError> {
+        let mut graph = TransactionGraph::new();
+
+        for record in RecordReader::<AddrTx>::open(&folder.join(AddrTx::FILE))? {
+            let record = record?;
+            graph.add_edge(
+                NodeId::address(record.input_address),
+                NodeId::transaction(record.tx_id),
+                GraphEdge::new(EdgeDirection::Input, None),
+            );
+        }
+        for record in RecordReader::<TxAddr>::open(&folder.join(TxAddr::FILE))? {
+            let record = record?;
+            graph.add_edge(
+                NodeId::transaction(record.tx_id),
+                NodeId::address(record.output_address),
+                GraphEdge::new(EdgeDirection::Output, None),
+            );
+        }
+
+        let tx_edges = folder.join(TxEdge::FILE);
+        if tx_edges.is_file() {
+            for record in RecordReader::<TxEdge>::open(&tx_edges)? {
+                let record = record?;
+                graph.add_edge(
+                    NodeId::transaction(record.tx_id1),
+                    NodeId::transaction(record.tx_id2),
+                    GraphEdge::new(EdgeDirection::Flow, None),
+                );
+            }
+        }
+
+        // Классы и временные шаги проставляются только узлам, которые уже есть в графе
+        let tx_classes = folder.join(TxClass::FILE);
+        if tx_classes.is_file() {
+            for record in RecordReader::<TxClass>::open(&tx_classes)? {
+                let record = record?;
+                graph.set_class(&NodeId::transaction(record.tx_id), record.class);
+            }
+        }
+        let tx_features = folder.join(TxTimeStep::FILE);
+        if tx_features.is_file() {
+            for record in RecordReader::<TxTimeStep>::open(&tx_features)? {
+                let record = record?;
+                let id = NodeId::transaction(record.tx_id);
+                if graph.index.contains_key(&id) {
+                    graph.tx_time_steps.insert(id.key().to_string(), record.time_step);
+                }
+            }
+        }
+        let wallets = folder.join(WalletFeatures::FILE);
+        if wallets.is_file() {
+            for record in RecordReader::<WalletFeatures>::open(&wallets)? {
+                let record = record?;
+                let id = NodeId::address(record.address);
+                graph.set_class(&id, record.class);
+                if graph.index.contains_key(&id) {
+                    let NodeId::Address(address) = id else { continue };
+                    let step = graph.address_time_steps.entry(address).or_insert(record.time_step);
+                    *step = (*step).min(record.time_step);
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    /// Добавляет транзакцию из explorer'а: входы, выходы и ребра от родительских транзакций
+    ///
+    /// Входы coinbase и выходы без адреса (OP_RETURN, нестандартные скрипты)
+    /// в адресную часть графа не попадают.
+    pub fn add_chain_transaction(&mut self, tx: &ChainTransaction) -> NodeIndex {
+        let tx_node = self.add_node(NodeId::transaction(&tx.txid));
+        for input in tx.inputs.iter().filter(|input| !input.is_coinbase) {
+            let value = input.prevout.as_ref().map(|prevout| Amount::from_sat(prevout.value));
+            self.add_edge(
+                NodeId::transaction(&input.txid),
+                NodeId::transaction(&tx.txid),
+                GraphEdge::new(EdgeDirection::Flow, value),
+            );
+            if let Some(address) = input.prevout.as_ref().and_then(|prevout| prevout.address.clone()) {
+                self.add_edge(
+                    NodeId::address(address),
+                    NodeId::transaction(&tx.txid),
+                    GraphEdge::new(EdgeDirection::Input, value),
+                );
+            }
+        }
+        for output in &tx.outputs {
+            if let Some(address) = &output.address {
+                self.add_edge(
+                    NodeId::transaction(&tx.txid),
+                    NodeId::address(address.clone()),
+                    GraphEdge::new(EdgeDirection::Output, Some(Amount::from_sat(output.value))),
+                );
+            }
+        }
+        tx_node
+    }
+
+    /// Возвращает индекс узла, добавляя узел при необходимости
+    pub fn add_node(&mut self, id: NodeId) -> NodeIndex {
+        if let Some(&index) = self.index.get(&id) {
+            return index;
+        }
+        let index = self.graph.add_node(GraphNode { id: id.clone(), class: None });
+        self.index.insert(id, index);
+        index
+    }
+
+    /// Добавляет ребро между узлами (узлы создаются при необходимости)
+    ///
+    /// Если ребро того же направления уже есть, суммы складываются;
+    /// неизвестная сумма у любого из ребер делает неизвестной и общую.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, edge: GraphEdge) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        let existing = self
+            .graph
+            .edges_connecting(from, to)
+            .find(|existing| existing.weight().direction == edge.direction)
+            .map(|existing| existing.id());
+        match existing {
+            Some(id) => {
+                let weight = &mut self.graph[id];
+                weight.value = weight.value.zip(edge.value).and_then(|(a, b)| a.checked_add(b));
+            }
+            None => {
+                self.graph.add_edge(from, to, edge);
+            }
+        }
+    }
+
+    /// Проставляет класс узлу, если он есть в графе
+    pub fn set_class(&mut self, id: &NodeId, class: Class) {
+        if let Some(&index) = self.index.get(id) {
+            self.graph[index].class = Some(class);
+        }
+    }
+
+    /// Временной шаг узла из датасета: у адреса — первый шаг, в котором он встречается
+    pub fn time_step(&self, id: &NodeId) -> Option<u8> {
+        match id {
+            NodeId::Transaction(tx_id) => self.tx_time_steps.get(tx_id).copied(),
+            NodeId::Address(address) => self.address_time_steps.get(address).copied(),
+        }
+    }
+
+    /// Граф petgraph для алгоритмов
+    pub fn graph(&self) -> &DiGraph<GraphNode, GraphEdge> {
+        &self.graph
+    }
+
+    /// Индекс узла по ID
+    pub fn index_of(&self, id: &NodeId) -> Option<NodeIndex> {
+        self.index.get(id).copied()
+    }
+
+    /// Узел по ID
+    pub fn node(&self, id: &NodeId) -> Option<&GraphNode> {
+        self.index_of(id).map(|index| &self.graph[index])
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Узлы-адреса
+    pub fn addresses(&self) -> impl Iterator<Item = &GraphNode> {
+        self.graph.node_weights().filter(|node| node.id.is_address())
+    }
+
+    /// Узлы-транзакции
+    pub fn transactions(&self) -> impl Iterator<Item = &GraphNode> {
+        self.graph.node_weights().filter(|node| node.id.is_transaction())
+    }
+
+    /// Адреса входов транзакции
+    pub fn input_addresses(&self, tx: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.neighbors(tx, Direction::Incoming, EdgeDirection::Input)
+    }
+
+    /// Адреса выходов транзакции
+    pub fn output_addresses(&self, tx: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.neighbors(tx, Direction::Outgoing, EdgeDirection::Output)
+    }
+
+    /// Транзакции, в которых адрес тратится
+    pub fn spending_transactions(&self, address: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.neighbors(address, Direction::Outgoing, EdgeDirection::Input)
+    }
+
+    /// Транзакции, в которых адрес получает средства
+    pub fn receiving_transactions(&self, address: NodeIndex) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.neighbors(address, Direction::Incoming, EdgeDirection::Output)
+    }
+
+    /// Ребра узла в заданном направлении
+    pub fn edges(&self, node: NodeIndex, direction: Direction) -> impl Iterator<Item = EdgeReference<'_, GraphEdge>> {
+        self.graph.edges_directed(node, direction)
+    }
+
+    fn neighbors(
+        &self,
+        node: NodeIndex,
+        direction: Direction,
+        kind: EdgeDirection,
+    ) -> impl Iterator<Item = NodeIndex> + '_ {
+        self.graph
+            .edges_directed(node, direction)
+            .filter(move |edge| edge.weight().direction == kind)
+            .map(move |edge| if direction == Direction::Outgoing { edge.target() } else { edge.source() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::models::esplora::{fixture_transaction, SEGWIT_TX};
+    use std::fs;
+
+    #[test]
+    fn test_load_bipartite_graph() {
+        let folder = std::env::temp_dir().join(format!("transaction_graph_{}", std::process::id()));
+        fs::create_dir_all(&folder).expect("Should create folder");
+        let files = [
+            ("AddrTx_edgelist.csv", "input_address,txId\n1A,101\n1B,101\n1A,101\n"),
+            ("TxAddr_edgelist.csv", "txId,output_address\n101,1C\n102,1A\n"),
+            ("elliptic_txs_edgelist.csv", "txId1,txId2\n102,101\n"),
+            ("elliptic_txs_classes.csv", "txId,class\n101,1\n999,2\n"),
+            ("elliptic_txs_features.csv", "101,3,0.5\n999,4,0.5\n"),
+            ("wallets_features_classes_combined.csv", "address,Time step,class\n1A,5,2\n1A,2,2\n101,7,1\n"),
+        ];
+        for (file, content) in files {
+            fs::write(folder.join(file), content).expect("Should write CSV");
+        }
+
+        let graph = TransactionGraph::load(&folder).expect("Should load");
+        assert_eq!(graph.addresses().count(), 3);
+        assert_eq!(graph.transactions().count(), 2);
+        // Повторная строка AddrTx не создает второе ребро
+        assert_eq!(graph.edge_count(), 5);
+
+        let tx = graph.index_of(&NodeId::transaction(101)).expect("Tx node");
+        let mut inputs: Vec<&str> = graph.input_addresses(tx).map(|i| graph.graph()[i].id.key()).collect();
+        inputs.sort_unstable();
+        assert_eq!(inputs, vec!["1A", "1B"]);
+        assert_eq!(graph.node(&NodeId::transaction(101)).and_then(|n| n.class), Some(Class::Illicit));
+        assert!(graph.node(&NodeId::transaction(999)).is_none());
+        assert_eq!(graph.time_step(&NodeId::transaction(101)), Some(3));
+        assert_eq!(graph.time_step(&NodeId::transaction(102)), None);
+        assert_eq!(graph.time_step(&NodeId::address("1A")), Some(2));
+        assert_eq!(graph.time_step(&NodeId::address("101")), None);
+        let _ = fs::remove_dir_all(&folder);
+    }
+
+    #[test]
+    fn test_chain_transaction_edges_carry_values() {
+        let mut graph = TransactionGraph::new();
+        let tx = graph.add_chain_transaction(&fixture_transaction(SEGWIT_TX).into());
+
+        let inputs: Amount = graph
+            .edges(tx, Direction::Incoming)
+            .filter(|edge| edge.weight().direction == EdgeDirection::Flow)
+            .filter_map(|edge| edge.weight().value)
+            .sum();
+        assert_eq!(inputs, Amount::from_sat(96_935_907));
+        // Сдача возвращается на адрес входов, но это отдельное ребро выхода
+        assert_eq!(graph.output_addresses(tx).count(), 3);
+    }
+}
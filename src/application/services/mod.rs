@@ -1 +1,8 @@
-pub mod transaction;
\ No newline at end of file
+pub mod transaction;
+pub mod address;
+pub mod scoring;
+pub mod graph;
+pub mod analysis;
+pub mod clustering;
+pub mod tracing;
+pub mod export;
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::dataset::schema::{CsvRecord, RecordReader, TxEdge, TxFeatures};
+use crate::infrastructure::model::aml_gnn::{gnn_features_from_elliptic_row, AmlGnn};
+
+/// Оценка транзакции локальной графовой моделью
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransactionScore {
+    /// ID транзакции
+    pub tx_id: String,
+    /// Вероятность класса «illicit»
+    pub illicit_probability: f32,
+}
+
+/// Оценивает все транзакции папки с данными Elliptic без обращения к Python сервису
+///
+/// Узлы графа строятся по `elliptic_txs_features.csv`, ребра — по
+/// `elliptic_txs_edgelist.csv` (ребра на неизвестные транзакции пропускаются).
+///
+/// # Arguments
+/// * `model` - Загруженная модель `aml_bitcoin.pth`
+/// * `folder` - Путь к папке с CSV файлами
+///
+/// # Returns
+/// `Result<Vec<TransactionScore>, Box<dyn std::error::Error>>` - оценки в порядке строк файла признаков
+pub fn score_elliptic_folder(model: &AmlGnn, folder: &Path) -> Result<Vec<TransactionScore>, Box<dyn std::error::Error>> {
+    let mut tx_ids: Vec<u64> = Vec::new();
+    let mut features: Vec<Vec<f32>> = Vec::new();
+
+    // В оригинальном Elliptic файл признаков без заголовка, в Elliptic++ — с заголовком
+    for record in RecordReader::<TxFeatures>::open(&folder.join(TxFeatures::FILE))? {
+        let record = record?;
+        let mut values = Vec::with_capacity(record.features.len() + 1);
+        values.push(f64::from(record.time_step));
+        values.extend(&record.features);
+        tx_ids.push(record.tx_id);
+        features.push(gnn_features_from_elliptic_row(&values));
+    }
+
+    let index: HashMap<u64, usize> = tx_ids.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+    let mut edges = Vec::new();
+    let edgelist = folder.join(TxEdge::FILE);
+    if edgelist.exists() {
+        for record in RecordReader::<TxEdge>::open(&edgelist)? {
+            let record = record?;
+            if let (Some(from), Some(to)) = (index.get(&record.tx_id1), index.get(&record.tx_id2)) {
+                edges.push((*from, *to));
+            }
+        }
+    }
+
+    let scores = model.score_subgraph(&features, &edges)?;
+    Ok(tx_ids
+        .into_iter()
+        .zip(scores)
+        .map(|(tx_id, score)| TransactionScore {
+            tx_id: tx_id.to_string(),
+            illicit_probability: score.illicit_probability,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::model::aml_gnn::GNN_INPUT_FEATURES;
+    use std::fs;
+
+    /// Строка признаков: txId, time step и 93 локальных признака
+    fn features_row(tx_id: u64, seed: usize) -> String {
+        let values: Vec<String> = (0..GNN_INPUT_FEATURES - 1)
+            .map(|j| format!("{:.1}", ((seed * 7 + j) % 11) as f32 / 10.0 - 0.5))
+            .collect();
+        format!("{},{},{}", tx_id, 1 + seed, values.join(","))
+    }
+
+    fn expected_scores(model: &AmlGnn, rows: &[String], edges: &[(usize, usize)]) -> Vec<f32> {
+        let features: Vec<Vec<f32>> = rows
+            .iter()
+            .map(|row| {
+                let values: Vec<f64> = row.split(',').skip(1).map(|v| v.parse().expect("Number")).collect();
+                gnn_features_from_elliptic_row(&values)
+            })
+            .collect();
+        model
+            .score_subgraph(&features, edges)
+            .expect("Should score subgraph")
+            .into_iter()
+            .map(|score| score.illicit_probability)
+            .collect()
+    }
+
+    #[test]
+    fn test_score_elliptic_folder() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/infrastructure/model/aml_bitcoin.pth");
+        let model = AmlGnn::load(path).expect("Should load model");
+        let rows = vec![features_row(101, 0), features_row(102, 1), features_row(103, 2)];
+        let feature_names: Vec<String> = (1..GNN_INPUT_FEATURES).map(|i| format!("Local_feature_{}", i)).collect();
+        let headered = format!("txId,Time step,{}\n{}\n", feature_names.join(","), rows.join("\n"));
+        let headerless = format!("{}\n", rows.join("\n"));
+        // Ребра на 999 и от 998 ссылаются на транзакции вне файла признаков
+        let edgelist = "txId1,txId2\n101,102\n102,999\n998,103\n";
+        let expected = expected_scores(&model, &rows, &[(0, 1)]);
+
+        for (name, features) in [("headerless", headerless), ("headered", headered)] {
+            let folder = std::env::temp_dir().join(format!("gnn_scoring_{}_{}", name, std::process::id()));
+            fs::create_dir_all(&folder).expect("Should create folder");
+            fs::write(folder.join(TxFeatures::FILE), features).expect("Should write features");
+            fs::write(folder.join(TxEdge::FILE), edgelist).expect("Should write edges");
+
+            let scores = score_elliptic_folder(&model, &folder).expect("Should score folder");
+            let ids: Vec<&str> = scores.iter().map(|s| s.tx_id.as_str()).collect();
+            assert_eq!(ids, vec!["101", "102", "103"], "{}", name);
+            for (score, expected) in scores.iter().zip(&expected) {
+                assert!((score.illicit_probability - expected).abs() < 1e-6, "{}", name);
+            }
+
+            fs::remove_dir_all(&folder).expect("Should remove folder");
+        }
+
+        // Без файла ребер все узлы изолированы
+        let folder = std::env::temp_dir().join(format!("gnn_scoring_no_edges_{}", std::process::id()));
+        fs::create_dir_all(&folder).expect("Should create folder");
+        fs::write(folder.join(TxFeatures::FILE), rows.join("\n")).expect("Should write features");
+        let scores = score_elliptic_folder(&model, &folder).expect("Should score folder");
+        let isolated = expected_scores(&model, &rows, &[]);
+        for (score, expected) in scores.iter().zip(&isolated) {
+            assert!((score.illicit_probability - expected).abs() < 1e-6);
+        }
+        fs::remove_dir_all(&folder).expect("Should remove folder");
+    }
+}
@@ -0,0 +1 @@
+pub mod gnn_scoring;
@@ -0,0 +1 @@
+pub mod taint_tracing;
@@ -0,0 +1,572 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use bitcoin::Amount;
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::constants::{
+    DEFAULT_TRACE_MAX_HOPS, DEFAULT_TRACE_MIN_VALUE_SATS, MAX_ADDRESS_HISTORY_PAGES, MAX_TRACE_TRANSACTIONS,
+};
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::explorer_client::ExplorerClient;
+use crate::services::explorer::models::chain::{ChainOutspend, ChainTransaction};
+
+/// Начало трассировки: выход транзакции или адрес
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceStart {
+    /// Выход `txid:vout`
+    Output { txid: String, vout: u32 },
+    /// Все выходы, полученные адресом
+    Address(String),
+}
+
+impl FromStr for TraceStart {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((txid, vout)) => {
+                let vout = vout.parse().map_err(|_| format!("invalid output index in {}", s))?;
+                Ok(TraceStart::Output { txid: txid.to_string(), vout })
+            }
+            None if !s.is_empty() => Ok(TraceStart::Address(s.to_string())),
+            None => Err("expected txid:vout or address".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for TraceStart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceStart::Output { txid, vout } => write!(f, "{}:{}", txid, vout),
+            TraceStart::Address(address) => write!(f, "{}", address),
+        }
+    }
+}
+
+/// Направление трассировки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceDirection {
+    /// По тратам выходов: куда ушли средства
+    #[default]
+    Forward,
+    /// По входам: откуда пришли средства
+    Backward,
+}
+
+/// Модель распространения загрязнения через транзакцию
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaintModel {
+    /// Любая загрязненная сумма загрязняет все выходы целиком
+    Poison,
+    /// Каждый выход загрязнен в доле загрязненных средств на входах
+    #[default]
+    Haircut,
+    /// Входы и выходы сопоставляются по порядку: первые сатоши входов
+    /// уходят в первые выходы, остаток — в комиссию
+    Fifo,
+}
+
+/// Параметры трассировки
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceOptions {
+    pub direction: TraceDirection,
+    pub model: TaintModel,
+    /// Глубина в транзакциях от начальных выходов
+    pub max_hops: usize,
+    /// Выходы с меньшей загрязненной суммой дальше не отслеживаются
+    pub min_value: Amount,
+    /// Ограничение размера результата
+    pub max_transactions: usize,
+}
+
+impl Default for TraceOptions {
+    fn default() -> Self {
+        TraceOptions {
+            direction: TraceDirection::default(),
+            model: TaintModel::default(),
+            max_hops: DEFAULT_TRACE_MAX_HOPS,
+            min_value: Amount::from_sat(DEFAULT_TRACE_MIN_VALUE_SATS),
+            max_transactions: MAX_TRACE_TRANSACTIONS,
+        }
+    }
+}
+
+/// Транзакция, через которую прошли загрязненные средства
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaintedTransaction {
+    pub txid: String,
+    /// Расстояние от начальных выходов в транзакциях
+    pub hop: usize,
+    /// Загрязненная сумма на входах
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub tainted_in: Amount,
+    /// Загрязненная сумма на выходах
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub tainted_out: Amount,
+    /// Время блока (unix timestamp); `None` для неподтвержденной транзакции
+    pub block_time: Option<u64>,
+}
+
+/// Ребро результата: выход `txid:vout`, потраченный транзакцией `spent_by`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaintEdge {
+    pub txid: String,
+    pub vout: u32,
+    pub address: Option<String>,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub value: Amount,
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub tainted: Amount,
+    /// Тратящая транзакция (`None` — выход не потрачен или это начальный выход обратной трассировки)
+    pub spent_by: Option<String>,
+}
+
+/// Подграф движения загрязненных средств
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaintTrace {
+    pub start: String,
+    pub direction: TraceDirection,
+    pub model: TaintModel,
+    pub transactions: Vec<TaintedTransaction>,
+    pub edges: Vec<TaintEdge>,
+    /// Трассировка остановлена по `max_transactions`
+    pub truncated: bool,
+}
+
+/// Распределяет загрязнение одной стороны транзакции на другую
+///
+/// # Arguments
+/// * `model` - Модель распространения
+/// * `sources` - Суммы и загрязненные суммы стороны, откуда идет загрязнение
+/// * `targets` - Суммы другой стороны по порядку
+///
+/// # Returns
+/// `Vec<u64>` - загрязненная сумма каждого элемента `targets` в сатоши
+pub fn distribute(model: TaintModel, sources: &[(u64, u64)], targets: &[u64]) -> Vec<u64> {
+    let tainted_total: u64 = sources.iter().map(|(_, tainted)| tainted).sum();
+    if tainted_total == 0 {
+        return vec![0; targets.len()];
+    }
+    match model {
+        TaintModel::Poison => targets.to_vec(),
+        TaintModel::Haircut => {
+            let source_total: u64 = sources.iter().map(|(value, _)| value).sum();
+            targets
+                .iter()
+                .map(|&value| {
+                    let share = value as u128 * tainted_total as u128 / source_total.max(1) as u128;
+                    share.min(value as u128) as u64
+                })
+                .collect()
+        }
+        TaintModel::Fifo => {
+            // Загрязненная часть каждого источника — его первые сатоши
+            let mut tainted_ranges = Vec::new();
+            let mut position = 0;
+            for &(value, tainted) in sources {
+                tainted_ranges.push((position, position + tainted.min(value)));
+                position += value;
+            }
+            let mut position = 0;
+            targets
+                .iter()
+                .map(|&value| {
+                    let (start, end) = (position, position + value);
+                    position = end;
+                    tainted_ranges
+                        .iter()
+                        .map(|&(from, to)| to.min(end).saturating_sub(from.max(start)))
+                        .sum()
+                })
+                .collect()
+        }
+    }
+}
+
+/// Обходит граф транзакций через explorer
+struct Tracer<'a> {
+    client: &'a ExplorerClient,
+    options: TraceOptions,
+    transactions: Vec<TaintedTransaction>,
+    edges: Vec<TaintEdge>,
+    visited: HashSet<String>,
+    truncated: bool,
+}
+
+/// Загрязнение, пришедшее в транзакцию: номер входа (или выхода) → сумма
+type Frontier = BTreeMap<String, BTreeMap<u32, u64>>;
+
+impl Tracer<'_> {
+    /// Начальные выходы и их транзакции
+    async fn seeds(&self, start: &TraceStart) -> Result<Vec<(ChainTransaction, u32)>, ExplorerError> {
+        match start {
+            TraceStart::Output { txid, vout } => {
+                let tx = self.client.get_transaction(txid).await?;
+                if tx.outputs.len() <= *vout as usize {
+                    return Err(ExplorerError::NotFound(format!("output {}:{}", txid, vout)));
+                }
+                Ok(vec![(tx, *vout)])
+            }
+            TraceStart::Address(address) => {
+                let history = self.client.get_address_transactions(address, MAX_ADDRESS_HISTORY_PAGES).await?;
+                Ok(history
+                    .into_iter()
+                    .flat_map(|tx| {
+                        let vouts: Vec<u32> = (0..tx.outputs.len() as u32)
+                            .filter(|&vout| tx.outputs[vout as usize].address.as_deref() == Some(address.as_str()))
+                            .collect();
+                        vouts.into_iter().map(move |vout| (tx.clone(), vout))
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Записывает загрязненные выходы транзакции и добавляет их траты в `next`
+    async fn record_outputs(&mut self, tx: &ChainTransaction, tainted: &[(u32, u64)], next: &mut Frontier, follow: bool) -> Result<(), ExplorerError> {
+        let outspends: Vec<ChainOutspend> = self.client.get_outspends(&tx.txid).await?;
+        for &(vout, amount) in tainted {
+            let output = &tx.outputs[vout as usize];
+            let spend = outspends.get(vout as usize).filter(|outspend| outspend.spent);
+            let spent_by = spend.and_then(|outspend| outspend.txid.clone());
+            self.edges.push(TaintEdge {
+                txid: tx.txid.clone(),
+                vout,
+                address: output.address.clone(),
+                value: Amount::from_sat(output.value),
+                tainted: Amount::from_sat(amount),
+                spent_by: spent_by.clone(),
+            });
+            if let (true, Some(spender), Some(vin)) = (follow && amount >= self.options.min_value.to_sat(), spent_by, spend.and_then(|s| s.vin)) {
+                *next.entry(spender).or_default().entry(vin).or_default() += amount;
+            }
+        }
+        Ok(())
+    }
+
+    async fn forward(&mut self, seeds: Vec<(ChainTransaction, u32)>) -> Result<(), ExplorerError> {
+        let mut frontier = Frontier::new();
+        for (tx, vout) in &seeds {
+            let value = tx.outputs[*vout as usize].value;
+            self.record_outputs(tx, &[(*vout, value)], &mut frontier, self.options.max_hops > 0).await?;
+        }
+
+        for hop in 1..=self.options.max_hops {
+            let mut next = Frontier::new();
+            for (txid, tainted_inputs) in std::mem::take(&mut frontier) {
+                if !self.enter(&txid) {
+                    continue;
+                }
+                let tx = self.client.get_transaction(&txid).await?;
+                let sources: Vec<(u64, u64)> = tx
+                    .inputs
+                    .iter()
+                    .enumerate()
+                    .map(|(vin, input)| {
+                        let value = input.prevout.as_ref().map_or(0, |prevout| prevout.value);
+                        (value, tainted_inputs.get(&(vin as u32)).copied().unwrap_or(0))
+                    })
+                    .collect();
+                let targets: Vec<u64> = tx.outputs.iter().map(|output| output.value).collect();
+                let tainted_outputs: Vec<(u32, u64)> = distribute(self.options.model, &sources, &targets)
+                    .into_iter()
+                    .enumerate()
+                    .filter(|&(_, amount)| amount > 0)
+                    .map(|(vout, amount)| (vout as u32, amount))
+                    .collect();
+
+                self.transactions.push(TaintedTransaction {
+                    txid: txid.clone(),
+                    hop,
+                    tainted_in: Amount::from_sat(sources.iter().map(|(_, tainted)| tainted).sum()),
+                    tainted_out: Amount::from_sat(tainted_outputs.iter().map(|(_, amount)| amount).sum()),
+                    block_time: tx.status.block_time,
+                });
+                self.record_outputs(&tx, &tainted_outputs, &mut next, hop < self.options.max_hops).await?;
+            }
+            frontier = next;
+        }
+        Ok(())
+    }
+
+    async fn backward(&mut self, seeds: Vec<(ChainTransaction, u32)>) -> Result<(), ExplorerError> {
+        let mut frontier = Frontier::new();
+        for (tx, vout) in &seeds {
+            let output = &tx.outputs[*vout as usize];
+            self.edges.push(TaintEdge {
+                txid: tx.txid.clone(),
+                vout: *vout,
+                address: output.address.clone(),
+                value: Amount::from_sat(output.value),
+                tainted: Amount::from_sat(output.value),
+                spent_by: None,
+            });
+            *frontier.entry(tx.txid.clone()).or_default().entry(*vout).or_default() += output.value;
+        }
+
+        for hop in 1..=self.options.max_hops {
+            let mut next = Frontier::new();
+            for (txid, tainted_outputs) in std::mem::take(&mut frontier) {
+                if !self.enter(&txid) {
+                    continue;
+                }
+                let tx = self.client.get_transaction(&txid).await?;
+                let sources: Vec<(u64, u64)> = tx
+                    .outputs
+                    .iter()
+                    .enumerate()
+                    .map(|(vout, output)| (output.value, tainted_outputs.get(&(vout as u32)).copied().unwrap_or(0)))
+                    .collect();
+                let targets: Vec<u64> = tx
+                    .inputs
+                    .iter()
+                    .map(|input| input.prevout.as_ref().map_or(0, |prevout| prevout.value))
+                    .collect();
+                let tainted_inputs = distribute(self.options.model, &sources, &targets);
+
+                self.transactions.push(TaintedTransaction {
+                    txid: txid.clone(),
+                    hop,
+                    tainted_in: Amount::from_sat(tainted_inputs.iter().sum()),
+                    tainted_out: Amount::from_sat(sources.iter().map(|(_, tainted)| tainted).sum()),
+                    block_time: tx.status.block_time,
+                });
+                for (input, amount) in tx.inputs.iter().zip(tainted_inputs) {
+                    let Some(prevout) = input.prevout.as_ref().filter(|_| amount > 0 && !input.is_coinbase) else {
+                        continue;
+                    };
+                    self.edges.push(TaintEdge {
+                        txid: input.txid.clone(),
+                        vout: input.vout,
+                        address: prevout.address.clone(),
+                        value: Amount::from_sat(prevout.value),
+                        tainted: Amount::from_sat(amount),
+                        spent_by: Some(txid.clone()),
+                    });
+                    if hop < self.options.max_hops && amount >= self.options.min_value.to_sat() {
+                        *next.entry(input.txid.clone()).or_default().entry(input.vout).or_default() += amount;
+                    }
+                }
+            }
+            frontier = next;
+        }
+        Ok(())
+    }
+
+    /// Отмечает транзакцию обработанной; false, если она уже была или достигнут лимит
+    fn enter(&mut self, txid: &str) -> bool {
+        if self.visited.contains(txid) {
+            return false;
+        }
+        if self.visited.len() >= self.options.max_transactions {
+            self.truncated = true;
+            return false;
+        }
+        self.visited.insert(txid.to_string())
+    }
+}
+
+/// Отслеживает движение средств от выхода или адреса
+///
+/// Обход идет по уровням: на каждом шаге загрязнение, пришедшее в
+/// транзакцию со всех ее входов (выходов при обратной трассировке),
+/// распределяется моделью [`TaintModel`] и передается дальше через
+/// статусы трат (`outspends`) или `prevout` входов. Каждая транзакция
+/// обрабатывается один раз — при первом достижении.
+///
+/// # Arguments
+/// * `client` - Клиент Bitcoin explorer
+/// * `start` - Начальный выход или адрес
+/// * `options` - Направление, модель и ограничения обхода
+///
+/// # Returns
+/// `Result<TaintTrace, ExplorerError>` - подграф с загрязненными суммами на ребрах
+pub async fn trace_taint(client: &ExplorerClient, start: &TraceStart, options: TraceOptions) -> Result<TaintTrace, ExplorerError> {
+    let mut tracer = Tracer {
+        client,
+        options,
+        transactions: Vec::new(),
+        edges: Vec::new(),
+        visited: HashSet::new(),
+        truncated: false,
+    };
+    let seeds = tracer.seeds(start).await?;
+    match options.direction {
+        TraceDirection::Forward => tracer.forward(seeds).await?,
+        TraceDirection::Backward => tracer.backward(seeds).await?,
+    }
+
+    Ok(TaintTrace {
+        start: start.to_string(),
+        direction: options.direction,
+        model: options.model,
+        transactions: tracer.transactions,
+        edges: tracer.edges,
+        truncated: tracer.truncated,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::backends::chain_backend::ChainBackend;
+    use crate::services::explorer::models::chain::{ChainAddressStats, ChainInput, ChainOutput, ChainStatus};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    /// Backend над набором транзакций в памяти; траты выходов вычисляются по входам
+    struct MemoryBackend {
+        transactions: HashMap<String, ChainTransaction>,
+    }
+
+    #[async_trait]
+    impl ChainBackend for MemoryBackend {
+        fn name(&self) -> &'static str {
+            "memory"
+        }
+
+        async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+            self.transactions.get(txid).cloned().ok_or_else(|| ExplorerError::NotFound(format!("/tx/{}", txid)))
+        }
+
+        async fn get_outspends(&self, txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+            let tx = self.get_transaction(txid).await?;
+            Ok((0..tx.outputs.len() as u32)
+                .map(|vout| {
+                    let spender = self.transactions.values().find_map(|spender| {
+                        let vin = spender.inputs.iter().position(|input| input.txid == txid && input.vout == vout)?;
+                        Some((spender.txid.clone(), vin as u32))
+                    });
+                    ChainOutspend {
+                        spent: spender.is_some(),
+                        txid: spender.as_ref().map(|(txid, _)| txid.clone()),
+                        vin: spender.map(|(_, vin)| vin),
+                        status: None,
+                    }
+                })
+                .collect())
+        }
+
+        async fn get_address_stats(&self, address: &str) -> Result<ChainAddressStats, ExplorerError> {
+            let history: Vec<ChainTransaction> = self.get_address_transactions(address, 1).await?;
+            Ok(ChainAddressStats::from_history(address, &history))
+        }
+
+        async fn get_address_transactions(&self, address: &str, _max_pages: usize) -> Result<Vec<ChainTransaction>, ExplorerError> {
+            let mut history: Vec<ChainTransaction> = self
+                .transactions
+                .values()
+                .filter(|tx| {
+                    tx.outputs.iter().any(|output| output.address.as_deref() == Some(address))
+                        || tx.inputs.iter().filter_map(|input| input.prevout.as_ref()).any(|prevout| prevout.address.as_deref() == Some(address))
+                })
+                .cloned()
+                .collect();
+            history.sort_by(|a, b| a.txid.cmp(&b.txid));
+            Ok(history)
+        }
+
+        async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+            Ok(0)
+        }
+    }
+
+    fn output(address: &str, value: u64) -> ChainOutput {
+        ChainOutput { script_pubkey: String::new(), address: Some(address.to_string()), value }
+    }
+
+    /// Транзакция, тратящая выходы `(txid, vout, address, value)`; без входов — coinbase
+    fn chain_tx(txid: &str, inputs: &[(&str, u32, &str, u64)], outputs: &[(&str, u64)]) -> ChainTransaction {
+        let inputs = if inputs.is_empty() {
+            vec![ChainInput { txid: "0".repeat(64), vout: u32::MAX, prevout: None, is_coinbase: true }]
+        } else {
+            inputs
+                .iter()
+                .map(|&(parent, vout, address, value)| ChainInput {
+                    txid: parent.to_string(),
+                    vout,
+                    prevout: Some(output(address, value)),
+                    is_coinbase: false,
+                })
+                .collect()
+        };
+        ChainTransaction {
+            txid: txid.to_string(),
+            inputs,
+            outputs: outputs.iter().map(|&(address, value)| output(address, value)).collect(),
+            fee: None,
+            status: ChainStatus::default(),
+        }
+    }
+
+    /// t0 (A 100 000) и tx (X 100 000) → t1 (C 150 000, D 40 000) → t2 (E 149 000)
+    fn sample_chain() -> ExplorerClient {
+        let transactions = [
+            chain_tx("t0", &[], &[("A", 100_000), ("B", 50_000)]),
+            chain_tx("tx", &[], &[("X", 100_000)]),
+            chain_tx("t1", &[("t0", 0, "A", 100_000), ("tx", 0, "X", 100_000)], &[("C", 150_000), ("D", 40_000)]),
+            chain_tx("t2", &[("t1", 0, "C", 150_000)], &[("E", 149_000)]),
+        ];
+        let transactions = transactions.into_iter().map(|tx| (tx.txid.clone(), tx)).collect();
+        ExplorerClient::with_backend(MemoryBackend { transactions })
+    }
+
+    fn tainted(trace: &TaintTrace, txid: &str, vout: u32) -> Option<u64> {
+        trace.edges.iter().find(|edge| edge.txid == txid && edge.vout == vout).map(|edge| edge.tainted.to_sat())
+    }
+
+    #[test]
+    fn test_distribute_models() {
+        let sources = [(100, 100), (100, 0)];
+        assert_eq!(distribute(TaintModel::Poison, &sources, &[150, 40]), vec![150, 40]);
+        assert_eq!(distribute(TaintModel::Haircut, &sources, &[150, 40]), vec![75, 20]);
+        assert_eq!(distribute(TaintModel::Fifo, &sources, &[150, 40]), vec![100, 0]);
+        assert_eq!(distribute(TaintModel::Fifo, &[(100, 0), (100, 100)], &[150, 40]), vec![50, 40]);
+        assert_eq!(distribute(TaintModel::Poison, &[(100, 0)], &[100]), vec![0]);
+    }
+
+    #[actix_web::test]
+    async fn test_forward_trace_models() {
+        let client = sample_chain();
+        let start: TraceStart = "t0:0".parse().expect("Start");
+
+        let trace = trace_taint(&client, &start, TraceOptions::default()).await.expect("Trace");
+        let txids: Vec<(&str, usize)> = trace.transactions.iter().map(|tx| (tx.txid.as_str(), tx.hop)).collect();
+        assert_eq!(txids, vec![("t1", 1), ("t2", 2)]);
+        assert_eq!((tainted(&trace, "t1", 0), tainted(&trace, "t1", 1)), (Some(75_000), Some(20_000)));
+        assert_eq!(tainted(&trace, "t2", 0), Some(74_500));
+        assert_eq!(trace.edges[0].spent_by.as_deref(), Some("t1"));
+
+        let fifo = TraceOptions { model: TaintModel::Fifo, ..TraceOptions::default() };
+        let trace = trace_taint(&client, &start, fifo).await.expect("Trace");
+        assert_eq!((tainted(&trace, "t1", 0), tainted(&trace, "t1", 1)), (Some(100_000), None));
+        assert_eq!(tainted(&trace, "t2", 0), Some(100_000));
+
+        let poison = TraceOptions { model: TaintModel::Poison, max_hops: 1, ..TraceOptions::default() };
+        let trace = trace_taint(&client, &start, poison).await.expect("Trace");
+        assert_eq!(trace.transactions.len(), 1);
+        assert_eq!(tainted(&trace, "t1", 1), Some(40_000));
+        assert_eq!(tainted(&trace, "t2", 0), None);
+    }
+
+    #[actix_web::test]
+    async fn test_backward_trace_from_address() {
+        let client = sample_chain();
+        let options = TraceOptions { direction: TraceDirection::Backward, ..TraceOptions::default() };
+        let trace = trace_taint(&client, &TraceStart::Address("E".to_string()), options).await.expect("Trace");
+
+        let txids: Vec<&str> = trace.transactions.iter().map(|tx| tx.txid.as_str()).collect();
+        assert_eq!(txids, vec!["t2", "t1", "t0", "tx"]);
+        // Вход t1 полностью оплатил выход E и комиссию
+        assert_eq!(tainted(&trace, "t1", 0), Some(150_000));
+        assert_eq!(tainted(&trace, "t0", 0), Some(78_947));
+        assert_eq!(tainted(&trace, "tx", 0), Some(78_947));
+
+        let missing = trace_taint(&client, &"t0:7".parse().expect("Start"), options).await;
+        assert!(matches!(missing, Err(ExplorerError::NotFound(_))));
+    }
+}
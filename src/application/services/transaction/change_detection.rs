@@ -0,0 +1,362 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::{Address, AddressType, Network, ScriptBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::constants::{CHANGE_PROBABILITY_THRESHOLD, ROUND_VALUE_SATS};
+use futures::future::try_join_all;
+
+use crate::services::explorer::errors::explorer_errors::{supported, ExplorerError};
+use crate::services::explorer::explorer_client::ExplorerClient;
+use crate::services::explorer::models::chain::{ChainAddressStats, ChainOutput, ChainTransaction};
+
+/// Эвристика определения сдачи
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeHeuristic {
+    /// Выход на один из адресов входов
+    AddressReuse,
+    /// Круглая сумма — скорее платеж, некруглая — сдача
+    RoundNumber,
+    /// Если бы выход был платежом, один из входов оказался бы лишним
+    UnnecessaryInput,
+    /// Адрес выхода ранее не получал средств
+    FreshAddress,
+    /// Тип скрипта выхода совпадает с типом скриптов входов
+    ScriptType,
+    /// Выход меньше любого входа
+    OptimalChange,
+}
+
+impl ChangeHeuristic {
+    /// Уверенность эвристики, когда она выделяет часть выходов
+    pub fn confidence(self) -> f64 {
+        match self {
+            ChangeHeuristic::AddressReuse => 0.95,
+            ChangeHeuristic::RoundNumber => 0.7,
+            ChangeHeuristic::UnnecessaryInput => 0.6,
+            ChangeHeuristic::FreshAddress => 0.65,
+            ChangeHeuristic::ScriptType => 0.7,
+            ChangeHeuristic::OptimalChange => 0.6,
+        }
+    }
+}
+
+/// Голос эвристики за выход
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeuristicVote {
+    pub heuristic: ChangeHeuristic,
+    /// true — выход похож на сдачу, false — на платеж
+    pub change: bool,
+    pub confidence: f64,
+}
+
+/// Итог определения сдачи для выхода
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputChange {
+    pub vout: u32,
+    pub address: Option<String>,
+    /// Сумма в сатоши
+    pub value: u64,
+    /// Голоса сработавших эвристик
+    pub votes: Vec<HeuristicVote>,
+    /// Вероятность того, что выход — сдача, по всем голосам
+    pub change_probability: f64,
+    pub is_change: bool,
+}
+
+/// Результат определения сдачи в транзакции
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeDetection {
+    pub txid: String,
+    pub outputs: Vec<OutputChange>,
+    /// Номер выхода-сдачи, если он определен однозначно
+    pub change_vout: Option<u32>,
+}
+
+impl ChangeDetection {
+    /// Выход, признанный сдачей
+    pub fn change_output(&self) -> Option<&OutputChange> {
+        self.change_vout.and_then(|vout| self.outputs.iter().find(|output| output.vout == vout))
+    }
+
+    /// Сумма сдачи в сатоши (0, если сдача не определена)
+    pub fn change_value(&self) -> u64 {
+        self.change_output().map_or(0, |output| output.value)
+    }
+}
+
+/// Тип скрипта выхода: по `script_pubkey`, а если его нет — по адресу
+fn script_type(output: &ChainOutput) -> Option<AddressType> {
+    if let Ok(script) = ScriptBuf::from_hex(&output.script_pubkey) {
+        if let Some(address_type) = Address::from_script(&script, Network::Bitcoin).ok().and_then(|a| a.address_type()) {
+            return Some(address_type);
+        }
+    }
+    let address = output.address.as_deref()?;
+    Address::<NetworkUnchecked>::from_str(address).ok()?.assume_checked().address_type()
+}
+
+/// Признак эвристики для каждого кандидата или `None`, если она неприменима
+///
+/// `true` у выхода означает, что эвристика выделяет его в сторону `marks_change`.
+/// Без статистики адресов неприменима только эвристика нового адреса.
+fn heuristic_flags(
+    heuristic: ChangeHeuristic,
+    tx: &ChainTransaction,
+    candidates: &[&ChainOutput],
+    address_stats: Option<&HashMap<String, ChainAddressStats>>,
+) -> Option<Vec<bool>> {
+    let prevouts: Vec<&ChainOutput> = tx.inputs.iter().filter_map(|input| input.prevout.as_ref()).collect();
+    let all_prevouts_known = prevouts.len() == tx.inputs.len();
+
+    match heuristic {
+        ChangeHeuristic::AddressReuse => {
+            let input_addresses: HashSet<&str> = prevouts.iter().filter_map(|prevout| prevout.address.as_deref()).collect();
+            Some(candidates.iter().map(|output| output.address.as_deref().is_some_and(|a| input_addresses.contains(a))).collect())
+        }
+        ChangeHeuristic::RoundNumber => Some(candidates.iter().map(|output| output.value % ROUND_VALUE_SATS == 0).collect()),
+        ChangeHeuristic::UnnecessaryInput => {
+            if !all_prevouts_known || prevouts.len() < 2 {
+                return None;
+            }
+            let fee = tx.computed_fee()?.to_sat();
+            let input_sum: u64 = prevouts.iter().map(|prevout| prevout.value).sum();
+            let min_input = prevouts.iter().map(|prevout| prevout.value).min()?;
+            Some(candidates.iter().map(|output| input_sum - min_input >= output.value + fee).collect())
+        }
+        ChangeHeuristic::FreshAddress => {
+            let address_stats = address_stats?;
+            candidates
+                .iter()
+                .map(|output| {
+                    let stats = address_stats.get(output.address.as_deref()?)?;
+                    Some(stats.funded_txo_count <= 1)
+                })
+                .collect()
+        }
+        ChangeHeuristic::ScriptType => {
+            let input_types: HashSet<AddressType> = prevouts.iter().map(|prevout| script_type(prevout)).collect::<Option<_>>()?;
+            if input_types.len() != 1 {
+                return None;
+            }
+            candidates
+                .iter()
+                .map(|output| script_type(output).map(|output_type| input_types.contains(&output_type)))
+                .collect()
+        }
+        ChangeHeuristic::OptimalChange => {
+            if !all_prevouts_known {
+                return None;
+            }
+            let min_input = prevouts.iter().map(|prevout| prevout.value).min()?;
+            Some(candidates.iter().map(|output| output.value < min_input).collect())
+        }
+    }
+}
+
+/// Определяет выход-сдачу транзакции
+///
+/// Каждая эвристика голосует, только если выделяет часть выходов: выделенные
+/// выходы получают голос в ее сторону, остальные — в противоположную.
+/// Голоса складываются как логарифмы шансов, сдачей признается выход
+/// с наибольшей вероятностью выше `CHANGE_PROBABILITY_THRESHOLD`.
+/// Выходы без адреса (OP_RETURN) не рассматриваются, у coinbase и
+/// транзакций с одним выходом сдачи нет.
+///
+/// # Arguments
+/// * `tx` - Транзакция
+/// * `address_stats` - Статистика адресов выходов (для эвристики нового адреса),
+///   `None`, если backend ее не поддерживает
+///
+/// # Returns
+/// `ChangeDetection` - голоса эвристик и решение по каждому выходу
+pub fn detect_change(tx: &ChainTransaction, address_stats: Option<&HashMap<String, ChainAddressStats>>) -> ChangeDetection {
+    let candidates: Vec<(u32, &ChainOutput)> = tx
+        .outputs
+        .iter()
+        .enumerate()
+        .filter(|(_, output)| output.address.is_some())
+        .map(|(vout, output)| (vout as u32, output))
+        .collect();
+    let is_coinbase = tx.inputs.iter().any(|input| input.is_coinbase);
+
+    let mut votes: Vec<Vec<HeuristicVote>> = vec![Vec::new(); candidates.len()];
+    if candidates.len() >= 2 && !is_coinbase {
+        let outputs: Vec<&ChainOutput> = candidates.iter().map(|(_, output)| *output).collect();
+        let heuristics = [
+            (ChangeHeuristic::AddressReuse, true),
+            (ChangeHeuristic::RoundNumber, false),
+            (ChangeHeuristic::UnnecessaryInput, true),
+            (ChangeHeuristic::FreshAddress, true),
+            (ChangeHeuristic::ScriptType, true),
+            (ChangeHeuristic::OptimalChange, true),
+        ];
+        for (heuristic, marks_change) in heuristics {
+            let Some(flags) = heuristic_flags(heuristic, tx, &outputs, address_stats) else {
+                continue;
+            };
+            // Эвристика, выделяющая все выходы или ни одного, ничего не различает
+            if flags.iter().all(|&flag| flag) || !flags.iter().any(|&flag| flag) {
+                continue;
+            }
+            for (output_votes, flag) in votes.iter_mut().zip(flags) {
+                output_votes.push(HeuristicVote {
+                    heuristic,
+                    change: flag == marks_change,
+                    confidence: heuristic.confidence(),
+                });
+            }
+        }
+    }
+
+    let mut outputs: Vec<OutputChange> = candidates
+        .iter()
+        .zip(votes)
+        .map(|((vout, output), votes)| {
+            let log_odds: f64 = votes
+                .iter()
+                .map(|vote| {
+                    let weight = (vote.confidence / (1.0 - vote.confidence)).ln();
+                    if vote.change { weight } else { -weight }
+                })
+                .sum();
+            OutputChange {
+                vout: *vout,
+                address: output.address.clone(),
+                value: output.value,
+                votes,
+                change_probability: 1.0 / (1.0 + (-log_odds).exp()),
+                is_change: false,
+            }
+        })
+        .collect();
+
+    // Сдача одна: выход с наибольшей вероятностью, если он единственный такой
+    let best = outputs.iter().map(|output| output.change_probability).fold(0.0, f64::max);
+    let mut leaders = outputs.iter_mut().filter(|output| output.change_probability == best);
+    let change_vout = match (leaders.next(), leaders.next()) {
+        (Some(leader), None) if best > CHANGE_PROBABILITY_THRESHOLD => {
+            leader.is_change = true;
+            Some(leader.vout)
+        }
+        _ => None,
+    };
+
+    ChangeDetection { txid: tx.txid.clone(), outputs, change_vout }
+}
+
+/// Загружает транзакцию и статистику адресов ее выходов и определяет сдачу
+///
+/// Статистика уникальных адресов запрашивается параллельно. Если backend
+/// ее не поддерживает, эвристика нового адреса пропускается.
+///
+/// # Arguments
+/// * `client` - Клиент Bitcoin explorer
+/// * `tx_id` - ID транзакции в формате hex string
+///
+/// # Returns
+/// `Result<ChangeDetection, ExplorerError>` - решение по выходам или ошибка explorer'а
+pub async fn extract_change(client: &ExplorerClient, tx_id: &str) -> Result<ChangeDetection, ExplorerError> {
+    let tx = client.get_transaction(tx_id).await?;
+
+    let addresses: HashSet<&str> = tx.outputs.iter().filter_map(|output| output.address.as_deref()).collect();
+    let address_stats = supported(
+        try_join_all(addresses.into_iter().map(|address| async move {
+            let stats = client.get_address_stats(address).await?;
+            Ok::<_, ExplorerError>((address.to_string(), stats))
+        }))
+        .await,
+    )?;
+    let address_stats: Option<HashMap<String, ChainAddressStats>> =
+        address_stats.map(|stats| stats.into_iter().collect());
+    Ok(detect_change(&tx, address_stats.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::models::chain::{ChainInput, ChainStatus};
+    use crate::services::explorer::backends::chain_backend::UnindexedFixtureBackend;
+    use crate::services::explorer::models::esplora::{fixture_transaction, BLOCK_1_COINBASE, SEGWIT_TX};
+
+    fn transaction(inputs: &[(&str, u64)], outputs: &[(&str, u64)]) -> ChainTransaction {
+        let output = |(address, value): &(&str, u64)| ChainOutput {
+            script_pubkey: String::new(),
+            address: Some(address.to_string()),
+            value: *value,
+        };
+        ChainTransaction {
+            txid: "tx".to_string(),
+            inputs: inputs
+                .iter()
+                .map(|input| ChainInput { txid: "parent".to_string(), vout: 0, prevout: Some(output(input)), is_coinbase: false })
+                .collect(),
+            outputs: outputs.iter().map(output).collect(),
+            fee: None,
+            status: ChainStatus::default(),
+        }
+    }
+
+    #[test]
+    fn test_round_payment_and_script_type() {
+        // Платеж 0.05 BTC на P2PKH адрес, сдача на адрес того же типа, что и вход (P2WPKH)
+        let tx = transaction(
+            &[("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", 7_000_000)],
+            &[("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", 5_000_000), ("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", 1_990_000)],
+        );
+        let detection = detect_change(&tx, None);
+
+        assert_eq!(detection.change_vout, Some(1));
+        let change = detection.change_output().expect("Change output");
+        let heuristics: Vec<ChangeHeuristic> = change.votes.iter().map(|vote| vote.heuristic).collect();
+        assert_eq!(heuristics, vec![ChangeHeuristic::RoundNumber, ChangeHeuristic::ScriptType]);
+        assert!(change.votes.iter().all(|vote| vote.change));
+        assert!(change.change_probability > 0.8);
+        assert!(!detection.outputs[0].is_change);
+    }
+
+    #[test]
+    fn test_unnecessary_input_fresh_address_and_ambiguity() {
+        // Без входа 20 000 выход 15 000 оплатить можно, выход 145 000 — нет
+        let tx = transaction(&[("A", 20_000), ("B", 150_000)], &[("C", 145_000), ("D", 15_000)]);
+        let stats = HashMap::from([
+            ("C".to_string(), ChainAddressStats { address: "C".to_string(), funded_txo_count: 5, spent_txo_count: 4, tx_count: 9 }),
+            ("D".to_string(), ChainAddressStats { address: "D".to_string(), funded_txo_count: 1, spent_txo_count: 0, tx_count: 1 }),
+        ]);
+        let detection = detect_change(&tx, Some(&stats));
+        assert_eq!(detection.change_vout, Some(1));
+        assert_eq!(detection.change_value(), 15_000);
+        let heuristics: Vec<ChangeHeuristic> = detection.outputs[1].votes.iter().map(|vote| vote.heuristic).collect();
+        assert_eq!(
+            heuristics,
+            vec![ChangeHeuristic::UnnecessaryInput, ChangeHeuristic::FreshAddress, ChangeHeuristic::OptimalChange]
+        );
+
+        // Ни одна эвристика не различает выходы
+        let tx = transaction(&[("A", 500_000)], &[("C", 200_000), ("D", 200_000)]);
+        let detection = detect_change(&tx, None);
+        assert_eq!(detection.change_vout, None);
+        assert!(detection.outputs.iter().all(|output| output.votes.is_empty() && output.change_probability == 0.5));
+
+        let detection = detect_change(&fixture_transaction(BLOCK_1_COINBASE).into(), None);
+        assert_eq!(detection.change_vout, None);
+    }
+
+    #[tokio::test]
+    async fn test_extract_change_without_address_index() {
+        let client = ExplorerClient::with_backend(UnindexedFixtureBackend);
+        let detection = extract_change(&client, SEGWIT_TX).await.expect("Address stats are optional");
+
+        // Сдача возвращается на адрес входов; эвристика нового адреса пропущена
+        assert_eq!(detection.change_vout, Some(2));
+        assert!(detection
+            .outputs
+            .iter()
+            .flat_map(|output| &output.votes)
+            .all(|vote| vote.heuristic != ChangeHeuristic::FreshAddress));
+        assert!(detection.outputs[2].votes.iter().any(|vote| vote.heuristic == ChangeHeuristic::AddressReuse));
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::{HashMap, HashSet};
+
+use bitcoin::Amount;
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::constants::{WHIRLPOOL_DENOMINATIONS, WHIRLPOOL_MIX_SIZES};
+use crate::services::explorer::models::chain::ChainTransaction;
+
+/// Шаблон CoinJoin транзакции
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinJoinPattern {
+    /// Микс Whirlpool: 5–8 входов и столько же выходов номинала пула
+    Whirlpool,
+    /// JoinMarket: n одинаковых выходов и n или n − 1 выходов сдачи
+    JoinMarket,
+    /// Прочие CoinJoin с одинаковыми выходами (Wasabi, JoinMarket с координатором и т.п.)
+    EqualOutput,
+}
+
+/// Распознанный CoinJoin
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoinJoinMatch {
+    pub pattern: CoinJoinPattern,
+    /// Размер множества анонимности — число одинаковых выходов
+    pub anonymity_set: usize,
+    /// Сумма одинаковых выходов
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub denomination: Amount,
+}
+
+/// Самая частая сумма выходов и число выходов с ней
+///
+/// При равной частоте выбирается большая сумма; выходы с нулевой суммой
+/// (OP_RETURN) не учитываются.
+fn most_common_output(tx: &ChainTransaction) -> Option<(u64, usize)> {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for output in tx.outputs.iter().filter(|output| output.value > 0) {
+        *counts.entry(output.value).or_default() += 1;
+    }
+    counts.into_iter().max_by_key(|&(value, count)| (count, value))
+}
+
+/// Распознает CoinJoin транзакцию
+///
+/// Шаблоны проверяются от самого узкого к общему: микс Whirlpool,
+/// JoinMarket, затем любые `min_equal_outputs` и более одинаковых
+/// выходов. Во всех шаблонах у каждого участника хотя бы один вход, поэтому
+/// входов не меньше, чем одинаковых выходов, а владельцев входов больше одного.
+///
+/// # Arguments
+/// * `tx` - Транзакция в нормализованном виде
+/// * `min_equal_outputs` - Минимальное число одинаковых выходов (участников)
+///
+/// # Returns
+/// `Option<CoinJoinMatch>` - шаблон и размер множества анонимности или `None`
+pub fn detect_coinjoin(tx: &ChainTransaction, min_equal_outputs: usize) -> Option<CoinJoinMatch> {
+    if tx.inputs.iter().any(|input| input.is_coinbase) {
+        return None;
+    }
+    let (denomination, equal_outputs) = most_common_output(tx)?;
+    let input_owners: HashSet<&str> = tx
+        .inputs
+        .iter()
+        .filter_map(|input| input.prevout.as_ref())
+        .map(|prevout| prevout.owner_key())
+        .collect();
+    if equal_outputs < min_equal_outputs || tx.inputs.len() < equal_outputs || input_owners.len() < 2 {
+        return None;
+    }
+    let found = |pattern| {
+        Some(CoinJoinMatch { pattern, anonymity_set: equal_outputs, denomination: Amount::from_sat(denomination) })
+    };
+
+    // Whirlpool: входы — номинал пула (remix) или номинал плюс не более 10% на комиссию (premix)
+    let is_pool_input = |value: u64| value >= denomination && value - denomination <= denomination / 10;
+    if WHIRLPOOL_DENOMINATIONS.contains(&denomination)
+        && WHIRLPOOL_MIX_SIZES.contains(&equal_outputs)
+        && tx.outputs.len() == equal_outputs
+        && tx.inputs.len() == equal_outputs
+        && tx.inputs.iter().all(|input| input.prevout.as_ref().is_some_and(|prevout| is_pool_input(prevout.value)))
+    {
+        return found(CoinJoinPattern::Whirlpool);
+    }
+
+    // JoinMarket: сдача у каждого maker'а, у taker'а ее может не быть
+    let other_outputs = tx.outputs.iter().filter(|output| output.value > 0 && output.value != denomination).count();
+    if other_outputs + 1 == equal_outputs || other_outputs == equal_outputs {
+        return found(CoinJoinPattern::JoinMarket);
+    }
+
+    found(CoinJoinPattern::EqualOutput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::constants::DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS;
+    use crate::services::explorer::models::esplora::{fixture_transaction, BLOCK_1_COINBASE, SEGWIT_TX};
+
+    fn detect(fixture: &str) -> Option<CoinJoinMatch> {
+        detect_coinjoin(&fixture_transaction(fixture).into(), DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS)
+    }
+
+    #[test]
+    fn test_detects_coinjoin_patterns() {
+        let whirlpool = detect("coinjoin_whirlpool").expect("Whirlpool");
+        assert_eq!(whirlpool.pattern, CoinJoinPattern::Whirlpool);
+        assert_eq!(whirlpool.anonymity_set, 5);
+        assert_eq!(whirlpool.denomination, Amount::from_sat(1_000_000));
+
+        let joinmarket = detect("coinjoin_joinmarket").expect("JoinMarket");
+        assert_eq!((joinmarket.pattern, joinmarket.anonymity_set), (CoinJoinPattern::JoinMarket, 4));
+        assert_eq!(joinmarket.denomination, Amount::from_sat(2_345_678));
+
+        let wasabi = detect("coinjoin_wasabi").expect("Equal output");
+        assert_eq!((wasabi.pattern, wasabi.anonymity_set), (CoinJoinPattern::EqualOutput, 12));
+        assert_eq!(detect_coinjoin(&fixture_transaction("coinjoin_joinmarket").into(), 5), None);
+    }
+
+    #[test]
+    fn test_ordinary_transactions_are_not_coinjoins() {
+        assert_eq!(detect(SEGWIT_TX), None);
+        assert_eq!(detect(BLOCK_1_COINBASE), None);
+
+        // Выплата биржи: одинаковые суммы, но один владелец входов
+        let mut payout: ChainTransaction = fixture_transaction("coinjoin_whirlpool").into();
+        let owner = payout.inputs[0].prevout.clone();
+        for input in &mut payout.inputs {
+            input.prevout = owner.clone();
+        }
+        assert_eq!(detect_coinjoin(&payout, DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS), None);
+    }
+}
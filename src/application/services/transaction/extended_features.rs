@@ -0,0 +1,492 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use futures::future::try_join_all;
+use futures::try_join;
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::transaction::change_detection::detect_change;
+use crate::infrastructure::constants::{
+    MAX_TIME_STEP, OLD_INPUT_AGE_SECS, TIME_STEP_BLOCKS, TIME_STEP_ORIGIN_HEIGHT,
+};
+use crate::services::explorer::errors::explorer_errors::{supported, ExplorerError};
+use crate::services::explorer::explorer_client::{get_or_init_client, ExplorerClient};
+use crate::services::explorer::models::chain::{
+    ChainAddressStats, ChainOutspend, ChainStatus, ChainTransaction,
+};
+
+/// Расширенные характеристики транзакции (поля 1 и 9–22 таблицы признаков)
+///
+/// Вместе с [`TransactionFeatures`](super::transaction_info::TransactionFeatures)
+/// образуют полный набор из 22 признаков, который ожидает ML сервис
+/// в поле `extended_features`.
+///
+/// Признаки по статистике адресов и тратам выходов равны `None`, если
+/// backend их не поддерживает (Bitcoin Core без индекса адресов).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExtendedFeatures {
+    /// Временной шаг Elliptic (1–49), вычисленный по высоте блока
+    pub time_step: u8,
+    /// Среднее число входящих транзакций на адресах входов
+    pub avg_input_incoming_txs: Option<f64>,
+    /// Среднее число исходящих транзакций с адресов выходов
+    pub avg_output_outgoing_txs: Option<f64>,
+    /// Число уникальных адресов среди входов
+    pub unique_input_addresses: usize,
+    /// Число уникальных адресов среди выходов
+    pub unique_output_addresses: usize,
+    /// Число coinbase входов
+    pub num_coinbase_inputs: u8,
+    /// Доля входов старше `OLD_INPUT_AGE_SECS`
+    pub old_input_fraction: f64,
+    /// Доля сдачи ([`detect_change`]) в сумме выходов
+    pub change_output_ratio: f64,
+    /// Энтропия распределения сумм по адресам входов (в битах)
+    pub inputs_address_entropy: f64,
+    /// Энтропия распределения сумм по адресам выходов (в битах)
+    pub outputs_address_entropy: f64,
+    /// Число уже потраченных выходов
+    pub spent_outputs_count: Option<usize>,
+    /// Число непотраченных выходов
+    pub unspent_outputs_count: Option<usize>,
+    /// Среднее время жизни потраченных входов в секундах
+    pub time_diff_prev_output: f64,
+    /// Среднее число трат с адресов входов
+    pub avg_outgoing_txs_inputs: Option<f64>,
+    /// Среднее число поступлений на адреса выходов
+    pub avg_incoming_txs_outputs: Option<f64>,
+}
+
+/// Данные explorer'а, необходимые для вычисления расширенных признаков
+pub struct ExtendedFeatureContext<'a> {
+    /// Анализируемая транзакция
+    pub tx: &'a ChainTransaction,
+    /// Статусы родительских транзакций по их ID
+    pub parent_statuses: &'a HashMap<String, ChainStatus>,
+    /// Статусы трат выходов транзакции (`None`, если backend их не отдает)
+    pub outspends: Option<&'a [ChainOutspend]>,
+    /// Статистика адресов входов и выходов (`None`, если backend ее не отдает)
+    pub address_stats: Option<&'a HashMap<String, ChainAddressStats>>,
+    /// Высота последнего блока (для неподтвержденных транзакций)
+    pub tip_height: u64,
+    /// Текущее время (unix timestamp) для неподтвержденных транзакций
+    pub now: u64,
+}
+
+/// Переводит высоту блока во временной шаг Elliptic (1–49)
+pub fn time_step_for_height(height: u64) -> u8 {
+    let step = height.saturating_sub(TIME_STEP_ORIGIN_HEIGHT) / TIME_STEP_BLOCKS + 1;
+    step.min(MAX_TIME_STEP as u64) as u8
+}
+
+/// Энтропия Шеннона (в битах) распределения, заданного весами
+fn shannon_entropy(weights: &[f64]) -> f64 {
+    let total: f64 = weights.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    weights
+        .iter()
+        .filter(|w| **w > 0.0)
+        .map(|w| {
+            let p = w / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Суммирует значения по ключу владельца, сохраняя порядок появления
+fn group_by_owner<'a>(items: impl Iterator<Item = (&'a str, u64)>) -> Vec<(&'a str, u64)> {
+    let mut grouped: Vec<(&str, u64)> = Vec::new();
+    for (owner, value) in items {
+        match grouped.iter_mut().find(|(key, _)| *key == owner) {
+            Some((_, sum)) => *sum += value,
+            None => grouped.push((owner, value)),
+        }
+    }
+    grouped
+}
+
+/// Среднее значение счетчика по адресам (адреса без статистики пропускаются)
+///
+/// `None`, если статистика адресов недоступна.
+fn average_stat<'a>(
+    addresses: impl Iterator<Item = &'a str>,
+    stats: Option<&HashMap<String, ChainAddressStats>>,
+    counter: impl Fn(&ChainAddressStats) -> u64,
+) -> Option<f64> {
+    let stats = stats?;
+    let values: Vec<f64> = addresses
+        .filter_map(|address| stats.get(address))
+        .map(|s| counter(s) as f64)
+        .collect();
+    if values.is_empty() {
+        Some(0.0)
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Вычисляет расширенные признаки по уже загруженным данным explorer'а
+///
+/// # Arguments
+/// * `ctx` - Транзакция и связанные с ней данные
+///
+/// # Returns
+/// `ExtendedFeatures` - расширенные характеристики транзакции
+pub fn compute_extended_features(ctx: &ExtendedFeatureContext) -> ExtendedFeatures {
+    let tx = ctx.tx;
+
+    let height = tx.status.block_height.unwrap_or(ctx.tip_height);
+    let reference_time = tx.status.block_time.unwrap_or(ctx.now);
+
+    let input_addresses: Vec<&str> = tx
+        .inputs
+        .iter()
+        .filter_map(|vin| vin.prevout.as_ref())
+        .filter_map(|prevout| prevout.address.as_deref())
+        .collect();
+    let output_addresses: Vec<&str> = tx
+        .outputs
+        .iter()
+        .filter_map(|vout| vout.address.as_deref())
+        .collect();
+
+    let input_groups = group_by_owner(
+        tx.inputs
+            .iter()
+            .filter_map(|vin| vin.prevout.as_ref())
+            .map(|prevout| (prevout.owner_key(), prevout.value)),
+    );
+    let output_groups = group_by_owner(tx.outputs.iter().map(|vout| (vout.owner_key(), vout.value)));
+
+    let unique_input_addresses = input_addresses.iter().collect::<HashSet<_>>().len();
+    let unique_output_addresses = output_addresses.iter().collect::<HashSet<_>>().len();
+
+    let num_coinbase_inputs = tx.inputs.iter().filter(|vin| vin.is_coinbase).count().min(u8::MAX as usize) as u8;
+
+    // Возраст каждого некоинбейс входа относительно времени транзакции
+    let input_ages: Vec<u64> = tx
+        .inputs
+        .iter()
+        .filter(|vin| !vin.is_coinbase)
+        .map(|vin| {
+            let parent_time = ctx
+                .parent_statuses
+                .get(&vin.txid)
+                .and_then(|status| status.block_time)
+                .unwrap_or(reference_time);
+            reference_time.saturating_sub(parent_time)
+        })
+        .collect();
+
+    let old_input_fraction = if input_ages.is_empty() {
+        0.0
+    } else {
+        input_ages.iter().filter(|age| **age > OLD_INPUT_AGE_SECS).count() as f64 / input_ages.len() as f64
+    };
+
+    let time_diff_prev_output = if input_ages.is_empty() {
+        0.0
+    } else {
+        input_ages.iter().sum::<u64>() as f64 / input_ages.len() as f64
+    };
+
+    let output_value_sum: u64 = tx.outputs.iter().map(|vout| vout.value).sum();
+    let change_value = detect_change(tx, ctx.address_stats).change_value();
+    let change_output_ratio = if output_value_sum > 0 {
+        change_value as f64 / output_value_sum as f64
+    } else {
+        0.0
+    };
+
+    let inputs_address_entropy =
+        shannon_entropy(&input_groups.iter().map(|(_, v)| *v as f64).collect::<Vec<_>>());
+    let outputs_address_entropy =
+        shannon_entropy(&output_groups.iter().map(|(_, v)| *v as f64).collect::<Vec<_>>());
+
+    let spent_outputs_count = ctx
+        .outspends
+        .map(|outspends| outspends.iter().filter(|o| o.spent).count().min(tx.outputs.len()));
+    let unspent_outputs_count = spent_outputs_count.map(|spent| tx.outputs.len() - spent);
+
+    ExtendedFeatures {
+        time_step: time_step_for_height(height),
+        avg_input_incoming_txs: average_stat(input_addresses.iter().copied(), ctx.address_stats, |s| s.funded_txo_count),
+        avg_output_outgoing_txs: average_stat(output_addresses.iter().copied(), ctx.address_stats, |s| s.spent_txo_count),
+        unique_input_addresses,
+        unique_output_addresses,
+        num_coinbase_inputs,
+        old_input_fraction,
+        change_output_ratio,
+        inputs_address_entropy,
+        outputs_address_entropy,
+        spent_outputs_count,
+        unspent_outputs_count,
+        time_diff_prev_output,
+        avg_outgoing_txs_inputs: average_stat(input_addresses.iter().copied(), ctx.address_stats, |s| s.spent_txo_count),
+        avg_incoming_txs_outputs: average_stat(output_addresses.iter().copied(), ctx.address_stats, |s| s.funded_txo_count),
+    }
+}
+
+/// Загружает через explorer все данные, нужные для расширенных признаков, и вычисляет их
+///
+/// Родительские транзакции входов, статусы трат выходов и статистика
+/// каждого уникального адреса входов и выходов запрашиваются параллельно;
+/// частоту запросов ограничивает политика клиента, если в ней задан лимит.
+/// Если backend не поддерживает статистику адресов или траты выходов,
+/// зависящие от них признаки остаются `None`.
+///
+/// # Arguments
+/// * `client` - Клиент Bitcoin explorer
+/// * `tx_id` - ID транзакции в формате hex string
+///
+/// # Returns
+/// `Result<ExtendedFeatures, ExplorerError>` - расширенные признаки или ошибка explorer'а
+pub async fn extract_extended_features(
+    client: &ExplorerClient,
+    tx_id: &str,
+) -> Result<ExtendedFeatures, ExplorerError> {
+    let tx = client.get_transaction(tx_id).await?;
+
+    let parent_txids: HashSet<&str> = tx
+        .inputs
+        .iter()
+        .filter(|vin| !vin.is_coinbase)
+        .map(|vin| vin.txid.as_str())
+        .collect();
+    let addresses: HashSet<&str> = tx
+        .inputs
+        .iter()
+        .filter_map(|vin| vin.prevout.as_ref())
+        .chain(tx.outputs.iter())
+        .filter_map(|vout| vout.address.as_deref())
+        .collect();
+
+    let parent_statuses = try_join_all(parent_txids.into_iter().map(|txid| async move {
+        let parent = client.get_transaction(txid).await?;
+        Ok::<_, ExplorerError>((txid.to_string(), parent.status))
+    }));
+    let address_stats = async {
+        supported(
+            try_join_all(addresses.into_iter().map(|address| async move {
+                let stats = client.get_address_stats(address).await?;
+                Ok::<_, ExplorerError>((address.to_string(), stats))
+            }))
+            .await,
+        )
+    };
+    let outspends = async { supported(client.get_outspends(tx_id).await) };
+    let tip_height = async {
+        if tx.status.confirmed {
+            Ok(0)
+        } else {
+            client.get_tip_height().await
+        }
+    };
+    let (parent_statuses, outspends, address_stats, tip_height) =
+        try_join!(parent_statuses, outspends, address_stats, tip_height)?;
+    let parent_statuses: HashMap<String, ChainStatus> = parent_statuses.into_iter().collect();
+    let address_stats: Option<HashMap<String, ChainAddressStats>> =
+        address_stats.map(|stats| stats.into_iter().collect());
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    Ok(compute_extended_features(&ExtendedFeatureContext {
+        tx: &tx,
+        parent_statuses: &parent_statuses,
+        outspends: outspends.as_deref(),
+        address_stats: address_stats.as_ref(),
+        tip_height,
+        now,
+    }))
+}
+
+/// Извлекает расширенные характеристики транзакции через глобальный клиент explorer
+///
+/// # Arguments
+/// * `tx_id` - ID транзакции в формате hex string (64 символа)
+///
+/// # Returns
+/// `Result<ExtendedFeatures, Box<dyn std::error::Error>>` - расширенные характеристики транзакции
+///
+/// # Example
+/// ```no_run
+/// use diploma_software::application::services::transaction::extended_features::get_extended_features;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let tx_id = "d6176384de4c0b98702eccb97f3ad6670bc8410d9da715fe5b49462d3e603993";
+/// let features = get_extended_features(tx_id).await?;
+/// println!("Time step: {}, entropy: {}", features.time_step, features.outputs_address_entropy);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_extended_features(tx_id: &str) -> Result<ExtendedFeatures, Box<dyn std::error::Error>> {
+    let client = get_or_init_client().await
+        .map_err(|e| format!("Failed to initialize explorer client: {}", e))?;
+    Ok(extract_extended_features(client, tx_id).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::backends::chain_backend::UnindexedFixtureBackend;
+    use crate::services::explorer::models::chain::{ChainInput, ChainOutput};
+    use crate::services::explorer::models::esplora::SEGWIT_TX;
+
+    fn vout(address: &str, value: u64) -> ChainOutput {
+        ChainOutput {
+            script_pubkey: String::new(),
+            address: Some(address.to_string()),
+            value,
+        }
+    }
+
+    fn vin(parent: &str, address: &str, value: u64) -> ChainInput {
+        ChainInput {
+            txid: parent.to_string(),
+            vout: 0,
+            prevout: Some(vout(address, value)),
+            is_coinbase: false,
+        }
+    }
+
+    fn confirmed(height: u64, time: u64) -> ChainStatus {
+        ChainStatus {
+            confirmed: true,
+            block_height: Some(height),
+            block_time: Some(time),
+        }
+    }
+
+    fn stats(address: &str, funded: u64, spent: u64) -> ChainAddressStats {
+        ChainAddressStats {
+            address: address.to_string(),
+            funded_txo_count: funded,
+            spent_txo_count: spent,
+            tx_count: funded + spent,
+        }
+    }
+
+    fn outspend(spent: bool) -> ChainOutspend {
+        ChainOutspend { spent, txid: None, vin: None, status: None }
+    }
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    #[test]
+    fn test_time_step_for_height() {
+        // Высота из примера датасета Elliptic++ относится к шагу 47
+        assert_eq!(time_step_for_height(483_937), 47);
+        assert_eq!(time_step_for_height(0), 1);
+        assert_eq!(time_step_for_height(900_000), MAX_TIME_STEP);
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy(&[]), 0.0);
+        assert_eq!(shannon_entropy(&[5.0]), 0.0);
+        assert!((shannon_entropy(&[1.0, 1.0]) - 1.0).abs() < 1e-12);
+        assert!((shannon_entropy(&[1.0, 1.0, 1.0, 1.0]) - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_compute_extended_features() {
+        let now = 1_500_000_000;
+        let tx = ChainTransaction {
+            txid: "tx".to_string(),
+            inputs: vec![
+                vin("p1", "A", 60_000),
+                vin("p1", "A", 40_000),
+                vin("p2", "B", 100_000),
+            ],
+            outputs: vec![vout("C", 150_000), vout("A", 40_000)],
+            fee: Some(10_000),
+            status: confirmed(483_937, now),
+        };
+        let parent_statuses = HashMap::from([
+            ("p1".to_string(), confirmed(483_000, now - 40 * DAY)),
+            ("p2".to_string(), confirmed(483_900, now - DAY)),
+        ]);
+        let address_stats = HashMap::from([
+            ("A".to_string(), stats("A", 4, 2)),
+            ("B".to_string(), stats("B", 1, 1)),
+            ("C".to_string(), stats("C", 3, 0)),
+        ]);
+        let outspends = vec![outspend(true), outspend(false)];
+
+        let features = compute_extended_features(&ExtendedFeatureContext {
+            tx: &tx,
+            parent_statuses: &parent_statuses,
+            outspends: Some(&outspends),
+            address_stats: Some(&address_stats),
+            tip_height: 0,
+            now,
+        });
+
+        assert_eq!(features.time_step, 47);
+        assert_eq!(features.unique_input_addresses, 2);
+        assert_eq!(features.unique_output_addresses, 2);
+        assert_eq!(features.num_coinbase_inputs, 0);
+        assert!((features.old_input_fraction - 2.0 / 3.0).abs() < 1e-12);
+        assert!((features.time_diff_prev_output - (81.0 * DAY as f64) / 3.0).abs() < 1e-6);
+        assert!((features.change_output_ratio - 40_000.0 / 190_000.0).abs() < 1e-12);
+        // A и B внесли по 100 000 сатоши
+        assert!((features.inputs_address_entropy - 1.0).abs() < 1e-12);
+        assert_eq!(features.spent_outputs_count, Some(1));
+        assert_eq!(features.unspent_outputs_count, Some(1));
+        assert_eq!(features.avg_input_incoming_txs, Some(3.0));
+        assert!((features.avg_outgoing_txs_inputs.unwrap_or_default() - 5.0 / 3.0).abs() < 1e-12);
+        assert_eq!(features.avg_output_outgoing_txs, Some(1.0));
+        assert_eq!(features.avg_incoming_txs_outputs, Some(3.5));
+    }
+
+    #[test]
+    fn test_coinbase_transaction_features() {
+        let tx = ChainTransaction {
+            txid: "cb".to_string(),
+            inputs: vec![ChainInput {
+                txid: "0".repeat(64),
+                vout: u32::MAX,
+                prevout: None,
+                is_coinbase: true,
+            }],
+            outputs: vec![vout("M", 625_000_000)],
+            fee: Some(0),
+            status: ChainStatus::default(),
+        };
+
+        let features = compute_extended_features(&ExtendedFeatureContext {
+            tx: &tx,
+            parent_statuses: &HashMap::new(),
+            outspends: Some(&[]),
+            address_stats: Some(&HashMap::new()),
+            tip_height: 391_000,
+            now: 0,
+        });
+
+        assert_eq!(features.num_coinbase_inputs, 1);
+        assert_eq!(features.time_step, 1);
+        assert_eq!(features.unique_input_addresses, 0);
+        assert_eq!(features.old_input_fraction, 0.0);
+        assert_eq!(features.inputs_address_entropy, 0.0);
+        assert_eq!(features.unspent_outputs_count, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_extract_without_address_index() {
+        let client = ExplorerClient::with_backend(UnindexedFixtureBackend);
+        let features = extract_extended_features(&client, SEGWIT_TX).await.expect("Unsupported data is optional");
+
+        assert_eq!(features.unique_input_addresses, 1);
+        assert_eq!(features.time_diff_prev_output, DAY as f64);
+        assert_eq!((features.spent_outputs_count, features.unspent_outputs_count), (None, None));
+        assert_eq!(features.avg_input_incoming_txs, None);
+        assert_eq!(features.avg_output_outgoing_txs, None);
+        assert_eq!(features.avg_outgoing_txs_inputs, None);
+        assert_eq!(features.avg_incoming_txs_outputs, None);
+    }
+}
@@ -1 +1,4 @@
-pub mod transaction_info;
\ No newline at end of file
+pub mod transaction_info;
+pub mod extended_features;
+pub mod change_detection;
+pub mod coinjoin_detection;
@@ -1,49 +1,117 @@
+use bitcoin::Amount;
 use crate::services::explorer::explorer_client::get_or_init_client;
 use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::models::chain::ChainTransaction;
 use serde::{Deserialize, Serialize};
 
 /// Характеристики транзакции Bitcoin
+///
+/// Суммы хранятся точно, в сатоши; перевод в BTC (`f64`) выполняется только
+/// при передаче признаков модели, см. [`MLTransactionFeatures`](crate::services::ml::models::MLTransactionFeatures).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct TransactionFeatures {
     /// Количество входов транзакции
     pub n_inputs: usize,
     /// Количество выходов транзакции
     pub n_outputs: usize,
-    /// Сумма BTC на входах
-    pub input_value_sum: f64,
-    /// Сумма BTC на выходах
-    pub output_value_sum: f64,
-    /// Комиссия транзакции (input_value_sum - output_value_sum)
-    pub transaction_fee: f64,
-    /// Средний размер входящего UTXO
+    /// Сумма на входах
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub input_value_sum: Amount,
+    /// Сумма на выходах
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub output_value_sum: Amount,
+    /// Комиссия транзакции (input_value_sum - output_value_sum, у coinbase - ноль)
+    #[serde(with = "bitcoin::amount::serde::as_sat")]
+    pub transaction_fee: Amount,
+    /// Средний размер входящего UTXO в BTC (0, если входов нет)
     pub avg_input_value: f64,
-    /// Средний размер создаваемого UTXO
+    /// Средний размер создаваемого UTXO в BTC (0, если выходов нет)
     pub avg_output_value: f64,
 }
 
-#[derive(Debug, Deserialize)]
-struct Vin {
-    #[serde(default)]
-    value: Option<f64>,
-    #[serde(default)]
-    #[allow(dead_code)]
-    value_sat: Option<u64>,
-}
+impl TransactionFeatures {
+    /// Создает характеристики и вычисляет средние размеры входов и выходов
+    ///
+    /// # Arguments
+    /// * `n_inputs` - Количество входов
+    /// * `n_outputs` - Количество выходов
+    /// * `input_value_sum` - Сумма на входах
+    /// * `output_value_sum` - Сумма на выходах
+    /// * `transaction_fee` - Комиссия транзакции
+    ///
+    /// # Returns
+    /// `Self` - характеристики с заполненными `avg_input_value` и `avg_output_value`
+    pub fn new(
+        n_inputs: usize,
+        n_outputs: usize,
+        input_value_sum: Amount,
+        output_value_sum: Amount,
+        transaction_fee: Amount,
+    ) -> Self {
+        TransactionFeatures {
+            n_inputs,
+            n_outputs,
+            input_value_sum,
+            output_value_sum,
+            transaction_fee,
+            avg_input_value: average_btc(input_value_sum, n_inputs),
+            avg_output_value: average_btc(output_value_sum, n_outputs),
+        }
+    }
 
-#[derive(Debug, Deserialize)]
-struct Vout {
-    value: f64,
-    #[serde(default)]
-    #[allow(dead_code)]
-    value_sat: Option<u64>,
+    /// Вычисляет характеристики по нормализованной транзакции
+    ///
+    /// # Arguments
+    /// * `tx` - Транзакция, полученная от любого backend'а explorer'а
+    ///
+    /// # Returns
+    /// `Result<Self, ExplorerError>` - характеристики, `MissingInputValue`,
+    /// если у некоинбейс входа неизвестен расходуемый выход, или `InvalidResponse`,
+    /// если выходы превышают входы либо комиссия не совпадает с указанной explorer'ом
+    pub fn from_transaction(tx: &ChainTransaction) -> Result<Self, ExplorerError> {
+        if tx.inputs.iter().any(|input| !input.is_coinbase && input.prevout.is_none()) {
+            return Err(ExplorerError::MissingInputValue);
+        }
+
+        let input_value_sum = tx.input_value();
+        let output_value_sum = tx.output_value();
+        let is_coinbase = tx.inputs.iter().any(|input| input.is_coinbase);
+
+        let transaction_fee = if is_coinbase {
+            Amount::ZERO
+        } else {
+            tx.computed_fee().ok_or_else(|| {
+                ExplorerError::InvalidResponse(format!(
+                    "transaction {} spends {} but creates {}",
+                    tx.txid, input_value_sum, output_value_sum
+                ))
+            })?
+        };
+        if let Some(reported) = tx.fee.map(Amount::from_sat) {
+            if reported != transaction_fee {
+                return Err(ExplorerError::InvalidResponse(format!(
+                    "transaction {} fee is {} but explorer reported {}",
+                    tx.txid, transaction_fee, reported
+                )));
+            }
+        }
+
+        Ok(TransactionFeatures::new(
+            tx.inputs.len(),
+            tx.outputs.len(),
+            input_value_sum,
+            output_value_sum,
+            transaction_fee,
+        ))
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct TransactionData {
-    #[allow(dead_code)]
-    txid: String,
-    vin: Vec<Vin>,
-    vout: Vec<Vout>,
+fn average_btc(sum: Amount, count: usize) -> f64 {
+    if count > 0 {
+        sum.to_btc() / count as f64
+    } else {
+        0.0
+    }
 }
 
 // | 1   | **time\_step**                                 | Порядковый номер «временного шага» транзакции (1–49), соответствующий ее времени (интервал \~2 недели). Вычисляется по метке времени блока (RPC `getblockheader`) или моменту включения в блок.                                                                                                                                                                                                                                         |
@@ -91,7 +159,6 @@ struct TransactionData {
 /// Возвращает ошибку если:
 /// - Клиент explorer не инициализирован
 /// - Не удалось получить данные транзакции
-/// - Не удалось десериализовать JSON
 /// - Отсутствуют значения входов
 ///
 /// # Example
@@ -108,60 +175,10 @@ struct TransactionData {
 pub async fn get_transaction_info(tx_id: &str) -> Result<TransactionFeatures, Box<dyn std::error::Error>> {
     let client = get_or_init_client().await
         .map_err(|e| format!("Failed to initialize explorer client: {}", e))?;
-    let tx_json = client.get_transaction(tx_id).await?;
-
-    println!("Transaction JSON: {:?}", tx_json);
-
-    // Десериализуем JSON в структуру
-    let tx: TransactionData = serde_json::from_value(tx_json)?;
-
-    let n_inputs = tx.vin.len();
-    let n_outputs = tx.vout.len();
-
-    println!("n_inputs: {}, n_outputs: {}", n_inputs, n_outputs);
-
-    // Вычисляем сумму входов
-    // В Blockbook API значение входа может быть в value или value_sat
-    let input_value_sum: f64 = tx
-        .vin
-        .iter()
-        .map(|vin| {
-            vin.value
-                .or_else(|| vin.value_sat.map(|sat| sat as f64 / 100_000_000.0))
-                .ok_or(ExplorerError::MissingInputValue)
-        })
-        .collect::<Result<Vec<_>, _>>()?
-        .iter()
-        .sum();
-
-    // Вычисляем сумму выходов
-    let output_value_sum: f64 = tx.vout.iter().map(|v| v.value).sum();
-
-    let transaction_fee = input_value_sum - output_value_sum;
-
-    let avg_input_value = if n_inputs > 0 {
-        input_value_sum / n_inputs as f64
-    } else {
-        0.0
-    };
-
-    let avg_output_value = if n_outputs > 0 {
-        output_value_sum / n_outputs as f64
-    } else {
-        0.0
-    };
-
-    let features = TransactionFeatures {
-        n_inputs,
-        n_outputs,
-        input_value_sum,
-        output_value_sum,
-        transaction_fee,
-        avg_input_value,
-        avg_output_value,
-    };
+    let tx = client.get_transaction(tx_id).await?;
 
-    println!("Transaction Features: {:?}", features);
+    let features = TransactionFeatures::from_transaction(&tx)?;
+    log::debug!("Transaction features of {}: {:?}", tx_id, features);
 
     Ok(features)
 }
@@ -169,53 +186,33 @@ pub async fn get_transaction_info(tx_id: &str) -> Result<TransactionFeatures, Bo
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::explorer::models::chain::{ChainInput, ChainOutput, ChainStatus};
+    use crate::services::explorer::models::esplora::{fixture_transaction, BLOCK_1_COINBASE, FIRST_P2P_TX, SEGWIT_TX};
+
+    fn features() -> TransactionFeatures {
+        TransactionFeatures::new(
+            2,
+            3,
+            Amount::from_sat(150_000_000),
+            Amount::from_sat(149_000_000),
+            Amount::from_sat(1_000_000),
+        )
+    }
 
-    #[test]
-    fn test_transaction_features_calculation() {
-        // Тест вычисления характеристик транзакции
-        let features = TransactionFeatures {
-            n_inputs: 2,
-            n_outputs: 3,
-            input_value_sum: 1.5,
-            output_value_sum: 1.49,
-            transaction_fee: 0.01,
-            avg_input_value: 0.75,
-            avg_output_value: 0.49666666666666664,
-        };
-
-        assert_eq!(features.n_inputs, 2);
-        assert_eq!(features.n_outputs, 3);
-        assert_eq!(features.transaction_fee, 0.01);
-        assert!((features.avg_input_value - 0.75).abs() < 0.0001);
+    fn fixture(name: &str) -> ChainTransaction {
+        fixture_transaction(name).into()
     }
 
     #[test]
-    fn test_transaction_fee_calculation() {
-        let features = TransactionFeatures {
-            n_inputs: 1,
-            n_outputs: 2,
-            input_value_sum: 1.0,
-            output_value_sum: 0.99,
-            transaction_fee: 0.01,
-            avg_input_value: 1.0,
-            avg_output_value: 0.495,
-        };
-
-        let calculated_fee = features.input_value_sum - features.output_value_sum;
-        assert!((calculated_fee - features.transaction_fee).abs() < 0.0001);
+    fn test_average_values() {
+        let features = features();
+        assert!((features.avg_input_value - 0.75).abs() < 1e-12);
+        assert!((features.avg_output_value - 0.49666666666666665).abs() < 1e-12);
     }
 
     #[test]
     fn test_avg_calculations_with_zero_inputs() {
-        let features = TransactionFeatures {
-            n_inputs: 0,
-            n_outputs: 1,
-            input_value_sum: 0.0,
-            output_value_sum: 1.0,
-            transaction_fee: 0.0,
-            avg_input_value: 0.0,
-            avg_output_value: 1.0,
-        };
+        let features = TransactionFeatures::new(0, 1, Amount::ZERO, Amount::ONE_BTC, Amount::ZERO);
 
         assert_eq!(features.avg_input_value, 0.0);
         assert_eq!(features.avg_output_value, 1.0);
@@ -223,36 +220,93 @@ mod tests {
 
     #[test]
     fn test_transaction_features_serialization() {
-        let features = TransactionFeatures {
-            n_inputs: 2,
-            n_outputs: 3,
-            input_value_sum: 1.5,
-            output_value_sum: 1.49,
-            transaction_fee: 0.01,
-            avg_input_value: 0.75,
-            avg_output_value: 0.4967,
-        };
+        let features = features();
 
-        let json = serde_json::to_string(&features).expect("Should serialize");
-        let deserialized: TransactionFeatures = serde_json::from_str(&json)
-            .expect("Should deserialize");
+        let json = serde_json::to_value(&features).expect("Should serialize");
+        assert_eq!(json["transaction_fee"], 1_000_000);
+        assert_eq!(json["avg_input_value"], 0.75);
+        assert!(json["avg_output_value"].is_f64());
+        let deserialized: TransactionFeatures = serde_json::from_value(json).expect("Should deserialize");
 
         assert_eq!(features, deserialized);
     }
 
+    #[test]
+    fn test_transaction_fee_calculation() {
+        let features = TransactionFeatures::new(
+            1,
+            2,
+            Amount::ONE_BTC,
+            Amount::from_sat(99_000_000),
+            Amount::from_sat(1_000_000),
+        );
+
+        let calculated_fee = features.input_value_sum.checked_sub(features.output_value_sum);
+        assert_eq!(calculated_fee, Some(features.transaction_fee));
+    }
+
     #[test]
     fn test_transaction_features_clone() {
-        let features = TransactionFeatures {
-            n_inputs: 1,
-            n_outputs: 1,
-            input_value_sum: 1.0,
-            output_value_sum: 0.99,
-            transaction_fee: 0.01,
-            avg_input_value: 1.0,
-            avg_output_value: 0.99,
-        };
+        let features = features();
 
         let cloned = features.clone();
         assert_eq!(features, cloned);
     }
+
+    #[test]
+    fn test_features_from_transaction() {
+        let output = |value| ChainOutput { script_pubkey: String::new(), address: None, value };
+        let mut tx = ChainTransaction {
+            txid: "tx".to_string(),
+            inputs: vec![ChainInput {
+                txid: "parent".to_string(),
+                vout: 0,
+                prevout: Some(output(150_000_000)),
+                is_coinbase: false,
+            }],
+            outputs: vec![output(100_000_000), output(49_000_000)],
+            fee: None,
+            status: ChainStatus::default(),
+        };
+
+        let features = TransactionFeatures::from_transaction(&tx).expect("Inputs are known");
+        assert_eq!(features.n_inputs, 1);
+        assert_eq!(features.n_outputs, 2);
+        assert_eq!(features.input_value_sum, Amount::from_sat(150_000_000));
+        assert_eq!(features.transaction_fee, Amount::from_sat(1_000_000));
+
+        tx.fee = Some(999_999);
+        assert!(matches!(
+            TransactionFeatures::from_transaction(&tx),
+            Err(ExplorerError::InvalidResponse(_))
+        ));
+
+        tx.inputs[0].prevout = None;
+        assert!(matches!(
+            TransactionFeatures::from_transaction(&tx),
+            Err(ExplorerError::MissingInputValue)
+        ));
+    }
+
+    #[test]
+    fn test_fee_is_exact_on_fixtures() {
+        for name in [FIRST_P2P_TX, SEGWIT_TX] {
+            let tx = fixture(name);
+            let features = TransactionFeatures::from_transaction(&tx).expect("Fixture is complete");
+
+            let inputs: u64 = tx.inputs.iter().map(|input| input.prevout.as_ref().unwrap().value).sum();
+            let outputs: u64 = tx.outputs.iter().map(|output| output.value).sum();
+            assert_eq!(features.transaction_fee.to_sat(), inputs - outputs, "{}", name);
+            assert_eq!(Some(features.transaction_fee.to_sat()), tx.fee, "{}", name);
+        }
+
+        // При вычитании в f64 BTC комиссия получается 26516.99… сатоши
+        let features = TransactionFeatures::from_transaction(&fixture(SEGWIT_TX)).expect("Fixture is complete");
+        assert_eq!(features.input_value_sum, Amount::from_sat(96_935_907));
+        assert_eq!(features.transaction_fee, Amount::from_sat(26_517));
+
+        let coinbase = TransactionFeatures::from_transaction(&fixture(BLOCK_1_COINBASE)).expect("Coinbase has no prevouts");
+        assert_eq!(coinbase.transaction_fee, Amount::ZERO);
+        assert_eq!(coinbase.output_value_sum, Amount::from_sat(5_000_000_000));
+    }
 }
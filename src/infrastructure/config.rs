@@ -1,4 +1,173 @@
 use std::env;
+use std::str::FromStr;
+
+use crate::infrastructure::errors::config_errors::ConfigError;
+use crate::infrastructure::constants::{
+    BITCOIN_CORE_RPC_URL, BITCOIN_EXPLORER_URL, DEFAULT_EXPLORER_MAX_RETRIES, DEFAULT_EXPLORER_NAME,
+    DEFAULT_EXPLORER_RETRY_BASE_DELAY_MS, DEFAULT_EXPLORER_RETRY_MAX_DELAY_MS,
+    DEFAULT_EXPLORER_TIMEOUT_SECS, DEFAULT_EXPLORER_USER_AGENT, DEFAULT_EXPLORER_CACHE_DIR,
+    DEFAULT_EXPLORER_CACHE_MEMPOOL_TTL_SECS, DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS,
+};
+
+/// Тип источника данных блокчейна
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplorerBackendKind {
+    /// Esplora-совместимый REST API (mempool.space, blockstream.info)
+    Esplora,
+    /// Blockbook (Trezor)
+    Blockbook,
+    /// Узел Bitcoin Core через JSON-RPC
+    BitcoinCore,
+}
+
+impl FromStr for ExplorerBackendKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "esplora" | "mempool" => Ok(ExplorerBackendKind::Esplora),
+            "blockbook" => Ok(ExplorerBackendKind::Blockbook),
+            "bitcoin-core" | "bitcoin_core" | "bitcoind" | "rpc" => Ok(ExplorerBackendKind::BitcoinCore),
+            other => Err(format!("unknown explorer backend: {}", other)),
+        }
+    }
+}
+
+/// Настройки одного клиента explorer'а
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplorerConfig {
+    /// Имя клиента, по которому он выбирается в приложении
+    pub name: String,
+    /// Тип источника данных
+    pub backend: ExplorerBackendKind,
+    /// Базовый URL API (для Bitcoin Core — URL JSON-RPC); пустой, если
+    /// для Blockbook URL не задан
+    pub base_url: String,
+    /// Таймаут одного запроса в секундах
+    pub timeout_secs: u64,
+    /// Дополнительный заголовок авторизации (имя, значение)
+    pub auth_header: Option<(String, String)>,
+    /// URL HTTP(S) прокси
+    pub proxy: Option<String>,
+    /// Значение заголовка User-Agent
+    pub user_agent: String,
+    /// Логин JSON-RPC (Bitcoin Core)
+    pub rpc_user: Option<String>,
+    /// Пароль JSON-RPC (Bitcoin Core)
+    pub rpc_password: Option<String>,
+    /// Путь к cookie файлу JSON-RPC (Bitcoin Core)
+    pub rpc_cookie_file: Option<String>,
+    /// Число повторов при 429, 5xx и сетевых ошибках
+    pub max_retries: u32,
+    /// Начальная задержка экспоненциального повтора в миллисекундах
+    pub retry_base_delay_ms: u64,
+    /// Максимальная задержка между повторами в миллисекундах
+    pub retry_max_delay_ms: u64,
+    /// Ограничение частоты запросов в секунду (`None` — без ограничения)
+    pub rate_limit_per_sec: Option<f64>,
+    /// Число запросов подряд без ожидания при включенном ограничении
+    pub rate_limit_burst: u32,
+}
+
+impl ExplorerConfig {
+    /// Создает настройки Esplora клиента со значениями по умолчанию
+    ///
+    /// # Arguments
+    /// * `name` - Имя клиента
+    /// * `base_url` - Базовый URL API
+    pub fn new(name: impl Into<String>, base_url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            backend: ExplorerBackendKind::Esplora,
+            base_url: base_url.into(),
+            timeout_secs: DEFAULT_EXPLORER_TIMEOUT_SECS,
+            auth_header: None,
+            proxy: None,
+            user_agent: DEFAULT_EXPLORER_USER_AGENT.to_string(),
+            rpc_user: None,
+            rpc_password: None,
+            rpc_cookie_file: None,
+            max_retries: DEFAULT_EXPLORER_MAX_RETRIES,
+            retry_base_delay_ms: DEFAULT_EXPLORER_RETRY_BASE_DELAY_MS,
+            retry_max_delay_ms: DEFAULT_EXPLORER_RETRY_MAX_DELAY_MS,
+            rate_limit_per_sec: None,
+            rate_limit_burst: 1,
+        }
+    }
+
+    /// Читает настройки клиента из переменных с общим префиксом
+    ///
+    /// Используются переменные `{prefix}URL`, `{prefix}BACKEND`, `{prefix}TIMEOUT`,
+    /// `{prefix}AUTH_HEADER` (в виде `Имя: значение`), `{prefix}PROXY`,
+    /// `{prefix}USER_AGENT`, `{prefix}RPC_USER`, `{prefix}RPC_PASSWORD`,
+    /// `{prefix}RPC_COOKIE_FILE`, `{prefix}MAX_RETRIES`, `{prefix}RETRY_BASE_DELAY_MS`,
+    /// `{prefix}RETRY_MAX_DELAY_MS`, `{prefix}RATE_LIMIT` (запросов в секунду)
+    /// и `{prefix}RATE_LIMIT_BURST`.
+    ///
+    /// URL по умолчанию зависит от типа источника: `BITCOIN_EXPLORER_URL` для
+    /// Esplora, `BITCOIN_CORE_RPC_URL` для Bitcoin Core. Для Blockbook URL
+    /// по умолчанию нет: клиент без `{prefix}URL` не создается.
+    ///
+    /// # Arguments
+    /// * `name` - Имя клиента
+    /// * `prefix` - Префикс переменных, например `BITCOIN_EXPLORER_`
+    /// * `lookup` - Источник значений переменных
+    ///
+    /// # Returns
+    /// `Result<Self, ConfigError>` - настройки или ошибка, если `{prefix}BACKEND`
+    /// содержит неизвестный тип источника
+    pub fn from_lookup(name: &str, prefix: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+        let var = |key: &str| lookup(&format!("{}{}", prefix, key)).filter(|v| !v.trim().is_empty());
+        let defaults = Self::new(name, BITCOIN_EXPLORER_URL);
+        let backend = match var("BACKEND") {
+            Some(value) => value.parse().map_err(|message| ConfigError::InvalidValue {
+                variable: format!("{}BACKEND", prefix),
+                message,
+            })?,
+            None => defaults.backend,
+        };
+        let default_url = match backend {
+            ExplorerBackendKind::Esplora => BITCOIN_EXPLORER_URL,
+            ExplorerBackendKind::BitcoinCore => BITCOIN_CORE_RPC_URL,
+            ExplorerBackendKind::Blockbook => "",
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            backend,
+            base_url: var("URL")
+                .map(|v| v.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| default_url.to_string()),
+            timeout_secs: var("TIMEOUT")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.timeout_secs),
+            auth_header: var("AUTH_HEADER").and_then(|v| {
+                v.split_once(':')
+                    .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            }),
+            proxy: var("PROXY"),
+            user_agent: var("USER_AGENT").unwrap_or(defaults.user_agent),
+            rpc_user: var("RPC_USER"),
+            rpc_password: var("RPC_PASSWORD"),
+            rpc_cookie_file: var("RPC_COOKIE_FILE"),
+            max_retries: var("MAX_RETRIES")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.max_retries),
+            retry_base_delay_ms: var("RETRY_BASE_DELAY_MS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.retry_base_delay_ms),
+            retry_max_delay_ms: var("RETRY_MAX_DELAY_MS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.retry_max_delay_ms),
+            rate_limit_per_sec: var("RATE_LIMIT")
+                .and_then(|v| v.parse::<f64>().ok())
+                .filter(|rate| *rate > 0.0),
+            rate_limit_burst: var("RATE_LIMIT_BURST")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.rate_limit_burst),
+        })
+    }
+}
 
 /// Конфигурация приложения
 pub struct Config {
@@ -8,19 +177,167 @@ pub struct Config {
     pub data_dir: String,
     /// Имя папки по умолчанию для данных
     pub default_data_folder: String,
+    /// Базовый URL Python ML сервиса
+    pub ml_service_url: String,
+    /// Таймаут запросов к ML сервису в секундах
+    pub ml_service_timeout: u64,
+    /// Путь к весам локальной графовой модели
+    pub aml_model_path: String,
+    /// Настройки всех именованных клиентов explorer'а
+    pub explorers: Vec<ExplorerConfig>,
+    /// Имя клиента explorer'а, используемого по умолчанию
+    pub default_explorer: String,
+    /// Директория кэша ответов explorer'а (`None` - кэш отключен)
+    pub explorer_cache_dir: Option<String>,
+    /// Время жизни неподтвержденных данных в кэше в секундах
+    pub explorer_cache_mempool_ttl: u64,
+    /// Время жизни подтвержденных данных в кэше в секундах (`None` - бессрочно)
+    pub explorer_cache_confirmed_ttl: Option<u64>,
+    /// Исключать похожие на CoinJoin транзакции из кластеризации адресов
+    pub cluster_exclude_coinjoin: bool,
+    /// Число выходов с одинаковой суммой, начиная с которого транзакция похожа на CoinJoin
+    pub cluster_coinjoin_min_equal_outputs: usize,
+    /// Транзакции с большим числом адресов входов считаются похожими на CoinJoin (`None` - без ограничения)
+    pub cluster_max_input_addresses: Option<usize>,
+    /// Добавлять в кластер отправителя выход-сдачу, найденный эвристиками
+    pub cluster_change_heuristics: bool,
 }
 
 impl Config {
     /// Создает конфигурацию из переменных окружения или использует значения по умолчанию
-    pub fn from_env() -> Self {
-        Self {
+    ///
+    /// Клиент `default` настраивается переменными `BITCOIN_EXPLORER_*`.
+    /// Дополнительные клиенты перечисляются через запятую в `BITCOIN_EXPLORERS`
+    /// и настраиваются переменными `BITCOIN_EXPLORER_{ИМЯ}_*`
+    /// (например, `BITCOIN_EXPLORER_LOCAL_URL`).
+    ///
+    /// # Returns
+    /// `Result<Self, ConfigError>` - конфигурация или ошибка в настройках explorer'а
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Ok(Self {
             test_tx_id: env::var("TEST_TX_ID")
                 .unwrap_or_else(|_| "d6176384de4c0b98702eccb97f3ad6670bc8410d9da715fe5b49462d3e603993".to_string()),
             data_dir: env::var("DATA_DIR")
                 .unwrap_or_else(|_| "./src/data".to_string()),
             default_data_folder: env::var("DEFAULT_DATA_FOLDER")
                 .unwrap_or_else(|_| "1111DAYXhoxZx2tsRnzimfozo783x1yC2".to_string()),
+            ml_service_url: env::var("ML_SERVICE_URL")
+                .unwrap_or_else(|_| "http://localhost:8001".to_string()),
+            ml_service_timeout: env::var("ML_SERVICE_TIMEOUT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            aml_model_path: env::var("AML_MODEL_PATH")
+                .unwrap_or_else(|_| "./src/infrastructure/model/aml_bitcoin.pth".to_string()),
+            explorers: explorers_from_lookup(|key| env::var(key).ok())?,
+            default_explorer: env::var("DEFAULT_EXPLORER")
+                .unwrap_or_else(|_| DEFAULT_EXPLORER_NAME.to_string()),
+            explorer_cache_dir: match env::var("EXPLORER_CACHE_DIR") {
+                Ok(dir) if dir.trim().is_empty() => None,
+                Ok(dir) => Some(dir),
+                Err(_) => Some(DEFAULT_EXPLORER_CACHE_DIR.to_string()),
+            },
+            explorer_cache_mempool_ttl: env::var("EXPLORER_CACHE_MEMPOOL_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_EXPLORER_CACHE_MEMPOOL_TTL_SECS),
+            explorer_cache_confirmed_ttl: env::var("EXPLORER_CACHE_CONFIRMED_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            cluster_exclude_coinjoin: env::var("CLUSTER_EXCLUDE_COINJOIN")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(true),
+            cluster_coinjoin_min_equal_outputs: env::var("CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS),
+            cluster_max_input_addresses: env::var("CLUSTER_MAX_INPUT_ADDRESSES")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+            cluster_change_heuristics: env::var("CLUSTER_CHANGE_HEURISTICS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+        })
+    }
+
+    /// Находит настройки клиента explorer'а по имени
+    pub fn explorer(&self, name: &str) -> Option<&ExplorerConfig> {
+        self.explorers.iter().find(|explorer| explorer.name == name)
+    }
+}
+
+/// Собирает настройки клиента `default` и всех клиентов из `BITCOIN_EXPLORERS`
+fn explorers_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Vec<ExplorerConfig>, ConfigError> {
+    let mut explorers = vec![ExplorerConfig::from_lookup(DEFAULT_EXPLORER_NAME, "BITCOIN_EXPLORER_", &lookup)?];
+
+    let names = lookup("BITCOIN_EXPLORERS").unwrap_or_default();
+    for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if explorers.iter().any(|explorer| explorer.name == name) {
+            continue;
         }
+        let prefix = format!("BITCOIN_EXPLORER_{}_", name.to_ascii_uppercase().replace('-', "_"));
+        explorers.push(ExplorerConfig::from_lookup(name, &prefix, &lookup)?);
     }
+    Ok(explorers)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_named_explorers_from_lookup() {
+        let vars = HashMap::from([
+            ("BITCOIN_EXPLORER_URL", "https://blockstream.info/api/"),
+            ("BITCOIN_EXPLORER_AUTH_HEADER", "Authorization: Bearer token"),
+            ("BITCOIN_EXPLORERS", "local, node, trezor"),
+            ("BITCOIN_EXPLORER_LOCAL_URL", "http://127.0.0.1:3002"),
+            ("BITCOIN_EXPLORER_LOCAL_BACKEND", "blockbook"),
+            ("BITCOIN_EXPLORER_LOCAL_TIMEOUT", "5"),
+            ("BITCOIN_EXPLORER_LOCAL_RATE_LIMIT", "2.5"),
+            ("BITCOIN_EXPLORER_LOCAL_MAX_RETRIES", "0"),
+            ("BITCOIN_EXPLORER_NODE_BACKEND", "bitcoin-core"),
+            ("BITCOIN_EXPLORER_NODE_RPC_USER", "user"),
+            ("BITCOIN_EXPLORER_TREZOR_BACKEND", "blockbook"),
+        ]);
+        let explorers = explorers_from_lookup(|key| vars.get(key).map(|v| v.to_string())).expect("Valid config");
+
+        assert_eq!(explorers.len(), 4);
+        assert_eq!(explorers[0].name, DEFAULT_EXPLORER_NAME);
+        assert_eq!(explorers[0].base_url, "https://blockstream.info/api");
+        assert_eq!(
+            explorers[0].auth_header,
+            Some(("Authorization".to_string(), "Bearer token".to_string()))
+        );
+        assert_eq!(explorers[1].backend, ExplorerBackendKind::Blockbook);
+        assert_eq!(explorers[1].timeout_secs, 5);
+        assert_eq!(explorers[1].rate_limit_per_sec, Some(2.5));
+        assert_eq!(explorers[1].max_retries, 0);
+        assert_eq!(explorers[0].rate_limit_per_sec, None);
+        assert_eq!(explorers[1].user_agent, DEFAULT_EXPLORER_USER_AGENT);
+        assert_eq!(explorers[2].backend, ExplorerBackendKind::BitcoinCore);
+        assert_eq!(explorers[2].base_url, BITCOIN_CORE_RPC_URL);
+        assert_eq!(explorers[2].rpc_user.as_deref(), Some("user"));
+        assert_eq!((explorers[3].backend, explorers[3].base_url.as_str()), (ExplorerBackendKind::Blockbook, ""));
+    }
+
+    #[test]
+    fn test_backend_kind_parsing() {
+        assert_eq!("Esplora".parse(), Ok(ExplorerBackendKind::Esplora));
+        assert_eq!("bitcoind".parse(), Ok(ExplorerBackendKind::BitcoinCore));
+        assert!("electrum".parse::<ExplorerBackendKind>().is_err());
+    }
+
+    #[test]
+    fn test_unknown_backend_is_rejected() {
+        let vars = HashMap::from([("BITCOIN_EXPLORERS", "local"), ("BITCOIN_EXPLORER_LOCAL_BACKEND", "blokbook")]);
+        let error = explorers_from_lookup(|key| vars.get(key).map(|v| v.to_string())).expect_err("Typo is an error");
+        assert_eq!(
+            error.to_string(),
+            "Invalid value of BITCOIN_EXPLORER_LOCAL_BACKEND: unknown explorer backend: blokbook"
+        );
+    }
+}
@@ -1,4 +1,140 @@
 pub static START_PORT: u16 = 31950;
 pub static END_PORT: u16 = 32033;
 
-pub static BITCOIN_EXPLORER_URL: &str = "https://mempool.space/api";
\ No newline at end of file
+pub static BITCOIN_EXPLORER_URL: &str = "https://mempool.space/api";
+/// URL JSON-RPC локального узла Bitcoin Core по умолчанию
+pub static BITCOIN_CORE_RPC_URL: &str = "http://127.0.0.1:8332";
+/// Имя клиента explorer'а, настраиваемого переменными `BITCOIN_EXPLORER_*`
+pub static DEFAULT_EXPLORER_NAME: &str = "default";
+/// Таймаут запросов к explorer'у по умолчанию в секундах
+pub static DEFAULT_EXPLORER_TIMEOUT_SECS: u64 = 30;
+/// User-Agent запросов к explorer'у по умолчанию
+pub static DEFAULT_EXPLORER_USER_AGENT: &str = concat!("diploma_software/", env!("CARGO_PKG_VERSION"));
+/// Число повторов запроса к explorer'у по умолчанию
+pub static DEFAULT_EXPLORER_MAX_RETRIES: u32 = 3;
+/// Начальная задержка экспоненциального повтора в миллисекундах
+pub static DEFAULT_EXPLORER_RETRY_BASE_DELAY_MS: u64 = 500;
+/// Максимальная задержка между повторами в миллисекундах
+pub static DEFAULT_EXPLORER_RETRY_MAX_DELAY_MS: u64 = 30_000;
+/// Директория кэша ответов explorer'а по умолчанию
+pub static DEFAULT_EXPLORER_CACHE_DIR: &str = "./cache/explorer";
+/// Время жизни неподтвержденных данных в кэше в секундах
+pub static DEFAULT_EXPLORER_CACHE_MEMPOOL_TTL_SECS: u64 = 60;
+
+/// Высота блока, с которой отсчитываются временные шаги Elliptic
+pub static TIME_STEP_ORIGIN_HEIGHT: u64 = 391_000;
+/// Длина временного шага в блоках (~2 недели)
+pub static TIME_STEP_BLOCKS: u64 = 2016;
+/// Последний временной шаг датасета Elliptic
+pub static MAX_TIME_STEP: u8 = 49;
+/// Возраст UTXO, после которого вход считается «старым» (30 дней)
+pub static OLD_INPUT_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+/// Сумма, кратная этому числу сатоши (0.001 BTC), считается круглой
+pub static ROUND_VALUE_SATS: u64 = 100_000;
+/// Вероятность, выше которой выход признается сдачей
+pub static CHANGE_PROBABILITY_THRESHOLD: f64 = 0.5;
+/// Номиналы пулов Whirlpool в сатоши (0.001, 0.01, 0.05 и 0.5 BTC)
+pub static WHIRLPOOL_DENOMINATIONS: [u64; 4] = [100_000, 1_000_000, 5_000_000, 50_000_000];
+/// Число участников микса Whirlpool
+pub static WHIRLPOOL_MIX_SIZES: std::ops::RangeInclusive<usize> = 5..=8;
+
+/// Размер страницы подтвержденной истории адреса в Esplora API
+pub static ADDRESS_TXS_PAGE_SIZE: usize = 25;
+/// Максимальное число страниц истории адреса для вычисления признаков
+pub static MAX_ADDRESS_HISTORY_PAGES: usize = 40;
+/// Число одновременных запросов транзакций при загрузке истории адреса из Blockbook
+pub static ADDRESS_TXS_CONCURRENCY: usize = 8;
+
+/// Признаки транзакций Elliptic (первая колонка — `txId`)
+pub static TX_FEATURES_FILE: &str = "elliptic_txs_features.csv";
+/// Классы транзакций Elliptic (`txId`, `class`)
+pub static TX_CLASSES_FILE: &str = "elliptic_txs_classes.csv";
+/// Признаки и классы адресов Elliptic++ (первая колонка — `address`)
+pub static WALLET_FEATURES_FILE: &str = "wallets_features_classes_combined.csv";
+/// Ребра адрес → транзакция
+pub static ADDR_TX_EDGELIST_FILE: &str = "AddrTx_edgelist.csv";
+/// Ребра транзакция → адрес
+pub static TX_ADDR_EDGELIST_FILE: &str = "TxAddr_edgelist.csv";
+/// Ребра транзакция → транзакция Elliptic (`txId1`, `txId2`)
+pub static TX_EDGELIST_FILE: &str = "elliptic_txs_edgelist.csv";
+/// Ребра адрес → адрес Elliptic++
+pub static ADDR_ADDR_EDGELIST_FILE: &str = "AddrAddr_edgelist.csv";
+
+/// Максимальный размер загружаемого архива с данными в байтах
+pub static MAX_DATASET_UPLOAD_BYTES: usize = 1024 * 1024 * 1024;
+/// Максимальный суммарный размер распакованных файлов архива в байтах
+pub static MAX_DATASET_EXTRACTED_BYTES: u64 = 8 * 1024 * 1024 * 1024;
+
+/// Минимальное число выходов с одинаковой суммой, при котором транзакция
+/// распознается как CoinJoin и не участвует в кластеризации
+pub static DEFAULT_CLUSTER_COINJOIN_MIN_EQUAL_OUTPUTS: usize = 3;
+
+/// Глубина трассировки средств по умолчанию (в транзакциях)
+pub static DEFAULT_TRACE_MAX_HOPS: usize = 3;
+/// Максимальная глубина трассировки средств
+pub static MAX_TRACE_HOPS: usize = 20;
+/// Выходы с меньшей загрязненной суммой (сатоши) дальше не отслеживаются
+pub static DEFAULT_TRACE_MIN_VALUE_SATS: u64 = 546;
+/// Максимальное число транзакций в результате трассировки
+pub static MAX_TRACE_TRANSACTIONS: usize = 500;
+
+/// Число путей между адресами по умолчанию
+pub static DEFAULT_PATH_COUNT: usize = 3;
+/// Максимальное число путей между адресами в одном запросе
+pub static MAX_PATH_COUNT: usize = 10;
+/// Глубина расширения графа через explorer по умолчанию (уровней истории адресов)
+pub static DEFAULT_PATH_EXPANSION_DEPTH: usize = 2;
+/// Максимальная глубина расширения графа через explorer
+pub static MAX_PATH_EXPANSION_DEPTH: usize = 4;
+/// Максимальное число транзакций в графе, расширенном через explorer
+pub static MAX_PATH_EXPANSION_TRANSACTIONS: usize = 300;
+/// Число страниц истории адреса, запрашиваемых при расширении графа
+pub static PATH_ADDRESS_HISTORY_PAGES: usize = 1;
+/// Цвет адресов и переводов выделенных путей на странице анализа
+pub static PATH_HIGHLIGHT_COLOR: &str = "#FFD60A";
+
+/// Коэффициент затухания PageRank
+pub static PAGERANK_DAMPING: f64 = 0.85;
+/// Максимальное число итераций PageRank
+pub static PAGERANK_MAX_ITERATIONS: usize = 100;
+/// Порог сходимости PageRank (сумма изменений рангов за итерацию)
+pub static PAGERANK_TOLERANCE: f64 = 1e-10;
+/// Графы с большим числом узлов получают приближенную посредническую
+/// центральность по выборке источников
+pub static BETWEENNESS_EXACT_MAX_NODES: usize = 2_000;
+/// Число источников для приближенной посреднической центральности
+pub static BETWEENNESS_SAMPLE_SOURCES: usize = 100;
+/// Максимальное число итераций распространения меток
+pub static LABEL_PROPAGATION_MAX_ITERATIONS: usize = 100;
+/// Размер узла с наименьшим значением метрики при масштабировании узлов графа
+pub static NODE_MIN_SIZE: f64 = 8.0;
+/// Размер узла с наибольшим значением метрики
+pub static NODE_MAX_SIZE: f64 = 40.0;
+
+/// Доля риска, получаемая от соседей при распространении меток
+/// (остальное — собственная метка узла)
+pub static RISK_PROPAGATION_ALPHA: f64 = 0.85;
+/// Максимальное число итераций распространения риска
+pub static RISK_PROPAGATION_MAX_ITERATIONS: usize = 100;
+/// Порог сходимости распространения риска (наибольшее изменение за итерацию)
+pub static RISK_PROPAGATION_TOLERANCE: f64 = 1e-9;
+/// Число ближайших размеченных соседей в объяснении риска
+pub static RISK_EXPLAIN_NEIGHBOURS: usize = 5;
+/// Глубина поиска размеченных соседей для объяснения риска (в ребрах)
+pub static RISK_EXPLAIN_MAX_DEPTH: usize = 6;
+
+/// Радиус окрестности узла по умолчанию (в ребрах графа адресов и транзакций)
+pub static DEFAULT_EGO_RADIUS: usize = 2;
+/// Максимальный радиус окрестности узла
+pub static MAX_EGO_RADIUS: usize = 6;
+/// Максимальное число узлов окрестности по умолчанию
+pub static DEFAULT_EGO_MAX_NODES: usize = 500;
+/// Максимальное число ребер окрестности по умолчанию
+pub static DEFAULT_EGO_MAX_EDGES: usize = 2_000;
+/// Степень, начиная с которой узел считается хабом и не раскрывается
+/// при сворачивании хабов
+pub static DEFAULT_EGO_HUB_DEGREE: usize = 50;
+/// Цвет центрального узла окрестности
+pub static EGO_CENTER_COLOR: &str = "#0A84FF";
+/// Цвет свернутых хабов окрестности
+pub static EGO_HUB_COLOR: &str = "#8E8E93";
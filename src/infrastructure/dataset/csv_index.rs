@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use csv::{ReaderBuilder, StringRecord};
+
+use crate::infrastructure::dataset::schema::implied_headers_for_file;
+
+/// Индекс CSV файла: смещение записи в байтах по значению первой колонки
+///
+/// Файл читается целиком один раз, после чего запись находится за одно
+/// чтение с диска. Если ключ встречается несколько раз, используется первая
+/// запись (как при линейном поиске). При изменении файла индекс обновляется
+/// через [`CsvIndex::refresh`]: дописанные в конец записи индексируются
+/// инкрементально, любое другое изменение приводит к полной переиндексации.
+#[derive(Debug)]
+pub struct CsvIndex {
+    path: PathBuf,
+    headers: StringRecord,
+    offsets: HashMap<String, u64>,
+    /// Смещение и ключ последней проиндексированной записи
+    tail: Option<(u64, String)>,
+    /// Смещение первой записи (0 для файла без заголовка)
+    data_start: u64,
+    indexed_len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl CsvIndex {
+    /// Индексирует CSV файл
+    ///
+    /// Заголовок определяется так же, как при чтении по схеме: у файла
+    /// признаков транзакций его может не быть (оригинальный Elliptic).
+    ///
+    /// # Arguments
+    /// * `path` - Путь к CSV файлу
+    ///
+    /// # Returns
+    /// `io::Result<Self>` - индекс или ошибка чтения/разбора файла
+    pub fn build(path: &Path) -> io::Result<Self> {
+        let modified = fs::metadata(path)?.modified().ok();
+        let (headers, data_start) = read_headers(path)?;
+
+        let mut index = CsvIndex {
+            path: path.to_path_buf(),
+            headers,
+            offsets: HashMap::new(),
+            tail: None,
+            data_start,
+            indexed_len: data_start,
+            modified,
+        };
+        index.index_from(data_start)?;
+        Ok(index)
+    }
+
+    /// Путь к файлу
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Названия колонок
+    pub fn headers(&self) -> &StringRecord {
+        &self.headers
+    }
+
+    /// Число уникальных ключей
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Проверяет, что в файле нет записей
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Проверяет, что ключ есть в файле
+    pub fn contains(&self, key: &str) -> bool {
+        self.offsets.contains_key(key)
+    }
+
+    /// Обновляет индекс, если файл изменился с момента индексации
+    ///
+    /// # Returns
+    /// `io::Result<bool>` - `true`, если индекс был обновлен
+    pub fn refresh(&mut self) -> io::Result<bool> {
+        let metadata = fs::metadata(&self.path)?;
+        let modified = metadata.modified().ok();
+        if metadata.len() == self.indexed_len && modified == self.modified {
+            return Ok(false);
+        }
+
+        if metadata.len() > self.indexed_len && self.is_appended()? {
+            // Последняя запись могла быть дописана не полностью - читаем ее заново
+            let start = self.tail.as_ref().map_or(self.data_start, |(offset, _)| *offset);
+            self.modified = modified;
+            self.index_from(start)?;
+            log::debug!("appended records indexed: {}", self.path.display());
+        } else {
+            *self = Self::build(&self.path)?;
+            log::debug!("file reindexed: {}", self.path.display());
+        }
+        Ok(true)
+    }
+
+    /// Читает запись по ключу
+    ///
+    /// # Arguments
+    /// * `key` - Значение первой колонки
+    ///
+    /// # Returns
+    /// `io::Result<Option<StringRecord>>` - запись или None, если ключа нет
+    pub fn get(&self, key: &str) -> io::Result<Option<StringRecord>> {
+        match self.offsets.get(key) {
+            Some(&offset) => self.read_at(offset).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Читает запись по ключу в виде значений по названиям колонок
+    pub fn get_fields(&self, key: &str) -> io::Result<Option<HashMap<String, String>>> {
+        Ok(self.get(key)?.map(|record| {
+            self.headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| (header.to_string(), value.to_string()))
+                .collect()
+        }))
+    }
+
+    /// Проверяет, что файл только дописывался: заголовок и последняя запись на месте
+    fn is_appended(&self) -> io::Result<bool> {
+        if read_headers(&self.path)? != (self.headers.clone(), self.data_start) {
+            return Ok(false);
+        }
+        match &self.tail {
+            Some((offset, key)) => Ok(self.read_at(*offset)?.get(0) == Some(key.as_str())),
+            None => Ok(true),
+        }
+    }
+
+    /// Индексирует записи, начиная со смещения `start`
+    fn index_from(&mut self, start: u64) -> io::Result<()> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(start))?;
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(BufReader::new(file));
+
+        let mut record = StringRecord::new();
+        loop {
+            let offset = start + reader.position().byte();
+            if !reader.read_record(&mut record)? {
+                break;
+            }
+            let Some(key) = record.get(0) else { continue };
+            self.offsets.entry(key.to_string()).or_insert(offset);
+            self.tail = Some((offset, key.to_string()));
+        }
+        self.indexed_len = start + reader.position().byte();
+        Ok(())
+    }
+
+    fn read_at(&self, offset: u64) -> io::Result<StringRecord> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(BufReader::new(file));
+        let mut record = StringRecord::new();
+        if !reader.read_record(&mut record)? {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!("no record at offset {} in {}", offset, self.path.display()),
+            ));
+        }
+        Ok(record)
+    }
+}
+
+/// Читает заголовок файла
+///
+/// # Returns
+/// `io::Result<(StringRecord, u64)>` - названия колонок и смещение первой записи
+fn read_headers(path: &Path) -> io::Result<(StringRecord, u64)> {
+    let mut reader = ReaderBuilder::new().has_headers(false).flexible(true).from_path(path)?;
+    let mut first = StringRecord::new();
+    reader.read_record(&mut first)?;
+    let file = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    match implied_headers_for_file(&file, &first) {
+        Some(headers) => Ok((headers, 0)),
+        None => Ok((first, reader.position().byte())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn temp_csv(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("csv_index_{}_{}.csv", name, std::process::id()));
+        fs::write(&path, content).expect("Should write CSV");
+        path
+    }
+
+    #[test]
+    fn test_lookup_by_key() {
+        let path = temp_csv("lookup", "txId,class\n101,1\n\"1,02\",2\n101,3\n103,unknown\n");
+        let index = CsvIndex::build(&path).expect("Should index");
+
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get("1,02").expect("Read").expect("Found").get(1), Some("2"));
+        // Для повторяющегося ключа возвращается первая запись
+        let fields = index.get_fields("101").expect("Read").expect("Found");
+        assert_eq!(fields.get("class").map(String::as_str), Some("1"));
+        assert!(index.get("999").expect("Read").is_none());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_headerless_features_file() {
+        let dir = std::env::temp_dir().join(format!("csv_index_headerless_{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("Should create dir");
+        let path = dir.join("elliptic_txs_features.csv");
+        fs::write(&path, "230425980,1,-0.17,0.5\n5530458,1,-0.11,0.2\n").expect("Should write CSV");
+
+        let index = CsvIndex::build(&path).expect("Should index");
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.headers().iter().collect::<Vec<_>>(), ["txId", "Time step", "feature_1", "feature_2"]);
+        let fields = index.get_fields("230425980").expect("Read").expect("Found");
+        assert_eq!(fields.get("feature_2").map(String::as_str), Some("0.5"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_refresh_indexes_appended_records() {
+        let path = temp_csv("append", "txId,class\n101,1\n102,2");
+        let mut index = CsvIndex::build(&path).expect("Should index");
+        assert!(!index.refresh().expect("Refresh"));
+
+        // Последняя строка без перевода строки дописывается до конца
+        let mut file = fs::OpenOptions::new().append(true).open(&path).expect("Open");
+        file.write_all(b"5\n103,3\n").expect("Append");
+        drop(file);
+
+        assert!(index.refresh().expect("Refresh"));
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.get("102").expect("Read").expect("Found").get(1), Some("25"));
+        assert_eq!(index.get("103").expect("Read").expect("Found").get(1), Some("3"));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_refresh_rebuilds_rewritten_file() {
+        let path = temp_csv("rewrite", "txId,class\n101,1\n102,2\n");
+        let mut index = CsvIndex::build(&path).expect("Should index");
+
+        fs::write(&path, "txId,class\n201,1\n202,2\n203,3\n").expect("Rewrite");
+        assert!(index.refresh().expect("Refresh"));
+        assert!(!index.contains("101"));
+        assert_eq!(index.get("203").expect("Read").expect("Found").get(1), Some("3"));
+        let _ = fs::remove_file(&path);
+    }
+}
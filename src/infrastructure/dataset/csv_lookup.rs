@@ -0,0 +1,29 @@
+use std::path::{Component, Path};
+
+/// Проверяет, что имя папки с данными не выходит за пределы `DATA_DIR`
+///
+/// Допускается ровно один обычный компонент пути: без `..`, разделителей
+/// и абсолютных путей.
+pub fn is_safe_folder_name(folder_name: &str) -> bool {
+    let mut components = Path::new(folder_name).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    ) && !folder_name.contains(['/', '\\'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_safe_folder_name() {
+        assert!(is_safe_folder_name("1111DAYXhoxZx2tsRnzimfozo783x1yC2"));
+        assert!(!is_safe_folder_name(""));
+        assert!(!is_safe_folder_name(".."));
+        assert!(!is_safe_folder_name("../secrets"));
+        assert!(!is_safe_folder_name("a/b"));
+        assert!(!is_safe_folder_name("/etc"));
+        assert!(!is_safe_folder_name("..\\windows"));
+    }
+}
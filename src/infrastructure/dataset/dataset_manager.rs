@@ -0,0 +1,357 @@
+use std::fs::{self, File};
+use std::io::{self, Read, Seek};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+
+use crate::infrastructure::dataset::csv_lookup::is_safe_folder_name;
+use crate::infrastructure::dataset::errors::dataset_errors::DatasetError;
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+use crate::infrastructure::dataset::schema::{
+    validate_file, AddrAddr, AddrTx, CsvRecord, TxAddr, TxClass, TxEdge, TxFeatures, WalletFeatures,
+};
+
+/// Файлы, нужные для построения графа адресов папки
+const GRAPH_FILES: [&str; 4] = [TxClass::FILE, WalletFeatures::FILE, AddrTx::FILE, TxAddr::FILE];
+
+/// Файл папки с данными
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatasetFile {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+/// Папка с данными и ее метаданные
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DatasetFolder {
+    pub name: String,
+    /// Время создания (секунды Unix), если файловая система его хранит
+    pub created: Option<u64>,
+    /// Время последнего изменения (секунды Unix)
+    pub modified: Option<u64>,
+    /// Суммарный размер файлов
+    pub size_bytes: u64,
+    pub files: Vec<DatasetFile>,
+    /// Есть все файлы для построения графа адресов
+    pub graph_ready: bool,
+}
+
+/// Результат проверки файла папки
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FileReport {
+    pub file: String,
+    /// Число записей без заголовка
+    pub records: usize,
+}
+
+/// Проверка схемы файла, возвращающая число записей
+type Validator = fn(&Path) -> Result<usize, SchemaError>;
+
+/// Файлы датасета, которые может содержать папка, и их проверка схемы
+fn validators() -> [(&'static str, Validator); 7] {
+    [
+        (TxFeatures::FILE, validate_file::<TxFeatures>),
+        (TxClass::FILE, validate_file::<TxClass>),
+        (TxEdge::FILE, validate_file::<TxEdge>),
+        (AddrTx::FILE, validate_file::<AddrTx>),
+        (TxAddr::FILE, validate_file::<TxAddr>),
+        (AddrAddr::FILE, validate_file::<AddrAddr>),
+        (WalletFeatures::FILE, validate_file::<WalletFeatures>),
+    ]
+}
+
+/// Проверяет имя новой папки с данными
+///
+/// Кроме требований [`is_safe_folder_name`], имя не должно начинаться с точки
+/// и может содержать только латинские буквы, цифры, `-` и `_` (до 128 символов).
+pub fn is_valid_dataset_name(name: &str) -> bool {
+    is_safe_folder_name(name)
+        && !name.starts_with('.')
+        && name.len() <= 128
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Возвращает папки `data_dir` с метаданными, новые первыми
+///
+/// # Arguments
+/// * `data_dir` - Директория с папками данных (`DATA_DIR`)
+///
+/// # Returns
+/// `io::Result<Vec<DatasetFolder>>` - папки или пустой список, если директории нет
+pub fn list_folders(data_dir: &Path) -> io::Result<Vec<DatasetFolder>> {
+    let entries = match fs::read_dir(data_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut folders = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || !entry.file_type()?.is_dir() {
+            continue;
+        }
+        folders.push(describe_folder(&entry.path(), name)?);
+    }
+    folders.sort_by(|a, b| {
+        b.created
+            .or(b.modified)
+            .cmp(&a.created.or(a.modified))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(folders)
+}
+
+/// Возвращает метаданные папки с данными
+///
+/// # Returns
+/// `Result<DatasetFolder, DatasetError>` - метаданные, `InvalidName` или `NotFound`
+pub fn folder_info(data_dir: &Path, name: &str) -> Result<DatasetFolder, DatasetError> {
+    let path = existing_folder(data_dir, name)?;
+    Ok(describe_folder(&path, name.to_string())?)
+}
+
+/// Проверяет схему всех файлов датасета в папке
+///
+/// # Arguments
+/// * `folder` - Путь к папке с данными
+///
+/// # Returns
+/// `Result<Vec<FileReport>, DatasetError>` - число записей по файлам или первая ошибка схемы
+pub fn validate_folder(folder: &Path) -> Result<Vec<FileReport>, DatasetError> {
+    let mut reports = Vec::new();
+    for (file, validate) in validators() {
+        let path = folder.join(file);
+        if path.is_file() {
+            reports.push(FileReport { file: file.to_string(), records: validate(&path)? });
+        }
+    }
+    if reports.is_empty() {
+        return Err(DatasetError::NoDatasetFiles);
+    }
+    Ok(reports)
+}
+
+/// Распаковывает zip архив с CSV файлами в новую папку и проверяет схему
+///
+/// Архив распаковывается во временную папку, которая переименовывается
+/// в `name` только после успешной проверки. Пути внутри архива
+/// отбрасываются: допускаются только файлы датасета с известными именами.
+///
+/// # Arguments
+/// * `data_dir` - Директория с папками данных (`DATA_DIR`)
+/// * `name` - Имя новой папки
+/// * `archive` - Содержимое zip архива
+/// * `max_bytes` - Ограничение суммарного размера распакованных файлов
+///
+/// # Returns
+/// `Result<DatasetFolder, DatasetError>` - метаданные созданной папки или ошибка
+pub fn import_archive<R: Read + Seek>(
+    data_dir: &Path,
+    name: &str,
+    archive: R,
+    max_bytes: u64,
+) -> Result<DatasetFolder, DatasetError> {
+    if !is_valid_dataset_name(name) {
+        return Err(DatasetError::InvalidName(name.to_string()));
+    }
+    let target = data_dir.join(name);
+    if target.exists() {
+        return Err(DatasetError::AlreadyExists(name.to_string()));
+    }
+
+    let nonce = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+    let staging = data_dir.join(format!(".upload-{}-{}-{}", name, std::process::id(), nonce));
+    fs::create_dir_all(&staging)?;
+
+    let result = extract_archive(archive, &staging, max_bytes)
+        .and_then(|_| validate_folder(&staging))
+        .and_then(|_| {
+            if target.exists() {
+                return Err(DatasetError::AlreadyExists(name.to_string()));
+            }
+            Ok(fs::rename(&staging, &target)?)
+        });
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+    Ok(describe_folder(&target, name.to_string())?)
+}
+
+/// Удаляет папку с данными
+///
+/// # Returns
+/// `Result<(), DatasetError>` - `InvalidName` для небезопасного имени, `NotFound`, если папки нет
+pub fn delete_folder(data_dir: &Path, name: &str) -> Result<(), DatasetError> {
+    let path = existing_folder(data_dir, name)?;
+    fs::remove_dir_all(path)?;
+    Ok(())
+}
+
+fn existing_folder(data_dir: &Path, name: &str) -> Result<std::path::PathBuf, DatasetError> {
+    if !is_safe_folder_name(name) || name.starts_with('.') {
+        return Err(DatasetError::InvalidName(name.to_string()));
+    }
+    let path = data_dir.join(name);
+    // symlink_metadata: ссылка на директорию вне DATA_DIR не считается папкой с данными
+    match fs::symlink_metadata(&path) {
+        Ok(metadata) if metadata.is_dir() => Ok(path),
+        _ => Err(DatasetError::NotFound(name.to_string())),
+    }
+}
+
+fn extract_archive<R: Read + Seek>(archive: R, destination: &Path, max_bytes: u64) -> Result<(), DatasetError> {
+    let mut archive = ZipArchive::new(archive)?;
+    let mut total = 0u64;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() {
+            continue;
+        }
+        let path = entry
+            .enclosed_name()
+            .ok_or_else(|| DatasetError::UnexpectedFile(entry.name().to_string()))?;
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("");
+        // Служебные файлы архиваторов macOS
+        if file_name.starts_with('.') || path.components().any(|c| c.as_os_str() == "__MACOSX") {
+            continue;
+        }
+        if !validators().iter().any(|(known, _)| *known == file_name) {
+            return Err(DatasetError::UnexpectedFile(entry.name().to_string()));
+        }
+
+        let output_path = destination.join(file_name);
+        if output_path.exists() {
+            return Err(DatasetError::UnexpectedFile(format!("{} (duplicate)", entry.name())));
+        }
+        let mut output = File::create(&output_path)?;
+        let remaining = max_bytes.saturating_sub(total);
+        total += io::copy(&mut (&mut entry).take(remaining + 1), &mut output)?;
+        if total > max_bytes {
+            return Err(DatasetError::TooLarge { limit: max_bytes });
+        }
+    }
+    Ok(())
+}
+
+fn describe_folder(path: &Path, name: String) -> io::Result<DatasetFolder> {
+    let metadata = fs::metadata(path)?;
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push(DatasetFile {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                size_bytes: metadata.len(),
+            });
+        }
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(DatasetFolder {
+        graph_ready: GRAPH_FILES.iter().all(|required| files.iter().any(|file| file.name == *required)),
+        name,
+        created: metadata.created().ok().and_then(unix_secs),
+        modified: metadata.modified().ok().and_then(unix_secs),
+        size_bytes: files.iter().map(|file| file.size_bytes).sum(),
+        files,
+    })
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    time.duration_since(UNIX_EPOCH).ok().map(|duration| duration.as_secs())
+}
+
+/// Собирает zip архив из пар «имя — содержимое» для тестов
+#[cfg(test)]
+pub(crate) fn zip_archive(files: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::{Cursor, Write};
+    use zip::write::SimpleFileOptions;
+    use zip::{CompressionMethod, ZipWriter};
+
+    let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in files {
+        writer.start_file(*name, options).expect("Should start file");
+        writer.write_all(content.as_bytes()).expect("Should write file");
+    }
+    writer.finish().expect("Should finish archive").into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn temp_data_dir(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("dataset_manager_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).expect("Should create data dir");
+        path
+    }
+
+    #[test]
+    fn test_import_list_and_delete() {
+        let data_dir = temp_data_dir("import");
+        let archive = zip_archive(&[
+            ("bundle/elliptic_txs_classes.csv", "txId,class\n101,1\n102,unknown\n"),
+            ("bundle/AddrTx_edgelist.csv", "input_address,txId\n1A,101\n"),
+            ("__MACOSX/bundle/._AddrTx_edgelist.csv", "junk"),
+        ]);
+
+        let folder = import_archive(&data_dir, "my_set", Cursor::new(archive.clone()), 1024).expect("Should import");
+        assert_eq!(folder.files.len(), 2);
+        assert!(!folder.graph_ready);
+        assert_eq!(
+            validate_folder(&data_dir.join("my_set")).expect("Valid"),
+            vec![
+                FileReport { file: TxClass::FILE.to_string(), records: 2 },
+                FileReport { file: AddrTx::FILE.to_string(), records: 1 },
+            ]
+        );
+
+        let listed = list_folders(&data_dir).expect("Should list");
+        assert_eq!(listed.iter().map(|f| f.name.as_str()).collect::<Vec<_>>(), vec!["my_set"]);
+        assert!(matches!(
+            import_archive(&data_dir, "my_set", Cursor::new(archive), 1024),
+            Err(DatasetError::AlreadyExists(_))
+        ));
+
+        delete_folder(&data_dir, "my_set").expect("Should delete");
+        assert!(matches!(delete_folder(&data_dir, "my_set"), Err(DatasetError::NotFound(_))));
+        assert!(matches!(delete_folder(&data_dir, ".."), Err(DatasetError::InvalidName(_))));
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn test_import_rejects_bad_archives() {
+        let data_dir = temp_data_dir("reject");
+        let import = |name: &str, files: &[(&str, &str)], limit: u64| {
+            import_archive(&data_dir, name, Cursor::new(zip_archive(files)), limit)
+        };
+
+        assert!(matches!(import("../escape", &[], 1024), Err(DatasetError::InvalidName(_))));
+        assert!(matches!(
+            import("traversal", &[("../../evil.sh", "#!/bin/sh")], 1024),
+            Err(DatasetError::UnexpectedFile(_))
+        ));
+        assert!(matches!(import("empty", &[("readme.txt", "")], 1024), Err(DatasetError::UnexpectedFile(_))));
+        assert!(matches!(import("nothing", &[], 1024), Err(DatasetError::NoDatasetFiles)));
+        assert!(matches!(
+            import("big", &[("elliptic_txs_classes.csv", "txId,class\n101,1\n")], 8),
+            Err(DatasetError::TooLarge { limit: 8 })
+        ));
+        let error = import("invalid", &[("elliptic_txs_classes.csv", "txId,class\n101,7\n")], 1024)
+            .expect_err("Schema error");
+        assert!(error.to_string().contains("elliptic_txs_classes.csv:2: column 2"), "{}", error);
+
+        // Неудачные загрузки не оставляют папок
+        assert_eq!(fs::read_dir(&data_dir).expect("Read dir").count(), 0);
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+}
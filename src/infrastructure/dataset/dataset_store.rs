@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use crate::infrastructure::constants::{TX_CLASSES_FILE, TX_FEATURES_FILE, WALLET_FEATURES_FILE};
+use crate::infrastructure::dataset::csv_index::CsvIndex;
+
+/// Файлы папки с данными, в которых ищутся записи по ключу
+pub const INDEXED_FILES: [&str; 3] = [TX_FEATURES_FILE, TX_CLASSES_FILE, WALLET_FEATURES_FILE];
+
+/// Хранилище индексов CSV файлов папок с данными
+///
+/// Индекс файла строится при первом обращении (или заранее через
+/// [`DatasetStore::load_folder`]) и переиспользуется всеми запросами.
+/// Перед каждым поиском проверяются размер и время изменения файла,
+/// поэтому измененные файлы переиндексируются автоматически.
+///
+/// Индексы хранятся по абсолютному пути, поэтому относительный и
+/// абсолютный путь к одному файлу используют один индекс.
+#[derive(Default)]
+pub struct DatasetStore {
+    indexes: Mutex<HashMap<PathBuf, Arc<Mutex<CsvIndex>>>>,
+}
+
+impl DatasetStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Индексирует файлы папки с данными, которые в ней есть
+    ///
+    /// # Arguments
+    /// * `folder` - Путь к папке с данными
+    ///
+    /// # Returns
+    /// `io::Result<usize>` - число проиндексированных файлов
+    pub fn load_folder(&self, folder: &Path) -> io::Result<usize> {
+        let mut loaded = 0;
+        for file in INDEXED_FILES {
+            let path = folder.join(file);
+            if path.is_file() {
+                self.index(&path)?;
+                loaded += 1;
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Ищет запись в CSV файле по значению первой колонки
+    ///
+    /// # Arguments
+    /// * `path` - Путь к CSV файлу с заголовком
+    /// * `key` - Ключ для поиска (ID транзакции или адрес)
+    ///
+    /// # Returns
+    /// `io::Result<Option<HashMap<String, String>>>` - значения записи по названиям колонок,
+    /// None, если ключа или файла нет
+    pub fn lookup(&self, path: &Path, key: &str) -> io::Result<Option<HashMap<String, String>>> {
+        if !path.is_file() {
+            self.forget(path);
+            return Ok(None);
+        }
+        let index = self.index(path)?;
+        let index = index.lock().unwrap_or_else(|e| e.into_inner());
+        index.get_fields(key)
+    }
+
+    /// Удаляет индексы всех файлов папки, например после удаления папки
+    pub fn forget_folder(&self, folder: &Path) {
+        let folder = normalize(folder);
+        self.lock().retain(|path, _| !path.starts_with(&folder));
+    }
+
+    /// Возвращает актуальный индекс файла, строя его при необходимости
+    fn index(&self, path: &Path) -> io::Result<Arc<Mutex<CsvIndex>>> {
+        let key = normalize(path);
+        let existing = self.lock().get(&key).cloned();
+        if let Some(index) = existing {
+            index.lock().unwrap_or_else(|e| e.into_inner()).refresh()?;
+            return Ok(index);
+        }
+
+        // Индекс строится без блокировки хранилища, чтобы не задерживать поиск в других файлах
+        let built = Arc::new(Mutex::new(CsvIndex::build(path)?));
+        log::debug!("dataset file indexed: {}", path.display());
+        Ok(self.lock().entry(key).or_insert(built).clone())
+    }
+
+    fn forget(&self, path: &Path) {
+        self.lock().remove(&normalize(path));
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, Arc<Mutex<CsvIndex>>>> {
+        self.indexes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Абсолютный путь без обращения к файловой системе (работает и для удаленных папок)
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_lookup_reflects_file_changes() {
+        let folder = std::env::temp_dir().join(format!("dataset_store_{}", std::process::id()));
+        fs::create_dir_all(&folder).expect("Should create folder");
+        let classes = folder.join(TX_CLASSES_FILE);
+        fs::write(&classes, "txId,class\n101,1\n").expect("Should write CSV");
+
+        let store = DatasetStore::new();
+        assert_eq!(store.load_folder(&folder).expect("Load"), 1);
+        let record = store.lookup(&classes, "101").expect("Lookup").expect("Found");
+        assert_eq!(record.get("class").map(String::as_str), Some("1"));
+
+        fs::write(&classes, "txId,class\n101,2\n102,unknown\n").expect("Should rewrite CSV");
+        let record = store.lookup(&classes, "101").expect("Lookup").expect("Found");
+        assert_eq!(record.get("class").map(String::as_str), Some("2"));
+
+        fs::remove_dir_all(&folder).expect("Should remove folder");
+        assert!(store.lookup(&classes, "101").expect("Lookup").is_none());
+    }
+
+    #[test]
+    fn test_relative_and_absolute_paths_share_index() {
+        let folder = PathBuf::from(format!("target/dataset_store_relative_{}", std::process::id()));
+        fs::create_dir_all(&folder).expect("Should create folder");
+        fs::write(folder.join(TX_CLASSES_FILE), "txId,class\n101,1\n").expect("Should write CSV");
+
+        let store = DatasetStore::new();
+        store.load_folder(&folder).expect("Load");
+        let absolute = std::env::current_dir().expect("Current dir").join(&folder).join(TX_CLASSES_FILE);
+        assert!(store.lookup(&absolute, "101").expect("Lookup").is_some());
+        assert_eq!(store.lock().len(), 1);
+
+        store.forget_folder(absolute.parent().expect("Folder"));
+        assert!(store.lock().is_empty());
+        fs::remove_dir_all(&folder).expect("Should remove folder");
+    }
+}
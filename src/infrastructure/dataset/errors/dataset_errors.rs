@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+
+/// Ошибка управления папками с данными
+#[derive(Debug, Error)]
+pub enum DatasetError {
+    #[error("Invalid data folder name: {0}")]
+    InvalidName(String),
+    #[error("Data folder {0} not found")]
+    NotFound(String),
+    #[error("Data folder {0} already exists")]
+    AlreadyExists(String),
+    #[error("Invalid archive: {0}")]
+    Archive(#[from] zip::result::ZipError),
+    #[error("Unexpected file in archive: {0}")]
+    UnexpectedFile(String),
+    #[error("No dataset files found")]
+    NoDatasetFiles,
+    #[error("Extracted files exceed {limit} bytes")]
+    TooLarge { limit: u64 },
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,2 @@
+pub mod schema_errors;
+pub mod dataset_errors;
@@ -0,0 +1,48 @@
+use thiserror::Error;
+
+/// Ошибка чтения или проверки CSV файла датасета
+///
+/// Номера строк и колонок начинаются с единицы; строка 1 — заголовок.
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("{file}: {source}")]
+    Io {
+        file: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{file}:{line}: malformed CSV: {message}")]
+    Csv { file: String, line: u64, message: String },
+    #[error("{file}:1: column {column} must be `{expected}`, found `{found}`")]
+    UnexpectedColumn {
+        file: String,
+        column: usize,
+        expected: String,
+        found: String,
+    },
+    #[error("{file}:1: expected {expected} columns, found {found}")]
+    ColumnCount { file: String, expected: String, found: usize },
+    #[error("{file}:{line}: expected {expected} fields, found {found}")]
+    FieldCount {
+        file: String,
+        line: u64,
+        expected: usize,
+        found: usize,
+    },
+    #[error("{file}:{line}: column {column} (`{name}`): invalid value `{value}`: {message}")]
+    InvalidValue {
+        file: String,
+        line: u64,
+        column: usize,
+        name: String,
+        value: String,
+        message: String,
+    },
+}
+
+impl SchemaError {
+    /// Проверяет, что ошибка означает отсутствие файла
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, SchemaError::Io { source, .. } if source.kind() == std::io::ErrorKind::NotFound)
+    }
+}
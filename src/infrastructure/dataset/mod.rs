@@ -0,0 +1,6 @@
+pub mod csv_lookup;
+pub mod csv_index;
+pub mod dataset_store;
+pub mod errors;
+pub mod schema;
+pub mod dataset_manager;
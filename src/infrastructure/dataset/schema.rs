@@ -0,0 +1,532 @@
+use std::fmt;
+use std::fs::File;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::str::FromStr;
+
+use csv::{ReaderBuilder, StringRecord};
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::constants::{
+    ADDR_ADDR_EDGELIST_FILE, ADDR_TX_EDGELIST_FILE, MAX_TIME_STEP, TX_ADDR_EDGELIST_FILE,
+    TX_CLASSES_FILE, TX_EDGELIST_FILE, TX_FEATURES_FILE, WALLET_FEATURES_FILE,
+};
+use crate::infrastructure::dataset::errors::schema_errors::SchemaError;
+
+/// Класс транзакции или адреса в Elliptic/Elliptic++
+///
+/// В файлах классы записаны как `1` (illicit), `2` (licit) и `unknown`
+/// для транзакций или `3` для адресов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Class {
+    Illicit,
+    Licit,
+    Unknown,
+}
+
+impl FromStr for Class {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "1" => Ok(Class::Illicit),
+            "2" => Ok(Class::Licit),
+            "3" => Ok(Class::Unknown),
+            other if other.eq_ignore_ascii_case("unknown") => Ok(Class::Unknown),
+            _ => Err("expected 1, 2, 3 or unknown".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Class::Illicit => write!(f, "illicit"),
+            Class::Licit => write!(f, "licit"),
+            Class::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Строка CSV файла с номером строки для сообщений об ошибках
+pub struct Row<'a> {
+    file: &'a str,
+    headers: &'a StringRecord,
+    record: &'a StringRecord,
+    line: u64,
+}
+
+impl Row<'_> {
+    /// Номер строки в файле (с единицы)
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Разбирает значение колонки `column` (с нуля)
+    ///
+    /// # Returns
+    /// `Result<T, SchemaError>` - значение или `InvalidValue` с номером строки и колонки
+    pub fn parse<T>(&self, column: usize) -> Result<T, SchemaError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.record.get(column).unwrap_or("").trim();
+        value.parse().map_err(|e: T::Err| self.invalid(column, e.to_string()))
+    }
+
+    /// Возвращает непустое значение колонки `column` (с нуля)
+    pub fn text(&self, column: usize) -> Result<String, SchemaError> {
+        let value = self.record.get(column).unwrap_or("").trim();
+        if value.is_empty() {
+            return Err(self.invalid(column, "value is empty".to_string()));
+        }
+        Ok(value.to_string())
+    }
+
+    /// Разбирает временной шаг (1..=`MAX_TIME_STEP`)
+    pub fn time_step(&self, column: usize) -> Result<u8, SchemaError> {
+        let time_step: u8 = self.parse(column)?;
+        if !(1..=MAX_TIME_STEP).contains(&time_step) {
+            return Err(self.invalid(column, format!("time step must be 1..={}", MAX_TIME_STEP)));
+        }
+        Ok(time_step)
+    }
+
+    /// Разбирает все колонки, начиная с `start`, как числа
+    pub fn features(&self, start: usize) -> Result<Vec<f64>, SchemaError> {
+        (start..self.record.len()).map(|column| self.parse(column)).collect()
+    }
+
+    fn invalid(&self, column: usize, message: String) -> SchemaError {
+        SchemaError::InvalidValue {
+            file: self.file.to_string(),
+            line: self.line,
+            column: column + 1,
+            name: self.headers.get(column).unwrap_or("").to_string(),
+            value: self.record.get(column).unwrap_or("").to_string(),
+            message,
+        }
+    }
+}
+
+/// Заголовок файла без строки заголовка
+///
+/// Если файл может не иметь заголовка, а первая строка начинается с числового
+/// ID, она считается записью: имена обязательных колонок берутся из схемы,
+/// колонки признаков нумеруются (`feature_1`, ...).
+///
+/// # Arguments
+/// * `first` - Первая строка файла
+///
+/// # Returns
+/// `Option<StringRecord>` - имена колонок или `None`, если первая строка — заголовок
+pub fn implied_headers<T: CsvRecord>(first: &StringRecord) -> Option<StringRecord> {
+    let starts_with_id = first.get(0).is_some_and(|v| v.trim().parse::<u64>().is_ok());
+    if !T::HEADER_OPTIONAL || !starts_with_id {
+        return None;
+    }
+    let mut headers: StringRecord = T::COLUMNS.iter().copied().collect();
+    for i in T::COLUMNS.len()..first.len() {
+        headers.push_field(&format!("feature_{}", i - T::COLUMNS.len() + 1));
+    }
+    Some(headers)
+}
+
+/// [`implied_headers`] по имени файла датасета
+///
+/// # Arguments
+/// * `file` - Имя файла в папке с данными
+/// * `first` - Первая строка файла
+pub fn implied_headers_for_file(file: &str, first: &StringRecord) -> Option<StringRecord> {
+    if file == TxFeatures::FILE {
+        implied_headers::<TxFeatures>(first)
+    } else {
+        None
+    }
+}
+
+/// Типизированная запись CSV файла датасета
+pub trait CsvRecord: Sized {
+    /// Имя файла в папке с данными
+    const FILE: &'static str;
+    /// Обязательные первые колонки
+    const COLUMNS: &'static [&'static str];
+    /// После обязательных колонок идут числовые признаки
+    const HAS_FEATURES: bool = false;
+    /// Файл может не иметь заголовка (оригинальный Elliptic)
+    const HEADER_OPTIONAL: bool = false;
+
+    /// Разбирает строку файла
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError>;
+}
+
+/// Признаки транзакции (`elliptic_txs_features.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxFeatures {
+    pub tx_id: u64,
+    pub time_step: u8,
+    /// Локальные и агрегированные признаки в порядке колонок
+    pub features: Vec<f64>,
+}
+
+impl CsvRecord for TxFeatures {
+    const FILE: &'static str = TX_FEATURES_FILE;
+    const COLUMNS: &'static [&'static str] = &["txId", "Time step"];
+    const HAS_FEATURES: bool = true;
+    const HEADER_OPTIONAL: bool = true;
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(TxFeatures {
+            tx_id: row.parse(0)?,
+            time_step: row.time_step(1)?,
+            features: row.features(2)?,
+        })
+    }
+}
+
+/// Временной шаг транзакции из `elliptic_txs_features.csv` без разбора признаков
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxTimeStep {
+    pub tx_id: u64,
+    pub time_step: u8,
+}
+
+impl CsvRecord for TxTimeStep {
+    const FILE: &'static str = TX_FEATURES_FILE;
+    const COLUMNS: &'static [&'static str] = TxFeatures::COLUMNS;
+    const HAS_FEATURES: bool = true;
+    const HEADER_OPTIONAL: bool = true;
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(TxTimeStep {
+            tx_id: row.parse(0)?,
+            time_step: row.time_step(1)?,
+        })
+    }
+}
+
+/// Класс транзакции (`elliptic_txs_classes.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxClass {
+    pub tx_id: u64,
+    pub class: Class,
+}
+
+impl CsvRecord for TxClass {
+    const FILE: &'static str = TX_CLASSES_FILE;
+    const COLUMNS: &'static [&'static str] = &["txId", "class"];
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(TxClass { tx_id: row.parse(0)?, class: row.parse(1)? })
+    }
+}
+
+/// Ребро транзакция → транзакция (`elliptic_txs_edgelist.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxEdge {
+    pub tx_id1: u64,
+    pub tx_id2: u64,
+}
+
+impl CsvRecord for TxEdge {
+    const FILE: &'static str = TX_EDGELIST_FILE;
+    const COLUMNS: &'static [&'static str] = &["txId1", "txId2"];
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(TxEdge { tx_id1: row.parse(0)?, tx_id2: row.parse(1)? })
+    }
+}
+
+/// Ребро адрес входа → транзакция (`AddrTx_edgelist.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddrTx {
+    pub input_address: String,
+    pub tx_id: u64,
+}
+
+impl CsvRecord for AddrTx {
+    const FILE: &'static str = ADDR_TX_EDGELIST_FILE;
+    const COLUMNS: &'static [&'static str] = &["input_address", "txId"];
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(AddrTx { input_address: row.text(0)?, tx_id: row.parse(1)? })
+    }
+}
+
+/// Ребро транзакция → адрес выхода (`TxAddr_edgelist.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxAddr {
+    pub tx_id: u64,
+    pub output_address: String,
+}
+
+impl CsvRecord for TxAddr {
+    const FILE: &'static str = TX_ADDR_EDGELIST_FILE;
+    const COLUMNS: &'static [&'static str] = &["txId", "output_address"];
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(TxAddr { tx_id: row.parse(0)?, output_address: row.text(1)? })
+    }
+}
+
+/// Ребро адрес входа → адрес выхода (`AddrAddr_edgelist.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddrAddr {
+    pub input_address: String,
+    pub output_address: String,
+}
+
+impl CsvRecord for AddrAddr {
+    const FILE: &'static str = ADDR_ADDR_EDGELIST_FILE;
+    const COLUMNS: &'static [&'static str] = &["input_address", "output_address"];
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(AddrAddr { input_address: row.text(0)?, output_address: row.text(1)? })
+    }
+}
+
+/// Признаки и класс адреса на временном шаге (`wallets_features_classes_combined.csv`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletFeatures {
+    pub address: String,
+    pub time_step: u8,
+    pub class: Class,
+    /// Признаки адреса в порядке колонок
+    pub features: Vec<f64>,
+}
+
+impl CsvRecord for WalletFeatures {
+    const FILE: &'static str = WALLET_FEATURES_FILE;
+    const COLUMNS: &'static [&'static str] = &["address", "Time step", "class"];
+    const HAS_FEATURES: bool = true;
+
+    fn from_row(row: &Row<'_>) -> Result<Self, SchemaError> {
+        Ok(WalletFeatures {
+            address: row.text(0)?,
+            time_step: row.time_step(1)?,
+            class: row.parse(2)?,
+            features: row.features(3)?,
+        })
+    }
+}
+
+/// Потоковое чтение типизированных записей CSV файла с проверкой схемы
+///
+/// Заголовок проверяется при открытии; каждая строка проверяется при чтении,
+/// ошибки содержат имя файла, номер строки и колонки.
+///
+/// # Example
+/// ```no_run
+/// use std::path::Path;
+/// use diploma_software::infrastructure::dataset::schema::{RecordReader, TxClass};
+///
+/// for record in RecordReader::<TxClass>::open(Path::new("data/elliptic_txs_classes.csv"))? {
+///     let record = record?;
+///     println!("{} {}", record.tx_id, record.class);
+/// }
+/// # Ok::<(), diploma_software::infrastructure::dataset::errors::schema_errors::SchemaError>(())
+/// ```
+pub struct RecordReader<T> {
+    file: String,
+    reader: csv::Reader<File>,
+    headers: StringRecord,
+    /// Первая строка файла без заголовка, еще не отданная читателю
+    pending: Option<StringRecord>,
+    _record: PhantomData<T>,
+}
+
+impl<T: CsvRecord> RecordReader<T> {
+    /// Открывает файл и проверяет заголовок
+    ///
+    /// # Arguments
+    /// * `path` - Путь к CSV файлу
+    ///
+    /// # Returns
+    /// `Result<Self, SchemaError>` - читатель или ошибка открытия/заголовка
+    pub fn open(path: &Path) -> Result<Self, SchemaError> {
+        let file = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| T::FILE.to_string());
+        let source = File::open(path).map_err(|source| SchemaError::Io { file: file.clone(), source })?;
+        let mut reader = ReaderBuilder::new().has_headers(false).flexible(true).from_reader(source);
+
+        let mut first = StringRecord::new();
+        let has_first = reader.read_record(&mut first).map_err(|e| csv_error(&file, e))?;
+        let mut reader = RecordReader {
+            file,
+            reader,
+            headers: StringRecord::new(),
+            pending: None,
+            _record: PhantomData,
+        };
+
+        if let Some(headers) = implied_headers::<T>(&first).filter(|_| has_first) {
+            reader.headers = headers;
+            reader.pending = Some(first);
+        } else {
+            reader.headers = first;
+            reader.check_headers()?;
+        }
+        Ok(reader)
+    }
+
+    /// Названия колонок
+    pub fn headers(&self) -> &StringRecord {
+        &self.headers
+    }
+
+    /// Названия колонок признаков (после обязательных колонок)
+    pub fn feature_names(&self) -> impl Iterator<Item = &str> {
+        self.headers.iter().skip(T::COLUMNS.len())
+    }
+
+    fn check_headers(&self) -> Result<(), SchemaError> {
+        let found = self.headers.len();
+        let too_few = found < T::COLUMNS.len();
+        if too_few || (!T::HAS_FEATURES && found != T::COLUMNS.len()) {
+            let expected = if T::HAS_FEATURES {
+                format!("at least {}", T::COLUMNS.len())
+            } else {
+                T::COLUMNS.len().to_string()
+            };
+            return Err(SchemaError::ColumnCount { file: self.file.clone(), expected, found });
+        }
+        for (i, (expected, header)) in T::COLUMNS.iter().zip(self.headers.iter()).enumerate() {
+            if !header.trim().eq_ignore_ascii_case(expected) {
+                return Err(SchemaError::UnexpectedColumn {
+                    file: self.file.clone(),
+                    column: i + 1,
+                    expected: expected.to_string(),
+                    found: header.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_record(&self, record: &StringRecord, line: u64) -> Result<T, SchemaError> {
+        if record.len() != self.headers.len() {
+            return Err(SchemaError::FieldCount {
+                file: self.file.clone(),
+                line,
+                expected: self.headers.len(),
+                found: record.len(),
+            });
+        }
+        T::from_row(&Row { file: &self.file, headers: &self.headers, record, line })
+    }
+}
+
+impl<T: CsvRecord> Iterator for RecordReader<T> {
+    type Item = Result<T, SchemaError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.pending.take() {
+            return Some(self.parse_record(&first, 1));
+        }
+        let mut record = StringRecord::new();
+        match self.reader.read_record(&mut record) {
+            Ok(true) => {
+                let line = record.position().map_or(0, |position| position.line());
+                Some(self.parse_record(&record, line))
+            }
+            Ok(false) => None,
+            Err(e) => Some(Err(csv_error(&self.file, e))),
+        }
+    }
+}
+
+/// Читает все записи файла
+pub fn read_records<T: CsvRecord>(path: &Path) -> Result<Vec<T>, SchemaError> {
+    RecordReader::<T>::open(path)?.collect()
+}
+
+/// Проверяет файл целиком и возвращает число записей
+pub fn validate_file<T: CsvRecord>(path: &Path) -> Result<usize, SchemaError> {
+    let mut count = 0;
+    for record in RecordReader::<T>::open(path)? {
+        record?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn csv_error(file: &str, error: csv::Error) -> SchemaError {
+    let line = error.position().map_or(0, |position| position.line());
+    match error.into_kind() {
+        csv::ErrorKind::Io(source) => SchemaError::Io { file: file.to_string(), source },
+        kind => SchemaError::Csv { file: file.to_string(), line, message: format!("{:?}", kind) },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn temp_csv(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("schema_{}_{}.csv", name, std::process::id()));
+        fs::write(&path, content).expect("Should write CSV");
+        path
+    }
+
+    #[test]
+    fn test_class_from_str() {
+        assert_eq!("1".parse(), Ok(Class::Illicit));
+        assert_eq!("2".parse(), Ok(Class::Licit));
+        assert_eq!("3".parse(), Ok(Class::Unknown));
+        assert_eq!("unknown".parse(), Ok(Class::Unknown));
+        assert!("4".parse::<Class>().is_err());
+        assert_eq!(Class::Illicit.to_string(), "illicit");
+    }
+
+    #[test]
+    fn test_reads_typed_records() {
+        let path = temp_csv("wallets", "address,Time step,class,num_txs\n1A,7,2,3\n1B,49,3,0.5\n");
+        let reader = RecordReader::<WalletFeatures>::open(&path).expect("Should open");
+        assert_eq!(reader.feature_names().collect::<Vec<_>>(), vec!["num_txs"]);
+
+        let records: Vec<WalletFeatures> = reader.collect::<Result<_, _>>().expect("Valid records");
+        assert_eq!(records[0].class, Class::Licit);
+        assert_eq!(records[1], WalletFeatures {
+            address: "1B".to_string(),
+            time_step: 49,
+            class: Class::Unknown,
+            features: vec![0.5],
+        });
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_headerless_tx_features() {
+        let path = temp_csv("features", "101,7,0.5,-1\n102,8,0.25,2\n");
+        let records = read_records::<TxFeatures>(&path).expect("Valid records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].features, vec![0.5, -1.0]);
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_errors_name_line_and_column() {
+        let path = temp_csv("classes", "txId,class\n101,1\n102,5\n");
+        let error = validate_file::<TxClass>(&path).expect_err("Invalid class");
+        assert!(matches!(error, SchemaError::InvalidValue { line: 3, column: 2, .. }), "{}", error);
+        assert!(error.to_string().contains(":3: column 2 (`class`)"), "{}", error);
+
+        fs::write(&path, "txId,label\n101,1\n").expect("Should write CSV");
+        let error = validate_file::<TxClass>(&path).expect_err("Invalid header");
+        assert!(matches!(error, SchemaError::UnexpectedColumn { column: 2, .. }), "{}", error);
+
+        fs::write(&path, "txId,class\n101\n").expect("Should write CSV");
+        let error = validate_file::<TxClass>(&path).expect_err("Short row");
+        assert!(matches!(error, SchemaError::FieldCount { line: 2, found: 1, .. }), "{}", error);
+        let _ = fs::remove_file(&path);
+
+        let error = validate_file::<TxClass>(&path).expect_err("Missing file");
+        assert!(error.is_not_found());
+    }
+}
@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum ConfigError {
+    #[error("Invalid value of {variable}: {message}")]
+    InvalidValue { variable: String, message: String },
+}
@@ -1,2 +1,5 @@
 pub mod constants;
-pub mod config;
\ No newline at end of file
+pub mod config;
+pub mod model;
+pub mod dataset;
+pub mod errors;
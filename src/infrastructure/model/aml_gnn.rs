@@ -0,0 +1,365 @@
+//! CPU инференс графовой модели `aml_bitcoin.pth`
+//!
+//! Модель состоит из четырех слоев `ChebConv` (PyTorch Geometric) с
+//! порядками полиномов Чебышева 1, 2, 3 и 4: 94 входных признака
+//! транзакции, 64 скрытых нейрона и 2 выходных класса. Между слоями
+//! применяется ReLU, dropout в режиме инференса отключен.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::errors::model_errors::ModelError;
+use super::torch_loader::{load_state_dict, Tensor};
+
+/// Число входных признаков модели: временной шаг и 93 локальных признака Elliptic
+pub const GNN_INPUT_FEATURES: usize = 94;
+/// Индекс класса «illicit» в выходе модели
+pub const ILLICIT_CLASS_INDEX: usize = 1;
+
+/// Слой Чебышевской свертки: `out = sum_k Tx_k W_k^T + b`
+#[derive(Debug, Clone)]
+struct ChebLayer {
+    /// Веса `lins.k.weight`, каждая матрица размером `[out, in]`
+    weights: Vec<Tensor>,
+    bias: Vec<f32>,
+    in_features: usize,
+    out_features: usize,
+}
+
+impl ChebLayer {
+    fn from_state_dict(state: &HashMap<String, Tensor>, name: &str) -> Result<Self, ModelError> {
+        let bias_name = format!("{}.bias", name);
+        let bias = state
+            .get(&bias_name)
+            .ok_or_else(|| ModelError::MissingTensor(bias_name.clone()))?;
+
+        let mut weights = Vec::new();
+        while let Some(weight) = state.get(&format!("{}.lins.{}.weight", name, weights.len())) {
+            weights.push(weight.clone());
+        }
+        let first = weights
+            .first()
+            .ok_or_else(|| ModelError::MissingTensor(format!("{}.lins.0.weight", name)))?;
+        if first.shape.len() != 2 {
+            return Err(ModelError::ShapeMismatch {
+                name: format!("{}.lins.0.weight", name),
+                expected: vec![0, 0],
+                actual: first.shape.clone(),
+            });
+        }
+        let (out_features, in_features) = (first.shape[0], first.shape[1]);
+
+        for (k, weight) in weights.iter().enumerate() {
+            if weight.shape != first.shape {
+                return Err(ModelError::ShapeMismatch {
+                    name: format!("{}.lins.{}.weight", name, k),
+                    expected: first.shape.clone(),
+                    actual: weight.shape.clone(),
+                });
+            }
+        }
+        if bias.shape != vec![out_features] {
+            return Err(ModelError::ShapeMismatch {
+                name: bias_name,
+                expected: vec![out_features],
+                actual: bias.shape.clone(),
+            });
+        }
+
+        Ok(Self {
+            weights,
+            bias: bias.data.clone(),
+            in_features,
+            out_features,
+        })
+    }
+
+    /// Прямой проход слоя по рекурсии Чебышева
+    fn forward(&self, x: &[Vec<f32>], laplacian: &ScaledLaplacian) -> Vec<Vec<f32>> {
+        let mut out: Vec<Vec<f32>> = vec![self.bias.clone(); x.len()];
+
+        let mut tx_prev: Vec<Vec<f32>> = x.to_vec();
+        accumulate_linear(&mut out, &tx_prev, &self.weights[0]);
+        if self.weights.len() == 1 {
+            return out;
+        }
+
+        let mut tx_curr = laplacian.apply(&tx_prev);
+        accumulate_linear(&mut out, &tx_curr, &self.weights[1]);
+
+        for weight in &self.weights[2..] {
+            // Tx_k = 2 * L * Tx_{k-1} - Tx_{k-2}
+            let mut tx_next = laplacian.apply(&tx_curr);
+            for (next, prev) in tx_next.iter_mut().zip(&tx_prev) {
+                for (n, p) in next.iter_mut().zip(prev) {
+                    *n = 2.0 * *n - p;
+                }
+            }
+            accumulate_linear(&mut out, &tx_next, weight);
+            tx_prev = std::mem::replace(&mut tx_curr, tx_next);
+        }
+
+        out
+    }
+}
+
+/// Добавляет `x W^T` к `out` построчно
+fn accumulate_linear(out: &mut [Vec<f32>], x: &[Vec<f32>], weight: &Tensor) {
+    let in_features = weight.shape[1];
+    for (row_out, row_x) in out.iter_mut().zip(x) {
+        for (o, w_row) in row_out.iter_mut().zip(weight.data.chunks_exact(in_features)) {
+            *o += w_row.iter().zip(row_x).map(|(w, v)| w * v).sum::<f32>();
+        }
+    }
+}
+
+/// Масштабированный лапласиан `2L/λmax - I` при `λmax = 2` и симметричной нормализации
+///
+/// Совпадает с `ChebConv(normalization="sym")` из PyTorch Geometric: ребро
+/// `j -> i` передает `-x_j / sqrt(deg(j) deg(i))`, где степень считается
+/// по исходящим ребрам, петли удаляются, диагональ обнуляется.
+struct ScaledLaplacian {
+    /// Для каждого узла-получателя: (узел-источник, вес)
+    incoming: Vec<Vec<(usize, f32)>>,
+}
+
+impl ScaledLaplacian {
+    fn new(num_nodes: usize, edges: &[(usize, usize)]) -> Self {
+        let edges: Vec<(usize, usize)> = edges.iter().copied().filter(|(s, t)| s != t).collect();
+
+        let mut degree = vec![0f32; num_nodes];
+        for (source, _) in &edges {
+            degree[*source] += 1.0;
+        }
+        let inv_sqrt: Vec<f32> = degree
+            .iter()
+            .map(|d| if *d > 0.0 { 1.0 / d.sqrt() } else { 0.0 })
+            .collect();
+
+        let mut incoming = vec![Vec::new(); num_nodes];
+        for (source, target) in edges {
+            incoming[target].push((source, -inv_sqrt[source] * inv_sqrt[target]));
+        }
+        Self { incoming }
+    }
+
+    fn apply(&self, x: &[Vec<f32>]) -> Vec<Vec<f32>> {
+        let width = x.first().map(|row| row.len()).unwrap_or(0);
+        self.incoming
+            .iter()
+            .map(|sources| {
+                let mut row = vec![0f32; width];
+                for (source, weight) in sources {
+                    for (r, v) in row.iter_mut().zip(&x[*source]) {
+                        *r += weight * v;
+                    }
+                }
+                row
+            })
+            .collect()
+    }
+}
+
+/// Оценка узла подграфа
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NodeScore {
+    /// Вероятности классов (softmax выхода модели)
+    pub probabilities: Vec<f32>,
+    /// Вероятность класса «illicit»
+    pub illicit_probability: f32,
+}
+
+/// Графовая модель AML, загруженная из `aml_bitcoin.pth`
+#[derive(Debug, Clone)]
+pub struct AmlGnn {
+    layers: Vec<ChebLayer>,
+}
+
+impl AmlGnn {
+    /// Загружает модель из state dict PyTorch
+    ///
+    /// # Arguments
+    /// * `path` - Путь к файлу `.pth`
+    ///
+    /// # Returns
+    /// `Result<Self, ModelError>` - модель или ошибка загрузки
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ModelError> {
+        Self::from_state_dict(&load_state_dict(path)?)
+    }
+
+    /// Собирает модель из уже загруженных тензоров (слои `conv1`, `conv2`, ...)
+    pub fn from_state_dict(state: &HashMap<String, Tensor>) -> Result<Self, ModelError> {
+        let mut layers: Vec<ChebLayer> = Vec::new();
+        while state.contains_key(&format!("conv{}.bias", layers.len() + 1)) {
+            let layer = ChebLayer::from_state_dict(state, &format!("conv{}", layers.len() + 1))?;
+            if let Some(previous) = layers.last() {
+                if previous.out_features != layer.in_features {
+                    return Err(ModelError::ShapeMismatch {
+                        name: format!("conv{}.lins.0.weight", layers.len() + 1),
+                        expected: vec![layer.out_features, previous.out_features],
+                        actual: vec![layer.out_features, layer.in_features],
+                    });
+                }
+            }
+            layers.push(layer);
+        }
+        if layers.is_empty() {
+            return Err(ModelError::MissingTensor("conv1.bias".to_string()));
+        }
+        Ok(Self { layers })
+    }
+
+    /// Число входных признаков узла
+    pub fn input_features(&self) -> usize {
+        self.layers[0].in_features
+    }
+
+    /// Число выходных классов
+    pub fn num_classes(&self) -> usize {
+        self.layers[self.layers.len() - 1].out_features
+    }
+
+    /// Порядки полиномов Чебышева по слоям
+    pub fn cheb_orders(&self) -> Vec<usize> {
+        self.layers.iter().map(|l| l.weights.len()).collect()
+    }
+
+    /// Прямой проход: логиты классов для каждого узла
+    ///
+    /// # Arguments
+    /// * `features` - Признаки узлов, по `input_features()` значений на узел
+    /// * `edges` - Направленные ребра `(источник, получатель)` по индексам узлов
+    pub fn forward(&self, features: &[Vec<f32>], edges: &[(usize, usize)]) -> Result<Vec<Vec<f32>>, ModelError> {
+        if let Some(row) = features.iter().position(|row| row.len() != self.input_features()) {
+            return Err(ModelError::InvalidInput(format!(
+                "node {} has {} features, expected {}",
+                row,
+                features[row].len(),
+                self.input_features()
+            )));
+        }
+        if let Some((s, t)) = edges.iter().find(|(s, t)| *s >= features.len() || *t >= features.len()) {
+            return Err(ModelError::InvalidInput(format!("edge ({}, {}) is out of range", s, t)));
+        }
+
+        let laplacian = ScaledLaplacian::new(features.len(), edges);
+        let mut x = features.to_vec();
+        for (i, layer) in self.layers.iter().enumerate() {
+            x = layer.forward(&x, &laplacian);
+            if i + 1 < self.layers.len() {
+                x.iter_mut().flatten().for_each(|v| *v = v.max(0.0));
+            }
+        }
+        Ok(x)
+    }
+
+    /// Оценивает подграф транзакций: вероятности классов для каждого узла
+    ///
+    /// # Arguments
+    /// * `features` - Признаки узлов
+    /// * `edges` - Направленные ребра между узлами
+    pub fn score_subgraph(&self, features: &[Vec<f32>], edges: &[(usize, usize)]) -> Result<Vec<NodeScore>, ModelError> {
+        Ok(self
+            .forward(features, edges)?
+            .into_iter()
+            .map(|logits| {
+                let probabilities = softmax(&logits);
+                NodeScore {
+                    illicit_probability: probabilities.get(ILLICIT_CLASS_INDEX).copied().unwrap_or(0.0),
+                    probabilities,
+                }
+            })
+            .collect())
+    }
+}
+
+fn softmax(logits: &[f32]) -> Vec<f32> {
+    let max = logits.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exp: Vec<f32> = logits.iter().map(|v| (v - max).exp()).collect();
+    let sum: f32 = exp.iter().sum();
+    exp.iter().map(|v| v / sum).collect()
+}
+
+/// Признаки узла для модели из строки `elliptic_txs_features.csv`
+///
+/// # Arguments
+/// * `row` - Значения строки без колонки `txId` (временной шаг и 165 признаков)
+pub fn gnn_features_from_elliptic_row(row: &[f64]) -> Vec<f32> {
+    row.iter().take(GNN_INPUT_FEATURES).map(|v| *v as f32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tensor(shape: &[usize], data: &[f32]) -> Tensor {
+        Tensor { shape: shape.to_vec(), data: data.to_vec() }
+    }
+
+    /// Один слой K=3 с единичными весами 1x1
+    fn identity_cheb_model() -> AmlGnn {
+        let state = HashMap::from([
+            ("conv1.bias".to_string(), tensor(&[1], &[0.0])),
+            ("conv1.lins.0.weight".to_string(), tensor(&[1, 1], &[0.0])),
+            ("conv1.lins.1.weight".to_string(), tensor(&[1, 1], &[0.0])),
+            ("conv1.lins.2.weight".to_string(), tensor(&[1, 1], &[1.0])),
+        ]);
+        AmlGnn::from_state_dict(&state).expect("Should build model")
+    }
+
+    #[test]
+    fn test_chebyshev_recursion() {
+        // Для неориентированной пары узлов L = [[0,-1],[-1,0]], L^2 = I,
+        // поэтому Tx_2 = 2 L^2 x - x = x
+        let model = identity_cheb_model();
+        let out = model
+            .forward(&[vec![3.0], vec![5.0]], &[(0, 1), (1, 0)])
+            .expect("Should run forward");
+        assert_eq!(out, vec![vec![3.0], vec![5.0]]);
+
+        // Изолированные узлы: L = 0, Tx_2 = -x
+        let out = model.forward(&[vec![3.0]], &[]).expect("Should run forward");
+        assert_eq!(out, vec![vec![-3.0]]);
+    }
+
+    #[test]
+    fn test_forward_rejects_invalid_input() {
+        let model = identity_cheb_model();
+        assert!(model.forward(&[vec![1.0, 2.0]], &[]).is_err());
+        assert!(model.forward(&[vec![1.0]], &[(0, 3)]).is_err());
+    }
+
+    #[test]
+    fn test_bundled_model_scores_subgraph() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/infrastructure/model/aml_bitcoin.pth");
+        let model = AmlGnn::load(path).expect("Should load model");
+
+        assert_eq!(model.input_features(), GNN_INPUT_FEATURES);
+        assert_eq!(model.num_classes(), 2);
+        assert_eq!(model.cheb_orders(), vec![1, 2, 3, 4]);
+
+        let features: Vec<Vec<f32>> = (0..4)
+            .map(|i| (0..GNN_INPUT_FEATURES).map(|j| ((i * 7 + j) % 11) as f32 / 10.0 - 0.5).collect())
+            .collect();
+        let scores = model
+            .score_subgraph(&features, &[(0, 1), (1, 2), (2, 3), (3, 0)])
+            .expect("Should score subgraph");
+
+        assert_eq!(scores.len(), 4);
+        for score in scores {
+            let sum: f32 = score.probabilities.iter().sum();
+            assert!((sum - 1.0).abs() < 1e-5);
+            assert!((0.0..=1.0).contains(&score.illicit_probability));
+        }
+    }
+
+    #[test]
+    fn test_features_from_elliptic_row() {
+        let row: Vec<f64> = (0..166).map(|v| v as f64).collect();
+        let features = gnn_features_from_elliptic_row(&row);
+        assert_eq!(features.len(), GNN_INPUT_FEATURES);
+        assert_eq!(features[0], 0.0);
+    }
+}
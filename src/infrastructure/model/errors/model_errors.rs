@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ModelError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Archive error: {0}")]
+    Archive(#[from] zip::result::ZipError),
+    #[error("Pickle error at byte {position}: {message}")]
+    Pickle { position: usize, message: String },
+    #[error("Unsupported tensor storage: {0}")]
+    UnsupportedStorage(String),
+    #[error("Missing tensor in state dict: {0}")]
+    MissingTensor(String),
+    #[error("Tensor {name} has shape {actual:?}, expected {expected:?}")]
+    ShapeMismatch {
+        name: String,
+        expected: Vec<usize>,
+        actual: Vec<usize>,
+    },
+    #[error("Invalid model input: {0}")]
+    InvalidInput(String),
+}
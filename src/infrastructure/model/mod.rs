@@ -0,0 +1,3 @@
+pub mod torch_loader;
+pub mod aml_gnn;
+pub mod errors;
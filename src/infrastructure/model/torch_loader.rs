@@ -0,0 +1,578 @@
+//! Загрузчик state dict PyTorch (`torch.save`, zip-формат) без Python
+//!
+//! Архив содержит `data.pkl` с описанием тензоров и файлы `data/<key>`
+//! с сырыми данными хранилищ. Pickle разбирается минимальной виртуальной
+//! машиной, которая понимает только опкоды, используемые `torch.save`.
+//! Расположение хранилища (`cuda:0` и т.п.) игнорируется: все тензоры
+//! загружаются в память процесса, что эквивалентно `map_location="cpu"`.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use super::errors::model_errors::ModelError;
+
+/// Тензор float32 в непрерывной (row-major) раскладке
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tensor {
+    pub shape: Vec<usize>,
+    pub data: Vec<f32>,
+}
+
+impl Tensor {
+    /// Число элементов тензора
+    pub fn numel(&self) -> usize {
+        self.shape.iter().product()
+    }
+}
+
+/// Значение pickle, достаточное для разбора state dict
+#[derive(Debug, Clone)]
+enum PickleValue {
+    None,
+    #[allow(dead_code)]
+    Bool(bool),
+    Int(i64),
+    #[allow(dead_code)]
+    Float(f64),
+    String(String),
+    Tuple(Vec<PickleValue>),
+    List(Vec<PickleValue>),
+    Dict(Vec<(PickleValue, PickleValue)>),
+    Global(String, String),
+    /// Ссылка на хранилище из `BINPERSID`
+    Storage(StorageRef),
+    /// Тензор, восстановленный `torch._utils._rebuild_tensor_v2`
+    Tensor(TensorRef),
+    Mark,
+}
+
+#[derive(Debug, Clone)]
+struct StorageRef {
+    dtype: String,
+    key: String,
+}
+
+#[derive(Debug, Clone)]
+struct TensorRef {
+    storage: StorageRef,
+    offset: usize,
+    shape: Vec<usize>,
+    stride: Vec<usize>,
+}
+
+impl PickleValue {
+    fn as_usize(&self) -> Option<usize> {
+        match self {
+            PickleValue::Int(v) if *v >= 0 => Some(*v as usize),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            PickleValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn as_usize_tuple(&self) -> Option<Vec<usize>> {
+        match self {
+            PickleValue::Tuple(items) => items.iter().map(|v| v.as_usize()).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Минимальная виртуальная машина pickle (протоколы 2–4)
+struct Unpickler<'a> {
+    data: &'a [u8],
+    pos: usize,
+    stack: Vec<PickleValue>,
+    memo: HashMap<u32, PickleValue>,
+}
+
+impl<'a> Unpickler<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            stack: Vec::new(),
+            memo: HashMap::new(),
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ModelError {
+        ModelError::Pickle {
+            position: self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn read_bytes(&mut self, n: usize) -> Result<&'a [u8], ModelError> {
+        let end = self.pos.checked_add(n).filter(|end| *end <= self.data.len());
+        match end {
+            Some(end) => {
+                let bytes = &self.data[self.pos..end];
+                self.pos = end;
+                Ok(bytes)
+            }
+            None => Err(self.error("unexpected end of data")),
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, ModelError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, ModelError> {
+        let b = self.read_bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ModelError> {
+        let b = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn read_line(&mut self) -> Result<String, ModelError> {
+        let start = self.pos;
+        while self.read_u8()? != b'\n' {}
+        Ok(String::from_utf8_lossy(&self.data[start..self.pos - 1]).into_owned())
+    }
+
+    fn read_string(&mut self, n: usize) -> Result<PickleValue, ModelError> {
+        let bytes = self.read_bytes(n)?;
+        let s = std::str::from_utf8(bytes).map_err(|_| self.error("invalid utf-8 string"))?;
+        Ok(PickleValue::String(s.to_string()))
+    }
+
+    fn pop(&mut self) -> Result<PickleValue, ModelError> {
+        self.stack.pop().ok_or_else(|| self.error("stack underflow"))
+    }
+
+    fn top(&self) -> Result<PickleValue, ModelError> {
+        self.stack.last().cloned().ok_or_else(|| self.error("empty stack"))
+    }
+
+    /// Снимает со стека все значения до ближайшей метки
+    fn pop_mark(&mut self) -> Result<Vec<PickleValue>, ModelError> {
+        let mark = self
+            .stack
+            .iter()
+            .rposition(|v| matches!(v, PickleValue::Mark))
+            .ok_or_else(|| self.error("mark not found"))?;
+        let items = self.stack.split_off(mark + 1);
+        self.stack.pop();
+        Ok(items)
+    }
+
+    fn memoize(&mut self, key: u32) -> Result<(), ModelError> {
+        let value = self.top()?;
+        self.memo.insert(key, value);
+        Ok(())
+    }
+
+    fn memo_get(&mut self, key: u32) -> Result<(), ModelError> {
+        let value = self
+            .memo
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| self.error(format!("memo key {} not found", key)))?;
+        self.stack.push(value);
+        Ok(())
+    }
+
+    /// Добавляет пары ключ-значение в словарь на вершине стека
+    fn set_items(&mut self, items: Vec<PickleValue>) -> Result<(), ModelError> {
+        let mut pairs = Vec::with_capacity(items.len() / 2);
+        let mut iter = items.into_iter();
+        while let (Some(k), Some(v)) = (iter.next(), iter.next()) {
+            pairs.push((k, v));
+        }
+        match self.stack.last_mut() {
+            Some(PickleValue::Dict(entries)) => {
+                entries.extend(pairs);
+                Ok(())
+            }
+            _ => Err(self.error("SETITEM target is not a dict")),
+        }
+    }
+
+    fn append_items(&mut self, items: Vec<PickleValue>) -> Result<(), ModelError> {
+        match self.stack.last_mut() {
+            Some(PickleValue::List(list)) => {
+                list.extend(items);
+                Ok(())
+            }
+            _ => Err(self.error("APPEND target is not a list")),
+        }
+    }
+
+    /// Выполняет вызов `callable(*args)` для известных torch/collections функций
+    fn reduce(&self, callable: PickleValue, args: PickleValue) -> Result<PickleValue, ModelError> {
+        let (module, name) = match &callable {
+            PickleValue::Global(module, name) => (module.as_str(), name.as_str()),
+            _ => return Err(self.error("REDUCE on non-global callable")),
+        };
+        let args = match args {
+            PickleValue::Tuple(args) => args,
+            _ => return Err(self.error("REDUCE arguments are not a tuple")),
+        };
+
+        match (module, name) {
+            ("collections", "OrderedDict") => Ok(PickleValue::Dict(Vec::new())),
+            ("torch._utils", "_rebuild_tensor_v2") | ("torch._utils", "_rebuild_tensor") => {
+                let storage = match args.first() {
+                    Some(PickleValue::Storage(storage)) => storage.clone(),
+                    _ => return Err(self.error("tensor without storage")),
+                };
+                let offset = args.get(1).and_then(|v| v.as_usize()).ok_or_else(|| self.error("invalid storage offset"))?;
+                let shape = args.get(2).and_then(|v| v.as_usize_tuple()).ok_or_else(|| self.error("invalid tensor size"))?;
+                let stride = args.get(3).and_then(|v| v.as_usize_tuple()).ok_or_else(|| self.error("invalid tensor stride"))?;
+                Ok(PickleValue::Tensor(TensorRef { storage, offset, shape, stride }))
+            }
+            _ => Err(self.error(format!("unsupported callable {}.{}", module, name))),
+        }
+    }
+
+    /// Разбирает persistent id вида `('storage', FloatStorage, key, location, numel)`
+    fn persistent_load(&self, pid: PickleValue) -> Result<PickleValue, ModelError> {
+        let items = match pid {
+            PickleValue::Tuple(items) => items,
+            _ => return Err(self.error("persistent id is not a tuple")),
+        };
+        if items.first().and_then(|v| v.as_str()) != Some("storage") {
+            return Err(self.error("persistent id is not a storage"));
+        }
+        let dtype = match items.get(1) {
+            Some(PickleValue::Global(_, name)) => name.clone(),
+            _ => return Err(self.error("invalid storage type")),
+        };
+        let key = items
+            .get(2)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| self.error("invalid storage key"))?
+            .to_string();
+        Ok(PickleValue::Storage(StorageRef { dtype, key }))
+    }
+
+    fn run(mut self) -> Result<PickleValue, ModelError> {
+        loop {
+            let opcode = self.read_u8()?;
+            match opcode {
+                // PROTO
+                0x80 => {
+                    self.read_u8()?;
+                }
+                // FRAME
+                0x95 => {
+                    self.read_bytes(8)?;
+                }
+                // STOP
+                b'.' => return self.pop(),
+                b'(' => self.stack.push(PickleValue::Mark),
+                b'N' => self.stack.push(PickleValue::None),
+                0x88 => self.stack.push(PickleValue::Bool(true)),
+                0x89 => self.stack.push(PickleValue::Bool(false)),
+                // BININT / BININT1 / BININT2
+                b'J' => {
+                    let v = self.read_u32()? as i32;
+                    self.stack.push(PickleValue::Int(v as i64));
+                }
+                b'K' => {
+                    let v = self.read_u8()?;
+                    self.stack.push(PickleValue::Int(v as i64));
+                }
+                b'M' => {
+                    let v = self.read_u16()?;
+                    self.stack.push(PickleValue::Int(v as i64));
+                }
+                // LONG1
+                0x8a => {
+                    let n = self.read_u8()? as usize;
+                    let bytes = self.read_bytes(n)?;
+                    if n > 8 {
+                        return Err(self.error("LONG1 value too large"));
+                    }
+                    let mut buf = if bytes.last().is_some_and(|b| b & 0x80 != 0) { [0xff; 8] } else { [0; 8] };
+                    buf[..n].copy_from_slice(bytes);
+                    self.stack.push(PickleValue::Int(i64::from_le_bytes(buf)));
+                }
+                // BINFLOAT
+                b'G' => {
+                    let b = self.read_bytes(8)?;
+                    let mut buf = [0u8; 8];
+                    buf.copy_from_slice(b);
+                    self.stack.push(PickleValue::Float(f64::from_be_bytes(buf)));
+                }
+                // BINUNICODE / SHORT_BINUNICODE
+                b'X' => {
+                    let n = self.read_u32()? as usize;
+                    let value = self.read_string(n)?;
+                    self.stack.push(value);
+                }
+                0x8c => {
+                    let n = self.read_u8()? as usize;
+                    let value = self.read_string(n)?;
+                    self.stack.push(value);
+                }
+                // GLOBAL / STACK_GLOBAL
+                b'c' => {
+                    let module = self.read_line()?;
+                    let name = self.read_line()?;
+                    self.stack.push(PickleValue::Global(module, name));
+                }
+                0x93 => {
+                    let name = self.pop()?;
+                    let module = self.pop()?;
+                    match (module, name) {
+                        (PickleValue::String(module), PickleValue::String(name)) => {
+                            self.stack.push(PickleValue::Global(module, name))
+                        }
+                        _ => return Err(self.error("STACK_GLOBAL expects strings")),
+                    }
+                }
+                // Memo: BINPUT / LONG_BINPUT / MEMOIZE / BINGET / LONG_BINGET
+                b'q' => {
+                    let key = self.read_u8()? as u32;
+                    self.memoize(key)?;
+                }
+                b'r' => {
+                    let key = self.read_u32()?;
+                    self.memoize(key)?;
+                }
+                0x94 => {
+                    let key = self.memo.len() as u32;
+                    self.memoize(key)?;
+                }
+                b'h' => {
+                    let key = self.read_u8()? as u32;
+                    self.memo_get(key)?;
+                }
+                b'j' => {
+                    let key = self.read_u32()?;
+                    self.memo_get(key)?;
+                }
+                // Кортежи
+                b')' => self.stack.push(PickleValue::Tuple(Vec::new())),
+                b't' => {
+                    let items = self.pop_mark()?;
+                    self.stack.push(PickleValue::Tuple(items));
+                }
+                0x85..=0x87 => {
+                    let n = (opcode - 0x84) as usize;
+                    if self.stack.len() < n {
+                        return Err(self.error("stack underflow in TUPLE"));
+                    }
+                    let items = self.stack.split_off(self.stack.len() - n);
+                    self.stack.push(PickleValue::Tuple(items));
+                }
+                // Списки и словари
+                b']' => self.stack.push(PickleValue::List(Vec::new())),
+                b'}' => self.stack.push(PickleValue::Dict(Vec::new())),
+                b'a' => {
+                    let item = self.pop()?;
+                    self.append_items(vec![item])?;
+                }
+                b'e' => {
+                    let items = self.pop_mark()?;
+                    self.append_items(items)?;
+                }
+                b's' => {
+                    let value = self.pop()?;
+                    let key = self.pop()?;
+                    self.set_items(vec![key, value])?;
+                }
+                b'u' => {
+                    let items = self.pop_mark()?;
+                    self.set_items(items)?;
+                }
+                // REDUCE / BUILD / BINPERSID
+                b'R' => {
+                    let args = self.pop()?;
+                    let callable = self.pop()?;
+                    let value = self.reduce(callable, args)?;
+                    self.stack.push(value);
+                }
+                b'b' => {
+                    // Состояние (например `_metadata` у OrderedDict) не нужно для весов
+                    self.pop()?;
+                }
+                b'Q' => {
+                    let pid = self.pop()?;
+                    let storage = self.persistent_load(pid)?;
+                    self.stack.push(storage);
+                }
+                other => return Err(self.error(format!("unsupported opcode 0x{:02x}", other))),
+            }
+        }
+    }
+}
+
+/// Копирует элементы тензора из хранилища с учетом смещения и шагов
+fn materialize(tensor: &TensorRef, storage: &[f32]) -> Result<Vec<f32>, ModelError> {
+    let numel: usize = tensor.shape.iter().product();
+    let mut data = Vec::with_capacity(numel);
+    let mut index = vec![0usize; tensor.shape.len()];
+    for _ in 0..numel {
+        let position = tensor.offset
+            + index
+                .iter()
+                .zip(&tensor.stride)
+                .map(|(i, s)| i * s)
+                .sum::<usize>();
+        let value = storage.get(position).copied().ok_or_else(|| {
+            ModelError::UnsupportedStorage(format!("storage {} is too small", tensor.storage.key))
+        })?;
+        data.push(value);
+
+        for dim in (0..index.len()).rev() {
+            index[dim] += 1;
+            if index[dim] < tensor.shape[dim] {
+                break;
+            }
+            index[dim] = 0;
+        }
+    }
+    Ok(data)
+}
+
+/// Декодирует little-endian хранилище в f32
+fn decode_storage(dtype: &str, bytes: &[u8]) -> Result<Vec<f32>, ModelError> {
+    match dtype {
+        "FloatStorage" => Ok(bytes
+            .chunks_exact(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect()),
+        "DoubleStorage" => Ok(bytes
+            .chunks_exact(8)
+            .map(|c| f64::from_le_bytes([c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7]]) as f32)
+            .collect()),
+        other => Err(ModelError::UnsupportedStorage(other.to_string())),
+    }
+}
+
+/// Загружает state dict из zip-архива `torch.save`
+///
+/// # Arguments
+/// * `reader` - Источник данных архива
+///
+/// # Returns
+/// `Result<HashMap<String, Tensor>, ModelError>` - тензоры по именам параметров
+pub fn read_state_dict<R: Read + Seek>(reader: R) -> Result<HashMap<String, Tensor>, ModelError> {
+    let mut archive = ZipArchive::new(reader)?;
+
+    // Все записи лежат в каталоге с именем архива, например `aml_bitcoin/`
+    let pickle_name = archive
+        .file_names()
+        .find(|name| name.ends_with("data.pkl"))
+        .map(|name| name.to_string())
+        .ok_or_else(|| ModelError::MissingTensor("data.pkl".to_string()))?;
+    let prefix = pickle_name.trim_end_matches("data.pkl").to_string();
+
+    if let Ok(mut byteorder) = archive.by_name(&format!("{}byteorder", prefix)) {
+        let mut order = String::new();
+        byteorder.read_to_string(&mut order)?;
+        if order.trim() != "little" {
+            return Err(ModelError::UnsupportedStorage(format!("byteorder {}", order.trim())));
+        }
+    }
+
+    let mut pickle = Vec::new();
+    archive.by_name(&pickle_name)?.read_to_end(&mut pickle)?;
+    let root = Unpickler::new(&pickle).run()?;
+
+    let entries = match root {
+        PickleValue::Dict(entries) => entries,
+        _ => {
+            return Err(ModelError::Pickle {
+                position: 0,
+                message: "state dict is not a dict".to_string(),
+            })
+        }
+    };
+
+    let mut storages: HashMap<String, Vec<f32>> = HashMap::new();
+    let mut tensors = HashMap::new();
+    for (key, value) in entries {
+        let (name, tensor) = match (key, value) {
+            (PickleValue::String(name), PickleValue::Tensor(tensor)) => (name, tensor),
+            _ => continue,
+        };
+
+        if !storages.contains_key(&tensor.storage.key) {
+            let mut bytes = Vec::new();
+            archive
+                .by_name(&format!("{}data/{}", prefix, tensor.storage.key))?
+                .read_to_end(&mut bytes)?;
+            storages.insert(tensor.storage.key.clone(), decode_storage(&tensor.storage.dtype, &bytes)?);
+        }
+        let data = materialize(&tensor, &storages[&tensor.storage.key])?;
+        tensors.insert(name, Tensor { shape: tensor.shape, data });
+    }
+
+    Ok(tensors)
+}
+
+/// Загружает state dict из файла `.pth`
+///
+/// # Arguments
+/// * `path` - Путь к файлу модели
+pub fn load_state_dict(path: impl AsRef<Path>) -> Result<HashMap<String, Tensor>, ModelError> {
+    read_state_dict(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpickle_ordered_dict_with_build() {
+        // OrderedDict() с двумя элементами и атрибутом _metadata через BUILD
+        let mut data = vec![0x80, 2];
+        data.extend(b"ccollections\nOrderedDict\nq\x00)Rq\x01(");
+        data.extend(b"X\x01\x00\x00\x00aq\x02K\x07");
+        data.extend(b"X\x01\x00\x00\x00bJ\xff\xff\xff\xffu");
+        data.extend(b"}b.");
+
+        let value = Unpickler::new(&data).run().expect("Should unpickle");
+        match value {
+            PickleValue::Dict(entries) => {
+                assert_eq!(entries.len(), 2);
+                assert_eq!(entries[0].0.as_str(), Some("a"));
+                assert_eq!(entries[0].1.as_usize(), Some(7));
+                assert!(matches!(entries[1].1, PickleValue::Int(-1)));
+            }
+            other => panic!("Unexpected value: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_materialize_transposed_tensor() {
+        let tensor = TensorRef {
+            storage: StorageRef { dtype: "FloatStorage".to_string(), key: "0".to_string() },
+            offset: 0,
+            shape: vec![2, 3],
+            stride: vec![1, 2],
+        };
+        let data = materialize(&tensor, &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]).expect("Should materialize");
+        assert_eq!(data, vec![0.0, 2.0, 4.0, 1.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_load_bundled_model() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/infrastructure/model/aml_bitcoin.pth");
+        let state = load_state_dict(path).expect("Should load state dict");
+
+        assert_eq!(state.len(), 14);
+        assert_eq!(state["conv1.lins.0.weight"].shape, vec![64, 94]);
+        assert_eq!(state["conv1.bias"].shape, vec![64]);
+        assert_eq!(state["conv4.lins.3.weight"].shape, vec![2, 64]);
+        assert!(state.values().all(|t| t.data.len() == t.numel()));
+        assert!(state.values().flat_map(|t| &t.data).all(|v| v.is_finite()));
+    }
+}
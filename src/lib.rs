@@ -1,3 +1,4 @@
+pub mod api;
 pub mod application;
 pub mod infrastructure;
 pub mod services;
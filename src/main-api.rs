@@ -1,24 +1,30 @@
-mod infrastructure;
-mod application;
-mod services;
-
 use actix_web::{Responder, web, get, post, HttpRequest, HttpResponse, HttpServer, App};
-use tokio::fs::{read_to_string, metadata, DirEntry};
-use serde_json::{json, Value};
-use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use tokio::fs::read_to_string;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::env;
-use env_logger;
 use std::net::{TcpListener};
-use urlencoding;
-use open;
-use csv::Reader;
+use std::sync::Arc;
 use tera::{Tera, Context};
-use crate::infrastructure::constants::{END_PORT, START_PORT};
-use std::time::{UNIX_EPOCH};
-
-use crate::infrastructure::config::Config;
+use serde::de::DeserializeOwned;
+use diploma_software::infrastructure::constants::{DEFAULT_EGO_HUB_DEGREE, END_PORT, START_PORT, TX_FEATURES_FILE, WALLET_FEATURES_FILE};
+
+use diploma_software::api::state::AppState;
+use diploma_software::api::v1::ego::{load_ego_network, EgoQuery};
+use diploma_software::api::v1::routes as api_v1;
+use diploma_software::api::v1::validation::parse_txid;
+use diploma_software::application::services::analysis::transaction_analysis::{collect_evidence, request_verdict};
+use diploma_software::application::services::clustering::address_clustering::ClusteringOptions;
+use diploma_software::application::services::graph::graph_data::{address_graph_data, highlight_ego, highlight_paths, style_nodes, NodeColoring, NodeSizing};
+use diploma_software::application::services::graph::path_finding::{k_shortest_paths, PathMode, PathOptions};
+use diploma_software::application::services::graph::transaction_graph::NodeId;
+use diploma_software::infrastructure::config::Config;
+use diploma_software::infrastructure::dataset::dataset_manager::list_folders;
+use diploma_software::infrastructure::dataset::dataset_store::DatasetStore;
+use diploma_software::infrastructure::dataset::csv_lookup::is_safe_folder_name;
+use diploma_software::infrastructure::dataset::errors::schema_errors::SchemaError;
+use diploma_software::services::explorer::explorer_registry::ExplorerRegistry;
+use diploma_software::services::ml::ml_client::MLClient;
 
 /// Перенаправляет на страницу ошибки с сообщением
 fn redirect_to_error_page(error_message: &str) -> HttpResponse {
@@ -28,39 +34,6 @@ fn redirect_to_error_page(error_message: &str) -> HttpResponse {
         .finish()
 }
 
-/// Ищет запись в CSV файле по ключу
-///
-/// # Arguments
-/// * `file_name` - Имя CSV файла
-/// * `key` - Ключ для поиска (первая колонка)
-/// * `data_folder` - Папка с данными
-///
-/// # Returns
-/// `Option<HashMap<String, String>>` - Найденные данные или None
-fn find_in_csv(file_name: &str, key: &str, data_folder: &str) -> Option<HashMap<String, String>> {
-    let path = get_data_path(data_folder, file_name);
-    if !path.exists() {
-        return None;
-    }
-    let mut rdr = Reader::from_path(&path).ok()?;
-    let headers = rdr.headers().ok()?.clone();
-    for result in rdr.records() {
-        let record = result.ok()?;
-        if record.get(0)? == key {
-            let mut data = HashMap::new();
-            for (i, field) in headers.iter().enumerate() {
-                data.insert(field.to_string(), record.get(i)?.to_string());
-            }
-            return Some(data);
-        }
-    }
-    None
-}
-
-struct AppState {
-    tera: Tera,
-}
-
 /// Получает информацию о транзакции по её ID
 ///
 /// # Arguments
@@ -70,21 +43,22 @@ struct AppState {
 /// HTML страницу с информацией о транзакции или страницу ошибки
 #[get("/tx/{tx_id}")]
 async fn get_transaction(
+    state: web::Data<AppState>,
     path: web::Path<String>
 ) -> impl Responder {
     let tx_id = path.into_inner();
-    let config = Config::from_env();
-    let file_path = get_data_path(&config.default_data_folder, "elliptic_txs_features.csv");
+    let config = &state.config;
+    let file_path = Path::new(&config.data_dir).join(&config.default_data_folder).join(TX_FEATURES_FILE);
 
-    let file_path_str = match file_path.to_str() {
-        Some(s) => s,
-        None => return redirect_to_error_page("Invalid file path encoding"),
-    };
+    let lookup_state = state.clone();
+    let key = tx_id.clone();
+    let record = web::block(move || lookup_state.datasets.lookup(&file_path, &key)).await;
 
-    match find_in_csv(file_path_str, &tx_id, &config.default_data_folder) {
-        Some(data) => {
+    match record {
+        Ok(Ok(Some(data))) => {
             let mut ctx = Context::new();
             ctx.insert("data", &data);
+            ctx.insert("tx_id", &tx_id);
 
             let analysis_template_path = Path::new("static/tx.html");
             let analysis_template_content = match read_to_string(analysis_template_path).await {
@@ -99,11 +73,63 @@ async fn get_transaction(
                 Err(err) => redirect_to_error_page(&format!("Ошибка рендеринга шаблона: {:?}", err)),
             }
         }
-        None => HttpResponse::NotFound().content_type("text/html; charset=utf-8").body("Транзакция не найдена"),
+        Ok(Ok(None)) => HttpResponse::NotFound().content_type("text/html; charset=utf-8").body("Транзакция не найдена"),
+        Ok(Err(err)) => redirect_to_error_page(&format!("Ошибка чтения данных: {}", err)),
+        Err(err) => redirect_to_error_page(&format!("Ошибка чтения данных: {}", err)),
     }
 }
 
 
+/// Анализирует транзакцию по данным explorer'а с вердиктом ML сервиса
+///
+/// Если ML сервис недоступен, страница показывает признаки транзакции
+/// и сообщение об ошибке вместо вердикта.
+///
+/// # Arguments
+/// * `path` - Path параметр с ID транзакции
+///
+/// # Returns
+/// HTML страницу с признаками и вердиктом или страницу ошибки
+#[get("/analyze/tx/{tx_id}")]
+async fn analyze_transaction(
+    state: web::Data<AppState>,
+    path: web::Path<String>
+) -> impl Responder {
+    let tx_id = match parse_txid(&path) {
+        Ok(tx_id) => tx_id,
+        Err(err) => return redirect_to_error_page(&err.to_string()),
+    };
+    let client = match state.explorer(None) {
+        Ok(client) => client,
+        Err(err) => return redirect_to_error_page(&err.to_string()),
+    };
+    let evidence = match collect_evidence(client, &tx_id).await {
+        Ok(evidence) => evidence,
+        Err(err) => return redirect_to_error_page(&format!("Не удалось получить транзакцию {}: {}", tx_id, err)),
+    };
+
+    let mut ctx = Context::new();
+    ctx.insert("txid", &evidence.txid);
+    ctx.insert("data", &evidence.feature_rows());
+    match request_verdict(&state.ml, &evidence).await {
+        Ok(verdict) => ctx.insert("verdict", &verdict),
+        Err(err) => ctx.insert("ml_error", &err.to_string()),
+    }
+
+    let analysis_template_path = Path::new("static/tx.html");
+    let analysis_template_content = match read_to_string(analysis_template_path).await {
+        Ok(content) => content,
+        Err(err) => return redirect_to_error_page(&format!("Ошибка загрузки шаблона анализа: {:?}", err)),
+    };
+
+    match Tera::one_off(&analysis_template_content, &ctx, true) {
+        Ok(html) => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(html),
+        Err(err) => redirect_to_error_page(&format!("Ошибка рендеринга шаблона: {:?}", err)),
+    }
+}
+
 /// Получает информацию об адресе по его значению
 ///
 /// # Arguments
@@ -113,21 +139,22 @@ async fn get_transaction(
 /// HTML страницу с информацией об адресе или страницу ошибки
 #[get("/address/{address}")]
 async fn get_address(
+    state: web::Data<AppState>,
     path: web::Path<String>
 ) -> impl Responder {
     let address = path.into_inner();
-    let config = Config::from_env();
-    let file_path = get_data_path(&config.default_data_folder, "wallets_features_classes_combined.csv");
+    let config = &state.config;
+    let file_path = Path::new(&config.data_dir).join(&config.default_data_folder).join(WALLET_FEATURES_FILE);
 
-    let file_path_str = match file_path.to_str() {
-        Some(s) => s,
-        None => return redirect_to_error_page("Invalid file path encoding"),
-    };
+    let lookup_state = state.clone();
+    let key = address.clone();
+    let record = web::block(move || lookup_state.datasets.lookup(&file_path, &key)).await;
 
-    match find_in_csv(file_path_str, &address, &config.default_data_folder) {
-        Some(data) => {
+    match record {
+        Ok(Ok(Some(data))) => {
             let mut ctx = Context::new();
             ctx.insert("data", &data);
+            ctx.insert("address", &address);
 
             let analysis_template_path = Path::new("static/address.html");
             let analysis_template_content = match read_to_string(analysis_template_path).await {
@@ -142,23 +169,67 @@ async fn get_address(
                 Err(err) => redirect_to_error_page(&format!("Ошибка рендеринга шаблона: {:?}", err)),
             }
         }
-        None => HttpResponse::NotFound().content_type("text/html; charset=utf-8").body("Адрес не найден"),
+        Ok(Ok(None)) => HttpResponse::NotFound().content_type("text/html; charset=utf-8").body("Адрес не найден"),
+        Ok(Err(err)) => redirect_to_error_page(&format!("Ошибка чтения данных: {}", err)),
+        Err(err) => redirect_to_error_page(&format!("Ошибка чтения данных: {}", err)),
     }
 }
 
-async fn process_entry(entry: DirEntry) {
-    let path = entry.path();
-    if path.is_dir() {
-        if let Ok(metadata) = metadata(&path).await {
-            if let Ok(created_time) = metadata.created() {
-                let since_epoch = created_time.duration_since(UNIX_EPOCH).unwrap_or_default();
-                println!(
-                    "Папка: {:?}, Дата создания: {} секунд с 1970-01-01",
-                    path.file_name().unwrap_or_default(),
-                    since_epoch.as_secs()
-                );
-            }
-        }
+/// Окрестность транзакции или адреса в графе папки с данными
+///
+/// # Arguments
+/// * `path` - Тип узла (`tx` или `address`) и его ID
+/// * `query` - Папка, радиус, ограничения и сворачивание хабов
+///
+/// # Returns
+/// HTML страницу с графом окрестности или страницу ошибки
+#[get("/ego/{kind}/{id}")]
+async fn ego_page(
+    state: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+    query: web::Query<EgoQuery>
+) -> impl Responder {
+    let (kind, id) = path.into_inner();
+    let center = match kind.as_str() {
+        "tx" => NodeId::transaction(&id),
+        "address" => NodeId::address(id.as_str()),
+        _ => return redirect_to_error_page(&format!("Неизвестный тип узла {}", kind)),
+    };
+    let ego = match load_ego_network(&state, &query, center).await {
+        Ok(ego) => ego,
+        Err(err) => return redirect_to_error_page(&format!("Ошибка построения окрестности: {}", err)),
+    };
+
+    let template_content = match read_to_string(Path::new("static/ego.html")).await {
+        Ok(content) => content,
+        Err(err) => return redirect_to_error_page(&format!("Ошибка загрузки шаблона: {:?}", err)),
+    };
+
+    // Граф адресов окрестности с выделенным центром и свернутыми хабами
+    let mut graph_data = address_graph_data(&ego.subgraph());
+    highlight_ego(&mut graph_data, &ego);
+
+    let options = query.options().unwrap_or_default();
+    let mut ctx = Context::new();
+    ctx.insert("nodes", &serde_json::to_string(&graph_data.nodes).unwrap_or_else(|_| "[]".to_string()));
+    ctx.insert("edges", &serde_json::to_string(&graph_data.edges).unwrap_or_else(|_| "[]".to_string()));
+    ctx.insert("kind", &kind);
+    ctx.insert("id", &id);
+    ctx.insert("folder", query.folder.as_deref().unwrap_or(&state.config.default_data_folder));
+    ctx.insert("radius", &options.radius);
+    ctx.insert("max_nodes", &options.max_nodes);
+    ctx.insert("collapse_hubs", &query.collapse_hubs);
+    ctx.insert("hub_degree", &query.hub_degree.unwrap_or(DEFAULT_EGO_HUB_DEGREE));
+    ctx.insert("node_count", &ego.nodes.len());
+    ctx.insert("edge_count", &ego.edges.len());
+    ctx.insert("hubs", &ego.hubs);
+    ctx.insert("truncated", &ego.truncated);
+
+    match Tera::one_off(&template_content, &ctx, true) {
+        Ok(html) => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(html),
+        Err(err) => redirect_to_error_page(&format!("Ошибка рендеринга шаблона: {:?}", err)),
     }
 }
 
@@ -185,179 +256,59 @@ async fn index(_req: HttpRequest) -> HttpResponse {
     }
 }
 
-#[derive(Debug)]
-struct Transaction {
-    tx_id: String,
-    class: String,
-    features: Vec<f64>,
-}
-
-/// Формирует путь к файлу данных
-///
-/// # Arguments
-/// * `folder_name` - Имя папки с данными
-/// * `file_name` - Имя файла
+/// Страница управления папками с данными
 ///
 /// # Returns
-/// `PathBuf` - Полный путь к файлу
-fn get_data_path(folder_name: &str, file_name: &str) -> PathBuf {
-    let config = Config::from_env();
-    let mut path = env::current_dir()
-        .unwrap_or_else(|_| PathBuf::from("."));
-    path.push(&config.data_dir);
-    path.push(folder_name);
-    path.push(file_name);
-    path
-}
-
-#[derive(Debug, Deserialize)]
-struct AddrTx {
-    input_address: String,
-    txId: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct TxAddr {
-    txId: String,
-    output_address: String,
-}
-
-#[derive(Debug, Serialize)]
-struct NormalNode {
-    fill: String
-}
-
-#[derive(Debug, Serialize)]
-struct Node {
-    id: String,
-    normal: NormalNode
-}
-
-#[derive(Debug, Serialize)]
-struct Edge {
-    from: String,
-    to: String,
-    id: String,
-    normal: NormalEdge
-}
-
-#[derive(Debug, Serialize)]
-struct NormalEdge {
-    stroke: StrokeEdge,
-}
-
-#[derive(Debug, Serialize)]
-struct StrokeEdge {
-    color: String
-}
-
-fn plot_graph(folder_name: &str) -> Result<Value, std::io::Error> {
-    let mut nodes_set = HashSet::new();
-    let mut edges = Vec::new();
-    let mut tx_map: HashMap<String, String> = HashMap::new();
-    let mut tx_classes: HashMap<String, String> = HashMap::new(); // Хранение классов транзакций
-
-    // Читаем elliptic_txs_classes.csv
-    let path = get_data_path(folder_name, "elliptic_txs_classes.csv");
-    if path.exists() {
-        let mut rdr = Reader::from_path(&path)?;
-        for result in rdr.records() {
-            let record = result?;
-            let tx_id = record.get(0).unwrap_or("").to_string();
-            let class = record.get(1).unwrap_or("").to_string();
-            tx_classes.insert(tx_id, class);
-        }
-    } else {
-        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("File {:?} not found", path)));
-    }
-
-    let mut node_classes: HashMap<String, String> = HashMap::new(); // Хранение классов узлов
-
-    // Читаем wallet_features_classes_combined.csv
-    let path = get_data_path(folder_name, "wallets_features_classes_combined.csv");
-    if path.exists() {
-        let mut rdr = Reader::from_path(&path)?;
-        for result in rdr.records() {
-            let record = result?;
-            let address = record.get(0).unwrap_or("").to_string();
-            let class = record.get(2).unwrap_or("").to_string(); // Третий столбец
-            node_classes.insert(address, class);
-        }
-    } else {
-        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("File {:?} not found", path)));
-    }
-
-    // Читаем AddrTx_edgelist.csv
-    let path = get_data_path(folder_name,"AddrTx_edgelist.csv");
-    if !path.exists() {
-        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("File {:?} not found", path)));
-    }
-    let mut rdr = Reader::from_path(&path)?;
-    let mut records = rdr.deserialize::<AddrTx>();
-    while let Some(record) = records.next() {
-        let record = record?;
-        nodes_set.insert(record.input_address.clone());
-        tx_map.insert(record.txId.clone(), record.input_address);
-    }
-
-    // Читаем TxAddr_edgelist.csv
-    let path = get_data_path(folder_name,"TxAddr_edgelist.csv");
-    if !path.exists() {
-        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, format!("File {:?} not found", path)));
-    }
-    let mut rdr = Reader::from_path(&path)?;
-    let mut records = rdr.deserialize::<TxAddr>();
-    while let Some(record) = records.next() {
-        let record = record?;
-        nodes_set.insert(record.output_address.clone());
-        if let Some(from) = tx_map.get(&record.txId) {
-            let tx_class = tx_classes.get(&record.txId).map(|s| s.as_str()).unwrap_or("unknown");
-            let fill_color = if tx_class == "unknown" {
-                "#00FF00" // Зеленый
-            } else if tx_class == "2" {
-                "#FF0000" // Красный
-            } else {
-                "#CCCCCC" // Серый по умолчанию
-            };
+/// HTML со списком папок `DATA_DIR`, формой загрузки архива и удалением
+#[get("/datasets")]
+async fn datasets_page(state: web::Data<AppState>) -> HttpResponse {
+    let data_dir = PathBuf::from(&state.config.data_dir);
+    let folders = match web::block(move || list_folders(&data_dir)).await {
+        Ok(Ok(folders)) => folders,
+        Ok(Err(err)) => return redirect_to_error_page(&format!("Ошибка чтения папок с данными: {}", err)),
+        Err(err) => return redirect_to_error_page(&format!("Ошибка чтения папок с данными: {}", err)),
+    };
 
-            edges.push(Edge {
-                from: from.clone(),
-                to: record.output_address,
-                id: record.txId,
-                normal: NormalEdge { stroke: StrokeEdge { color: String::from(fill_color) }},
-            });
-        }
+    let template_content = match read_to_string(Path::new("static/datasets.html")).await {
+        Ok(content) => content,
+        Err(err) => return redirect_to_error_page(&format!("Ошибка загрузки шаблона: {:?}", err)),
+    };
+    let mut ctx = Context::new();
+    ctx.insert("folders", &folders);
+    ctx.insert("default_folder", &state.config.default_data_folder);
+
+    match Tera::one_off(&template_content, &ctx, true) {
+        Ok(html) => HttpResponse::Ok()
+            .content_type("text/html; charset=utf-8")
+            .body(html),
+        Err(err) => redirect_to_error_page(&format!("Ошибка рендеринга шаблона: {:?}", err)),
     }
+}
 
-    // Формируем список узлов
-    let nodes: Vec<Node> = nodes_set.into_iter().map(|id| {
-        let node_class = node_classes.get(&id).map(|s| s.as_str()).unwrap_or("unknown");
-        let fill_color = if node_class == "3" {
-            "#00FF00" // Зеленый
-        } else if node_class == "2" {
-            "#CCCCCC" // Серый
-        } else {
-            "#FFFFFF" // Обычный белый
-        };
-
-        Node { id, normal: NormalNode { fill: String::from(fill_color) } }
-    }).collect();
-
-    // Создаём объект графа
-    Ok(json!({ "nodes": nodes, "edges": edges }))
+/// Значение поля формы в виде варианта перечисления; неизвестное значение —
+/// вариант по умолчанию
+fn form_choice<T: DeserializeOwned + Default>(form: &HashMap<String, String>, field: &str) -> T {
+    form.get(field)
+        .and_then(|value| serde_json::from_value(serde_json::Value::from(value.as_str())).ok())
+        .unwrap_or_default()
 }
 
 #[post("/confirm")]
-async fn confirm_file(form: web::Form<HashMap<String, String>>) -> impl Responder {
+async fn confirm_file(
+    state: web::Data<AppState>,
+    form: web::Form<HashMap<String, String>>
+) -> impl Responder {
     // Получаем название папки
     let folder_name = match form.get("inputText") {
         Some(name) => name,
         None => return redirect_to_error_page("Поле inputText отсутствует"),
     };
 
-    // Проверка длины
-    if folder_name.len() < 14 || folder_name.len() > 74 {
-        return redirect_to_error_page("Название папки должно содержать от 14 до 74 символов");
+    if !is_safe_folder_name(folder_name) {
+        return redirect_to_error_page("Недопустимое название папки");
+    }
+    if !Path::new(&state.config.data_dir).join(folder_name).is_dir() {
+        return redirect_to_error_page(&format!("Папка с данными {} не найдена", folder_name));
     }
 
     // Загружаем template для анализа
@@ -368,21 +319,52 @@ async fn confirm_file(form: web::Form<HashMap<String, String>>) -> impl Responde
     };
 
     // Генерация данных графа (AnyChart)
-    let graph_data = match plot_graph(folder_name) {
-        Ok(data) => data,
+    // colorBy (cluster, component, community) раскрашивает адреса по группам,
+    // sizeBy (degree, betweenness, pagerank) задает размер узлов,
+    // pathFrom и pathTo выделяют кратчайшие пути между адресами
+    let folder_path = Path::new(&state.config.data_dir).join(folder_name);
+    let color_by: NodeColoring = form_choice(&form, "colorBy");
+    let size_by: NodeSizing = form_choice(&form, "sizeBy");
+    let path_from = form.get("pathFrom").map(|address| address.trim().to_string()).unwrap_or_default();
+    let path_to = form.get("pathTo").map(|address| address.trim().to_string()).unwrap_or_default();
+    let path_mode: PathMode = form_choice(&form, "pathMode");
+    let clustering = ClusteringOptions::from_config(&state.config);
+
+    // Загрузка графа, метрики и поиск путей выполняются в пуле блокирующих задач
+    let (source, target) = (path_from.clone(), path_to.clone());
+    let graphs = state.graphs.clone();
+    let analysis = web::block(move || {
+        let graph = graphs.load(&folder_path)?;
+        let mut graph_data = address_graph_data(&graph);
+        style_nodes(&graph, &mut graph_data, color_by, size_by, &clustering);
+        let paths = if source.is_empty() || target.is_empty() {
+            Vec::new()
+        } else {
+            k_shortest_paths(&graph, &source, &target, PathOptions { mode: path_mode, ..PathOptions::default() })
+        };
+        highlight_paths(&mut graph_data, &paths);
+        Ok::<_, SchemaError>((graph_data, paths))
+    })
+    .await;
+    let (graph_data, paths) = match analysis {
+        Ok(Ok(analysis)) => analysis,
+        Ok(Err(err)) => return redirect_to_error_page(&format!("Ошибка генерации графа: {:?}", err)),
         Err(err) => return redirect_to_error_page(&format!("Ошибка генерации графа: {:?}", err)),
     };
 
     // Создаем контекст для шаблона
     let mut graph_context = Context::new();
-    if let Some(nodes) = graph_data.get("nodes") {
-        let nodes_json = serde_json::to_string(nodes).unwrap_or_else(|_| "[]".to_string());
-        graph_context.insert("nodes", &nodes_json);
-    }
-    if let Some(edges) = graph_data.get("edges") {
-        let edges_json = serde_json::to_string(edges).unwrap_or_else(|_| "[]".to_string());
-        graph_context.insert("edges", &edges_json);
-    }
+    let nodes_json = serde_json::to_string(&graph_data.nodes).unwrap_or_else(|_| "[]".to_string());
+    graph_context.insert("nodes", &nodes_json);
+    let edges_json = serde_json::to_string(&graph_data.edges).unwrap_or_else(|_| "[]".to_string());
+    graph_context.insert("edges", &edges_json);
+    graph_context.insert("folder", folder_name);
+    graph_context.insert("color_by", &color_by);
+    graph_context.insert("size_by", &size_by);
+    graph_context.insert("path_from", &path_from);
+    graph_context.insert("path_to", &path_to);
+    graph_context.insert("path_mode", &path_mode);
+    graph_context.insert("paths", &paths);
 
     // Рендерим граф в шаблоне
     let graph_rendered = match Tera::one_off(&analysis_template_content, &graph_context, true) {
@@ -422,16 +404,43 @@ async fn main() -> Result<(), std::io::Error> {
     std::env::set_var("RUST_LOG", "debug");
     env_logger::init();
 
+    let config = Config::from_env()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    let explorers = ExplorerRegistry::from_config(&config)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    let ml = MLClient::from_config(&config)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+    let datasets = DatasetStore::new();
+    let default_folder = Path::new(&config.data_dir).join(&config.default_data_folder);
+    if default_folder.is_dir() {
+        let started = std::time::Instant::now();
+        match datasets.load_folder(&default_folder) {
+            Ok(files) => println!("Indexed {} dataset files in {:?}", files, started.elapsed()),
+            Err(e) => eprintln!("Warning: Failed to index {}: {}", default_folder.display(), e),
+        }
+    }
+    let state = web::Data::new(AppState { config, explorers, ml, datasets, graphs: Arc::default() });
+    println!(
+        "Explorer clients: {} (default: {})",
+        state.explorers.names().collect::<Vec<_>>().join(", "),
+        state.config.default_explorer
+    );
+
     let available_port: Option<u16> = find_available_port(START_PORT, END_PORT).await;
 
     match available_port {
         Some(port) => {
             println!("Found available port: {}", port);
-            let server = HttpServer::new(|| App::new()
+            let server = HttpServer::new(move || App::new()
+                .app_data(state.clone())
+                .configure(api_v1::configure)
                 .service(index)
+                .service(datasets_page)
                 .service(confirm_file)
                 .service(get_transaction)
+                .service(analyze_transaction)
                 .service(get_address)
+                .service(ego_page)
             )
                 .bind(("127.0.0.1", port))?
                 .workers(1)
@@ -1,30 +1,138 @@
-mod application;
-mod infrastructure;
-mod services;
+use std::path::Path;
 
-use services::explorer::explorer_client::get_or_init_client;
-use application::services::transaction::transaction_info::get_transaction_info;
-use infrastructure::config::Config;
+use diploma_software::services::explorer::cache::ExplorerCache;
+use diploma_software::services::explorer::explorer_registry::ExplorerRegistry;
+use diploma_software::application::services::clustering::address_clustering::ClusteringOptions;
+use diploma_software::application::services::export::graph_export::{export_folder, export_trace, ExportFormat, ExportGraph};
+use diploma_software::application::services::tracing::taint_tracing::{trace_taint, TraceOptions, TraceStart};
+use diploma_software::application::services::transaction::transaction_info::TransactionFeatures;
+use diploma_software::application::services::scoring::gnn_scoring::score_elliptic_folder;
+use diploma_software::infrastructure::config::Config;
+use diploma_software::infrastructure::model::aml_gnn::AmlGnn;
+
+/// Оценивает папку с данными локальной моделью и печатает самые рискованные транзакции
+///
+/// Работает полностью офлайн: нужны только CSV файлы и `aml_bitcoin.pth`.
+fn score_folder(config: &Config, folder_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let model = AmlGnn::load(&config.aml_model_path)?;
+    let folder = Path::new(&config.data_dir).join(folder_name);
+
+    let mut scores = score_elliptic_folder(&model, &folder)?;
+    scores.sort_by(|a, b| b.illicit_probability.total_cmp(&a.illicit_probability));
+
+    println!("Scored {} transactions in {:?}", scores.len(), folder);
+    for score in scores.iter().take(20) {
+        println!("  {}  illicit probability: {:.4}", score.tx_id, score.illicit_probability);
+    }
+    Ok(())
+}
+
+/// Печатает статистику или очищает дисковый кэш explorer'а
+///
+/// `cache stats` - число записей и размер, `cache purge` - удалить все записи,
+/// `cache purge --expired` - удалить только устаревшие. Команда выполняется
+/// для кэша каждого клиента explorer'а.
+fn manage_cache(config: &Config, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    if config.explorer_cache_dir.is_none() {
+        println!("Explorer cache is disabled (EXPLORER_CACHE_DIR is empty)");
+        return Ok(());
+    }
+
+    for explorer in &config.explorers {
+        let Some(cache) = ExplorerCache::from_config(config, &explorer.name) else {
+            continue;
+        };
+        match args.first().map(String::as_str) {
+            Some("stats") | None => {
+                let stats = cache.stats()?;
+                println!("Explorer cache for {} at {:?}", explorer.name, cache.root());
+                println!("  Transactions: {}", stats.transactions);
+                println!("  Outspends: {}", stats.outspends);
+                println!("  Expired: {}", stats.expired);
+                println!("  Size: {} bytes", stats.total_bytes);
+            }
+            Some("purge") => {
+                let only_expired = args.iter().any(|arg| arg == "--expired");
+                let removed = cache.purge(only_expired)?;
+                println!("Removed {} entries from {:?}", removed, cache.root());
+            }
+            Some(other) => return Err(format!("unknown cache command: {}", other).into()),
+        }
+    }
+    Ok(())
+}
+
+/// Записывает граф в файл или, если файл не задан, в стандартный вывод
+fn write_export(graph: &ExportGraph, format: ExportFormat, output: Option<&String>) -> Result<(), Box<dyn std::error::Error>> {
+    let content = graph.render(format);
+    match output {
+        Some(output) => {
+            std::fs::write(output, content)?;
+            eprintln!("Exported {} nodes and {} edges to {}", graph.nodes.len(), graph.edges.len(), output);
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Экспортирует граф папки с данными: `export <folder> [format] [output]`
+///
+/// Формат - `graphml` (по умолчанию), `gexf`, `dot` или `cytoscape`.
+fn export_dataset(config: &Config, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let folder = Path::new(&config.data_dir).join(args.first().unwrap_or(&config.default_data_folder));
+    let format = args.get(1).map(|format| format.parse::<ExportFormat>()).transpose()?.unwrap_or_default();
+
+    let graph = export_folder(&folder, &ClusteringOptions::from_config(config))?;
+    write_export(&graph, format, args.get(2))
+}
+
+/// Экспортирует подграф трассировки средств: `export-trace <txid:vout|address> [format] [output]`
+///
+/// Трассировка идет вперед с параметрами по умолчанию через explorer по умолчанию.
+async fn export_traced(config: &Config, args: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let start = args.first().ok_or("usage: export-trace <txid:vout|address> [format] [output]")?;
+    let start = start.parse::<TraceStart>()?;
+    let format = args.get(1).map(|format| format.parse::<ExportFormat>()).transpose()?.unwrap_or_default();
+
+    let explorers = ExplorerRegistry::from_config(config)?;
+    let trace = trace_taint(explorers.default_client()?, &start, TraceOptions::default()).await?;
+    let graph = export_trace(&trace, &ClusteringOptions::from_config(config));
+    write_export(&graph, format, args.get(2))
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Инициализируем клиент explorer
-    get_or_init_client().await?;
-    
     // Получаем конфигурацию
-    let config = Config::from_env();
-    
+    let config = Config::from_env()?;
+
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("score") => {
+            let folder = args.get(2).unwrap_or(&config.default_data_folder);
+            return score_folder(&config, folder);
+        }
+        Some("cache") => return manage_cache(&config, &args[2..]),
+        Some("export") => return export_dataset(&config, &args[2..]),
+        Some("export-trace") => return export_traced(&config, &args[2..]).await,
+        _ => {}
+    }
+
+    // Создаем клиенты explorer по конфигурации
+    let explorers = ExplorerRegistry::from_config(&config)?;
+    let client = explorers.default_client()?;
+
     // Получаем информацию о транзакции
-    let features = get_transaction_info(&config.test_tx_id).await?;
-    
+    let tx = client.get_transaction(&config.test_tx_id).await?;
+    let features = TransactionFeatures::from_transaction(&tx)?;
+
     println!("Transaction Features:");
     println!("  Inputs: {}", features.n_inputs);
     println!("  Outputs: {}", features.n_outputs);
-    println!("  Input Value Sum: {} BTC", features.input_value_sum);
-    println!("  Output Value Sum: {} BTC", features.output_value_sum);
-    println!("  Transaction Fee: {} BTC", features.transaction_fee);
+    println!("  Input Value Sum: {} BTC", features.input_value_sum.to_btc());
+    println!("  Output Value Sum: {} BTC", features.output_value_sum.to_btc());
+    println!("  Transaction Fee: {} BTC", features.transaction_fee.to_btc());
     println!("  Avg Input Value: {} BTC", features.avg_input_value);
     println!("  Avg Output Value: {} BTC", features.avg_output_value);
-    
+
     Ok(())
-}
\ No newline at end of file
+}
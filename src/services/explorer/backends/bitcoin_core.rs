@@ -0,0 +1,406 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use bitcoin::{Amount, ScriptBuf};
+use bitcoincore_rpc_async::{Auth, Client, RpcApi};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::OnceCell;
+
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::models::chain::{
+    ChainAddressStats, ChainInput, ChainOutput, ChainOutspend, ChainStatus, ChainTransaction,
+};
+use crate::services::explorer::request_policy::RequestPolicy;
+use super::chain_backend::ChainBackend;
+
+const BACKEND_NAME: &str = "bitcoin-core";
+
+/// Транзакция из `getrawtransaction` с `verbosity` 1 или 2
+#[derive(Debug, Deserialize)]
+struct RpcTransaction {
+    txid: String,
+    vin: Vec<RpcVin>,
+    vout: Vec<RpcVout>,
+    #[serde(default)]
+    blockhash: Option<String>,
+    #[serde(default)]
+    blocktime: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcVin {
+    #[serde(default)]
+    txid: Option<String>,
+    #[serde(default)]
+    vout: Option<u32>,
+    #[serde(default)]
+    coinbase: Option<String>,
+    /// Появляется только при `verbosity` 2 (Bitcoin Core 25+)
+    #[serde(default)]
+    prevout: Option<RpcPrevout>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcPrevout {
+    value: f64,
+    #[serde(rename = "scriptPubKey")]
+    script_pub_key: RpcScriptPubKey,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcVout {
+    value: f64,
+    #[serde(rename = "scriptPubKey")]
+    script_pub_key: RpcScriptPubKey,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcScriptPubKey {
+    #[serde(default)]
+    hex: String,
+    #[serde(default)]
+    address: Option<String>,
+    /// Старые версии Bitcoin Core отдают список адресов
+    #[serde(default)]
+    addresses: Option<Vec<String>>,
+}
+
+impl RpcScriptPubKey {
+    fn address(&self) -> Option<String> {
+        self.address
+            .clone()
+            .or_else(|| self.addresses.as_ref().and_then(|a| a.first().cloned()))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcBlockHeader {
+    height: u64,
+}
+
+/// Переводит сумму из BTC (формат RPC) в сатоши
+fn btc_to_sat(value: f64) -> Result<u64, ExplorerError> {
+    Amount::from_btc(value)
+        .map(|amount| amount.to_sat())
+        .map_err(|e| ExplorerError::InvalidResponse(format!("invalid amount {}: {}", value, e)))
+}
+
+fn to_chain_output(value: f64, script: &RpcScriptPubKey) -> Result<ChainOutput, ExplorerError> {
+    Ok(ChainOutput {
+        script_pubkey: script.hex.clone(),
+        address: script.address(),
+        value: btc_to_sat(value)?,
+    })
+}
+
+/// Является ли выход непотрачиваемым (OP_RETURN)
+fn is_unspendable(script: &RpcScriptPubKey) -> bool {
+    ScriptBuf::from_hex(&script.hex).is_ok_and(|script| script.is_op_return())
+}
+
+/// Приводит транзакцию RPC к нормализованной модели
+///
+/// # Arguments
+/// * `tx` - Транзакция из `getrawtransaction`
+/// * `parents` - Родительские транзакции входов без `prevout` (узлы до версии 25)
+/// * `block_height` - Высота блока подтвержденной транзакции
+///
+/// # Returns
+/// `Result<ChainTransaction, ExplorerError>` - транзакция или ошибка разбора ответа
+fn normalize_transaction(
+    tx: RpcTransaction,
+    parents: &HashMap<String, RpcTransaction>,
+    block_height: Option<u64>,
+) -> Result<ChainTransaction, ExplorerError> {
+    let mut inputs = Vec::with_capacity(tx.vin.len());
+    for vin in &tx.vin {
+        if vin.coinbase.is_some() {
+            inputs.push(ChainInput {
+                txid: "0".repeat(64),
+                vout: u32::MAX,
+                prevout: None,
+                is_coinbase: true,
+            });
+            continue;
+        }
+
+        let (Some(parent_txid), Some(vout)) = (vin.txid.clone(), vin.vout) else {
+            return Err(ExplorerError::InvalidResponse(format!("input without outpoint in {}", tx.txid)));
+        };
+        let prevout = match &vin.prevout {
+            Some(prevout) => to_chain_output(prevout.value, &prevout.script_pub_key)?,
+            None => {
+                let spent = parents
+                    .get(&parent_txid)
+                    .and_then(|parent| parent.vout.get(vout as usize))
+                    .ok_or(ExplorerError::MissingInputValue)?;
+                to_chain_output(spent.value, &spent.script_pub_key)?
+            }
+        };
+        inputs.push(ChainInput {
+            txid: parent_txid,
+            vout,
+            prevout: Some(prevout),
+            is_coinbase: false,
+        });
+    }
+
+    let outputs = tx
+        .vout
+        .iter()
+        .map(|vout| to_chain_output(vout.value, &vout.script_pub_key))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut normalized = ChainTransaction {
+        txid: tx.txid,
+        inputs,
+        outputs,
+        fee: None,
+        status: ChainStatus {
+            confirmed: block_height.is_some(),
+            block_height,
+            block_time: tx.blocktime,
+        },
+    };
+    normalized.fee = normalized.computed_fee().map(Amount::to_sat);
+    Ok(normalized)
+}
+
+/// Backend для узла Bitcoin Core через JSON-RPC
+///
+/// Требует `txindex=1`. Индекса адресов и трат в Bitcoin Core нет, поэтому
+/// запросы по адресам не поддерживаются, а для выходов известен только
+/// факт траты (через `gettxout`), но не расходующая транзакция.
+pub struct BitcoinCoreBackend {
+    url: String,
+    auth: Auth,
+    client: OnceCell<Client>,
+    policy: RequestPolicy,
+}
+
+impl BitcoinCoreBackend {
+    /// Создает backend для узла Bitcoin Core
+    ///
+    /// # Arguments
+    /// * `url` - URL JSON-RPC интерфейса (например, `http://127.0.0.1:8332`)
+    /// * `auth` - Способ аутентификации (логин/пароль или cookie файл)
+    pub fn new(url: impl Into<String>, auth: Auth) -> Self {
+        BitcoinCoreBackend {
+            url: url.into(),
+            auth,
+            client: OnceCell::new(),
+            policy: RequestPolicy::default(),
+        }
+    }
+
+    /// Задает политику повторов и ограничения частоты запросов
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Возвращает RPC клиент, создавая его при первом обращении
+    async fn client(&self) -> Result<&Client, ExplorerError> {
+        self.client
+            .get_or_try_init(|| Client::new(self.url.clone(), self.auth.clone()))
+            .await
+            .map_err(backend_error)
+    }
+
+    /// Выполняет RPC вызов с учетом политики запросов
+    async fn call<T: DeserializeOwned>(&self, method: &str, args: &[Value]) -> Result<T, ExplorerError> {
+        let client = self.client().await?;
+        self.policy
+            .execute(|| async { client.call(method, args).await.map_err(backend_error) })
+            .await
+    }
+}
+
+/// Оборачивает ошибку крейта `bitcoincore-rpc-async`
+///
+/// Код RPC `-5` (`RPC_INVALID_ADDRESS_OR_KEY`) означает, что транзакция
+/// или блок не найдены, ошибки транспорта — что узел недоступен.
+fn backend_error(error: bitcoincore_rpc_async::Error) -> ExplorerError {
+    use bitcoincore_rpc_async::jsonrpc::error::Error as JsonRpcError;
+
+    match &error {
+        bitcoincore_rpc_async::Error::JsonRpc(JsonRpcError::Rpc(rpc)) if rpc.code == -5 => {
+            ExplorerError::NotFound(rpc.message.clone())
+        }
+        bitcoincore_rpc_async::Error::JsonRpc(JsonRpcError::Transport(_)) => {
+            ExplorerError::UpstreamUnavailable(error.to_string())
+        }
+        _ => ExplorerError::Backend {
+            backend: BACKEND_NAME,
+            message: error.to_string(),
+        },
+    }
+}
+
+#[async_trait]
+impl ChainBackend for BitcoinCoreBackend {
+    fn name(&self) -> &'static str {
+        BACKEND_NAME
+    }
+
+    /// Запрашивает транзакцию с `verbosity` 2; если узел не вернул `prevout`
+    /// (версии до 25), расходуемые выходы берутся из родительских транзакций
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+        let tx: RpcTransaction = self.call("getrawtransaction", &[json!(txid), json!(2)]).await?;
+
+        let mut parents: HashMap<String, RpcTransaction> = HashMap::new();
+        for vin in tx.vin.iter().filter(|vin| vin.coinbase.is_none() && vin.prevout.is_none()) {
+            if let Some(Entry::Vacant(entry)) = vin.txid.clone().map(|parent| parents.entry(parent)) {
+                let parent: RpcTransaction = self
+                    .call("getrawtransaction", &[json!(entry.key()), json!(1)])
+                    .await?;
+                entry.insert(parent);
+            }
+        }
+
+        let block_height = match &tx.blockhash {
+            Some(hash) => {
+                let header: RpcBlockHeader = self.call("getblockheader", &[json!(hash)]).await?;
+                Some(header.height)
+            }
+            None => None,
+        };
+
+        normalize_transaction(tx, &parents, block_height)
+    }
+
+    /// Bitcoin Core знает только, остался ли выход в наборе UTXO
+    /// (`gettxout`), но не расходующую транзакцию. Поэтому статусы
+    /// возвращаются, только если ни один расходуемый выход не потрачен, иначе
+    /// `Unsupported`: вызывающий код отличает «нет данных» от «не потрачен».
+    /// Непотрачиваемые выходы (OP_RETURN) в наборе UTXO не хранятся и
+    /// считаются непотраченными.
+    async fn get_outspends(&self, txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+        let tx: RpcTransaction = self.call("getrawtransaction", &[json!(txid), json!(1)]).await?;
+
+        let mut outspends = Vec::with_capacity(tx.vout.len());
+        for (n, vout) in tx.vout.iter().enumerate() {
+            if !is_unspendable(&vout.script_pub_key) {
+                let utxo: Option<Value> = self
+                    .call("gettxout", &[json!(txid), json!(n), json!(true)])
+                    .await?;
+                if utxo.is_none() {
+                    return Err(ExplorerError::Unsupported {
+                        backend: BACKEND_NAME,
+                        operation: "spending transactions",
+                    });
+                }
+            }
+            outspends.push(ChainOutspend {
+                spent: false,
+                txid: None,
+                vin: None,
+                status: None,
+            });
+        }
+        Ok(outspends)
+    }
+
+    async fn get_address_stats(&self, _address: &str) -> Result<ChainAddressStats, ExplorerError> {
+        Err(ExplorerError::Unsupported {
+            backend: BACKEND_NAME,
+            operation: "address statistics",
+        })
+    }
+
+    async fn get_address_transactions(
+        &self,
+        _address: &str,
+        _max_pages: usize,
+    ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+        Err(ExplorerError::Unsupported {
+            backend: BACKEND_NAME,
+            operation: "address history",
+        })
+    }
+
+    async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+        self.call("getblockcount", &[]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRST_P2P_TX: &str = "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16";
+    const BLOCK_1_COINBASE: &str = "0e3e2357e806b6cdb1f70b54c3a3a17b6714ee1f0e68bebb44a74b1efd512098";
+
+    fn fixture(txid: &str) -> RpcTransaction {
+        let path = format!("{}/tests/fixtures/bitcoin_core/{}.json", env!("CARGO_MANIFEST_DIR"), txid);
+        let json = std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Fixture {} should exist: {}", path, e));
+        serde_json::from_str(&json).expect("Fixture should parse")
+    }
+
+    #[test]
+    fn test_normalize_fixtures() {
+        let tx = normalize_transaction(fixture(FIRST_P2P_TX), &HashMap::new(), Some(170)).expect("Valid transaction");
+        let prevout = tx.inputs[0].prevout.clone().expect("Prevout");
+        assert_eq!((prevout.value, prevout.address), (5_000_000_000, None));
+        assert!(prevout.script_pubkey.starts_with("410411db93e1"));
+        let values: Vec<u64> = tx.outputs.iter().map(|output| output.value).collect();
+        assert_eq!(values, [1_000_000_000, 4_000_000_000]);
+        assert_eq!(tx.fee, Some(0));
+        assert_eq!((tx.status.confirmed, tx.status.block_time), (true, Some(1_231_731_025)));
+
+        // Узел до версии 25: расходуемый выход берется из родительской транзакции
+        let legacy = || {
+            let mut tx = fixture(FIRST_P2P_TX);
+            tx.vin[0].prevout = None;
+            tx
+        };
+        assert!(matches!(
+            normalize_transaction(legacy(), &HashMap::new(), None),
+            Err(ExplorerError::MissingInputValue)
+        ));
+        let parent: RpcTransaction = serde_json::from_value(json!({
+            "txid": tx.inputs[0].txid,
+            "vin": [{"coinbase": "04ffff001d0134"}],
+            "vout": [{"value": 50.0, "scriptPubKey": {"hex": prevout.script_pubkey}}]
+        }))
+        .expect("Parent should parse");
+        let parents = HashMap::from([(tx.inputs[0].txid.clone(), parent)]);
+        let legacy = normalize_transaction(legacy(), &parents, Some(170)).expect("Valid transaction");
+        assert_eq!(legacy.inputs, tx.inputs);
+
+        let coinbase = normalize_transaction(fixture(BLOCK_1_COINBASE), &HashMap::new(), Some(1)).expect("Valid coinbase");
+        assert!(coinbase.inputs[0].is_coinbase);
+        assert_eq!((coinbase.inputs[0].prevout.clone(), coinbase.fee), (None, None));
+    }
+
+    #[test]
+    fn test_op_return_is_unspendable() {
+        let script = |hex: &str| RpcScriptPubKey { hex: hex.to_string(), address: None, addresses: None };
+        assert!(is_unspendable(&script("6a24aa21a9ede2f61c3f71d1defd3fa999dfa36953755c690689799962b48bebd836974e8cf9")));
+        assert!(!is_unspendable(&script("0014751e76e8199196d454941c45d1b3a323f1433bd6")));
+    }
+
+    #[test]
+    fn test_parse_verbose_transaction() {
+        let raw = r#"{
+            "txid": "ab",
+            "vin": [
+                {"txid": "p1", "vout": 1, "prevout": {"value": 0.1, "scriptPubKey": {"hex": "0014aa", "address": "bc1qa"}}},
+                {"coinbase": "03abcd"}
+            ],
+            "vout": [{"value": 0.09999, "n": 0, "scriptPubKey": {"hex": "76a9", "addresses": ["1B"]}}],
+            "blockhash": "00ff",
+            "blocktime": 1500000000
+        }"#;
+        let tx: RpcTransaction = serde_json::from_str(raw).expect("Should parse");
+
+        assert_eq!(tx.vin[0].prevout.as_ref().map(|p| p.script_pub_key.address()), Some(Some("bc1qa".to_string())));
+        assert!(tx.vin[1].coinbase.is_some());
+        assert_eq!(tx.vout[0].script_pub_key.address().as_deref(), Some("1B"));
+        assert_eq!(btc_to_sat(tx.vout[0].value).expect("Valid amount"), 9_999_000);
+        assert_eq!(btc_to_sat(0.1).expect("Valid amount"), 10_000_000);
+    }
+}
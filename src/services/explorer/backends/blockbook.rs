@@ -0,0 +1,449 @@
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use reqwest::StatusCode;
+use serde::{Deserialize, Deserializer};
+use tokio::sync::{OnceCell, Semaphore};
+
+use crate::infrastructure::constants::{ADDRESS_TXS_CONCURRENCY, ADDRESS_TXS_PAGE_SIZE, MAX_ADDRESS_HISTORY_PAGES};
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::models::chain::{
+    ChainAddressStats, ChainInput, ChainOutput, ChainOutspend, ChainStatus, ChainTransaction,
+};
+use crate::services::explorer::request_policy::RequestPolicy;
+use super::chain_backend::ChainBackend;
+use super::esplora::{check_status, transport_error};
+
+const BACKEND_NAME: &str = "blockbook";
+
+/// Транзакция из `GET /api/v2/tx/{txid}`
+///
+/// Модель крейта `blockbook` требует `txid` у каждого входа, а у coinbase
+/// входа Blockbook его не отдает, поэтому транзакции разбираются своей моделью.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookTransaction {
+    txid: String,
+    vin: Vec<BlockbookVin>,
+    vout: Vec<BlockbookVout>,
+    /// `-1` у неподтвержденной транзакции
+    #[serde(default)]
+    block_height: Option<i64>,
+    #[serde(default)]
+    block_time: Option<u64>,
+    #[serde(default, deserialize_with = "sats")]
+    fees: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookVin {
+    #[serde(default)]
+    txid: Option<String>,
+    /// Blockbook опускает поле `vout`, если оно равно нулю
+    #[serde(default)]
+    vout: u32,
+    #[serde(default)]
+    coinbase: Option<String>,
+    #[serde(default)]
+    addresses: Vec<String>,
+    #[serde(default, deserialize_with = "sats")]
+    value: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockbookVout {
+    #[serde(default, deserialize_with = "sats")]
+    value: u64,
+    #[serde(default)]
+    spent: Option<bool>,
+    #[serde(default)]
+    spent_tx_id: Option<String>,
+    #[serde(default)]
+    spent_index: Option<u32>,
+    #[serde(default)]
+    spent_height: Option<u64>,
+    #[serde(default)]
+    hex: String,
+    #[serde(default)]
+    addresses: Vec<String>,
+}
+
+/// Разбирает сумму в сатоши, которую Blockbook передает строкой
+fn sats<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+}
+
+/// Backend для Blockbook API (Trezor)
+///
+/// Транзакции запрашиваются напрямую (`/api/v2/tx/{txid}`), история адресов
+/// и высота цепочки — через крейт `blockbook`. Клиент `blockbook` при
+/// создании проверяет версию сервера, поэтому подключение откладывается до
+/// первого запроса.
+pub struct BlockbookBackend {
+    base_url: url::Url,
+    http: reqwest::Client,
+    client: OnceCell<blockbook::Client>,
+    policy: RequestPolicy,
+}
+
+impl BlockbookBackend {
+    /// Создает backend для Blockbook сервера
+    ///
+    /// # Arguments
+    /// * `base_url` - URL сервера без фрагмента `/api/v2`
+    ///
+    /// # Returns
+    /// `Result<Self, ExplorerError>` - backend или ошибка разбора URL
+    pub fn new(base_url: &str) -> Result<Self, ExplorerError> {
+        let base_url = url::Url::parse(base_url)
+            .map_err(|e| ExplorerError::InvalidArgument(format!("invalid Blockbook URL {}: {}", base_url, e)))?;
+        let http = reqwest::Client::builder()
+            .build()
+            .map_err(|e| ExplorerError::ClientBuildError(e.to_string()))?;
+
+        Ok(BlockbookBackend {
+            base_url,
+            http,
+            client: OnceCell::new(),
+            policy: RequestPolicy::default(),
+        })
+    }
+
+    /// Задает политику повторов и ограничения частоты запросов
+    pub fn with_policy(mut self, policy: RequestPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Возвращает клиент, подключаясь к серверу при первом обращении
+    async fn client(&self) -> Result<&blockbook::Client, ExplorerError> {
+        self.client
+            .get_or_try_init(|| async {
+                self.policy
+                    .execute(|| async { blockbook::Client::new(self.base_url.clone()).await.map_err(backend_error) })
+                    .await
+            })
+            .await
+    }
+
+    /// Получает число транзакций адреса и его историю
+    ///
+    /// Постраничные запросы крейта `blockbook` не являются `Send`, поэтому
+    /// берется список txid из `address_info`, а транзакции загружаются
+    /// параллельно, не больше `ADDRESS_TXS_CONCURRENCY` одновременно
+    /// (не больше `max_pages` страниц по `ADDRESS_TXS_PAGE_SIZE`).
+    ///
+    /// # Returns
+    /// `Result<(u64, Vec<ChainTransaction>), ExplorerError>` - полное число транзакций
+    /// адреса и загруженные транзакции в порядке Blockbook
+    async fn address_history(
+        &self,
+        address: &str,
+        max_pages: usize,
+    ) -> Result<(u64, Vec<ChainTransaction>), ExplorerError> {
+        let client = self.client().await?;
+        let address = parse_address(address)?;
+        let info = self
+            .policy
+            .execute(|| async { client.address_info(&address).await.map_err(backend_error) })
+            .await?;
+
+        let limit = max_pages.saturating_mul(ADDRESS_TXS_PAGE_SIZE);
+        let txids = info.txids.unwrap_or_default();
+        if txids.len() > limit {
+            log::warn!("address history of {} truncated at {} of {} transactions", address, limit, txids.len());
+        }
+        let permits = Semaphore::new(ADDRESS_TXS_CONCURRENCY);
+        let txs = try_join_all(txids.iter().take(limit).map(|txid| async {
+            let _permit = permits
+                .acquire()
+                .await
+                .map_err(|e| ExplorerError::InvalidResponse(e.to_string()))?;
+            self.transaction(&txid.to_string()).await.map(normalize_transaction)
+        }))
+        .await?;
+
+        Ok((u64::from(info.basic.txs), txs))
+    }
+
+    /// Получает транзакцию с учетом политики запросов
+    async fn transaction(&self, txid: &str) -> Result<BlockbookTransaction, ExplorerError> {
+        let path = format!("/api/v2/tx/{}", parse_txid(txid)?);
+        let url = self
+            .base_url
+            .join(&path)
+            .map_err(|e| ExplorerError::InvalidArgument(format!("invalid Blockbook URL: {}", e)))?;
+        self.policy
+            .execute(|| async {
+                let resp = self.http.get(url.clone()).send().await.map_err(transport_error)?;
+                Ok(check_status(resp, &path)?.json().await?)
+            })
+            .await
+    }
+}
+
+/// Оборачивает ошибку крейта `blockbook`, выделяя 404, 429 и недоступность сервера
+fn backend_error(error: blockbook::Error) -> ExplorerError {
+    if let blockbook::Error::RequestError(request_error) = &error {
+        if request_error.is_timeout() || request_error.is_connect() {
+            return ExplorerError::UpstreamUnavailable(error.to_string());
+        }
+        match request_error.status() {
+            Some(StatusCode::NOT_FOUND) => return ExplorerError::NotFound(error.to_string()),
+            Some(StatusCode::TOO_MANY_REQUESTS) => return ExplorerError::RateLimited { retry_after: None },
+            Some(status) if status.is_server_error() => {
+                return ExplorerError::UpstreamUnavailable(error.to_string())
+            }
+            _ => {}
+        }
+    }
+    ExplorerError::Backend {
+        backend: BACKEND_NAME,
+        message: error.to_string(),
+    }
+}
+
+fn parse_txid(txid: &str) -> Result<blockbook::Txid, ExplorerError> {
+    blockbook::Txid::from_str(txid)
+        .map_err(|e| ExplorerError::InvalidArgument(format!("invalid txid {}: {}", txid, e)))
+}
+
+fn parse_address(address: &str) -> Result<blockbook::Address, ExplorerError> {
+    address
+        .parse::<blockbook::Address<blockbook::NetworkUnchecked>>()
+        .map(|address| address.assume_checked())
+        .map_err(|e| ExplorerError::InvalidArgument(format!("invalid address {}: {}", address, e)))
+}
+
+/// Приводит транзакцию Blockbook к нормализованной модели
+///
+/// Blockbook не отдает скрипт расходуемого выхода, поэтому у `prevout`
+/// заполняются только адрес и сумма. У coinbase входа (поле `coinbase`
+/// без `txid`) расходуемого выхода нет.
+fn normalize_transaction(tx: BlockbookTransaction) -> ChainTransaction {
+    let inputs = tx
+        .vin
+        .into_iter()
+        .map(|vin| match vin.txid {
+            Some(txid) if vin.coinbase.is_none() => ChainInput {
+                txid,
+                vout: vin.vout,
+                prevout: Some(ChainOutput {
+                    script_pubkey: String::new(),
+                    address: vin.addresses.into_iter().next(),
+                    value: vin.value,
+                }),
+                is_coinbase: false,
+            },
+            _ => ChainInput {
+                txid: "0".repeat(64),
+                vout: u32::MAX,
+                prevout: None,
+                is_coinbase: true,
+            },
+        })
+        .collect();
+    let outputs = tx
+        .vout
+        .into_iter()
+        .map(|vout| ChainOutput {
+            script_pubkey: vout.hex,
+            address: vout.addresses.into_iter().next(),
+            value: vout.value,
+        })
+        .collect();
+    let block_height = tx.block_height.and_then(|height| u64::try_from(height).ok());
+
+    ChainTransaction {
+        txid: tx.txid,
+        inputs,
+        outputs,
+        fee: Some(tx.fees),
+        status: ChainStatus {
+            confirmed: block_height.is_some(),
+            block_height,
+            block_time: tx.block_time.filter(|_| block_height.is_some()),
+        },
+    }
+}
+
+#[async_trait]
+impl ChainBackend for BlockbookBackend {
+    fn name(&self) -> &'static str {
+        BACKEND_NAME
+    }
+
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+        Ok(normalize_transaction(self.transaction(txid).await?))
+    }
+
+    async fn get_outspends(&self, txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+        let tx = self.transaction(txid).await?;
+
+        Ok(tx
+            .vout
+            .into_iter()
+            .map(|vout| ChainOutspend {
+                spent: vout.spent.unwrap_or(false),
+                txid: vout.spent_tx_id,
+                vin: vout.spent_index,
+                status: vout.spent_height.map(|height| ChainStatus {
+                    confirmed: true,
+                    block_height: Some(height),
+                    block_time: None,
+                }),
+            })
+            .collect())
+    }
+
+    /// Blockbook не отдает счетчики выходов, поэтому они подсчитываются
+    /// по истории адреса (не глубже `MAX_ADDRESS_HISTORY_PAGES`), а число
+    /// транзакций берется из `address_info` целиком
+    async fn get_address_stats(&self, address: &str) -> Result<ChainAddressStats, ExplorerError> {
+        let (tx_count, history) = self.address_history(address, MAX_ADDRESS_HISTORY_PAGES).await?;
+        Ok(ChainAddressStats {
+            tx_count,
+            ..ChainAddressStats::from_history(address, &history)
+        })
+    }
+
+    async fn get_address_transactions(
+        &self,
+        address: &str,
+        max_pages: usize,
+    ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+        Ok(self.address_history(address, max_pages).await?.1)
+    }
+
+    async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+        let client = self.client().await?;
+        let status = self
+            .policy
+            .execute(|| async { client.status().await.map_err(backend_error) })
+            .await?;
+        Ok(status.blockbook.best_height.to_consensus_u32() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use serde_json::json;
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const FIRST_P2P_TX: &str = "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16";
+    const BLOCK_1_COINBASE: &str = "0e3e2357e806b6cdb1f70b54c3a3a17b6714ee1f0e68bebb44a74b1efd512098";
+
+    const SENDER: &str = "12cbQLTFMXRnSzktFkuoG3eHoMeFtpTu3S";
+    const HISTORY_LEN: usize = 20;
+
+    fn fixture_json(txid: &str) -> String {
+        let path = format!("{}/tests/fixtures/blockbook/{}.json", env!("CARGO_MANIFEST_DIR"), txid);
+        std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Fixture {} should exist: {}", path, e))
+    }
+
+    fn fixture(txid: &str) -> BlockbookTransaction {
+        serde_json::from_str(&fixture_json(txid)).expect("Fixture should parse")
+    }
+
+    async fn status() -> HttpResponse {
+        let time = "2024-01-01T00:00:00Z";
+        HttpResponse::Ok().json(json!({
+            "blockbook": {
+                "coin": "Bitcoin", "host": "mock", "version": "0.4.0", "gitCommit": "mock",
+                "buildTime": time, "syncMode": true, "initialSync": false, "inSync": true,
+                "bestHeight": 840_000, "lastBlockTime": time, "inSyncMempool": true,
+                "lastMempoolTime": time, "mempoolSize": 0, "decimals": 8, "dbSize": 0, "about": "mock",
+                "hasFiatRates": false, "currentFiatRatesTime": time, "historicalFiatRatesTime": time
+            },
+            "backend": {
+                "chain": "main", "blocks": 840_000, "headers": 840_000,
+                "bestBlockHash": "0000000000000000000320283a032748cef8227873ff4872689bf23f1cda83a5",
+                "difficulty": "0", "sizeOnDisk": 0, "version": "270000",
+                "subversion": "/Satoshi:27.0.0/", "protocolVersion": "70016"
+            }
+        }))
+    }
+
+    /// История `SENDER` — `HISTORY_LEN` раз транзакция блока 170 из полной истории в 120 транзакций
+    async fn address_info() -> HttpResponse {
+        HttpResponse::Ok().json(json!({
+            "page": 1, "totalPages": 1, "itemsOnPage": 1000,
+            "address": SENDER, "balance": "0", "totalReceived": "0", "totalSent": "0",
+            "unconfirmedBalance": "0", "unconfirmedTxs": 0, "txs": 120,
+            "txids": vec![FIRST_P2P_TX; HISTORY_LEN]
+        }))
+    }
+
+    /// Отдает транзакцию с задержкой и запоминает наибольшее число одновременных запросов
+    async fn transaction(in_flight: web::Data<(AtomicUsize, AtomicUsize)>) -> HttpResponse {
+        let (current, peak) = in_flight.get_ref();
+        peak.fetch_max(current.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        current.fetch_sub(1, Ordering::SeqCst);
+        HttpResponse::Ok().content_type("application/json").body(fixture_json(FIRST_P2P_TX))
+    }
+
+    fn start_mock_blockbook(in_flight: web::Data<(AtomicUsize, AtomicUsize)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!("http://{}", listener.local_addr().expect("Should have address"));
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(in_flight.clone())
+                .route("/api/v2", web::get().to(status))
+                .route("/api/v2/address/{address}", web::get().to(address_info))
+                .route("/api/v2/tx/{txid}", web::get().to(transaction))
+        })
+        .listen(listener)
+        .expect("Should listen")
+        .workers(1)
+        .run();
+        actix_web::rt::spawn(server);
+        url
+    }
+
+    #[actix_web::test]
+    async fn test_address_stats_fetch_history_concurrently() {
+        let in_flight = web::Data::from(Arc::new((AtomicUsize::new(0), AtomicUsize::new(0))));
+        let backend = BlockbookBackend::new(&start_mock_blockbook(in_flight.clone())).expect("Should build");
+
+        let stats = backend.get_address_stats(SENDER).await.expect("Should load stats");
+        assert_eq!(stats.tx_count, 120);
+        assert_eq!((stats.funded_txo_count, stats.spent_txo_count), (HISTORY_LEN as u64, HISTORY_LEN as u64));
+
+        let peak = in_flight.1.load(Ordering::SeqCst);
+        assert!(peak > 1 && peak <= ADDRESS_TXS_CONCURRENCY, "peak concurrency {}", peak);
+    }
+
+    #[test]
+    fn test_normalize_fixtures() {
+        let tx = normalize_transaction(fixture(FIRST_P2P_TX));
+        let input = &tx.inputs[0];
+        assert_eq!((input.txid.as_str(), input.vout, input.is_coinbase), ("0437cd7f8525ceed2324359c2d0ba26006d92d856a9c20fa0241106ee5a597c9", 0, false));
+        let prevout = input.prevout.as_ref().expect("Prevout");
+        assert_eq!((prevout.address.as_deref(), prevout.value), (Some("12cbQLTFMXRnSzktFkuoG3eHoMeFtpTu3S"), 5_000_000_000));
+        let values: Vec<u64> = tx.outputs.iter().map(|output| output.value).collect();
+        assert_eq!(values, [1_000_000_000, 4_000_000_000]);
+        assert_eq!(tx.outputs[0].address.as_deref(), Some("1Q2TWHE3GMdB6BZKafqwxXtWAWgFt5Jvm3"));
+        assert!(tx.outputs[0].script_pubkey.starts_with("4104ae1a62fe"));
+        assert_eq!(tx.fee, Some(0));
+        assert_eq!((tx.status.block_height, tx.status.block_time), (Some(170), Some(1_231_731_025)));
+
+        let coinbase = normalize_transaction(fixture(BLOCK_1_COINBASE));
+        assert!(coinbase.inputs[0].is_coinbase);
+        assert_eq!(coinbase.inputs[0].prevout, None);
+        assert_eq!(coinbase.outputs[0].address.as_deref(), Some("12c6DSiU4Rq3P4ZxziKxzrGPqKc9f9wPcr"));
+
+        let mut unconfirmed = fixture(FIRST_P2P_TX);
+        unconfirmed.block_height = Some(-1);
+        let status = normalize_transaction(unconfirmed).status;
+        assert_eq!((status.confirmed, status.block_height, status.block_time), (false, None, None));
+    }
+}
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::models::chain::{
+    ChainAddressStats, ChainOutspend, ChainTransaction,
+};
+
+/// Источник данных блокчейна, скрытый за [`ExplorerClient`](crate::services::explorer::explorer_client::ExplorerClient)
+///
+/// Каждая реализация приводит ответы своего API к нормализованной модели
+/// из [`models::chain`](crate::services::explorer::models::chain). Операции,
+/// которые источник выполнить не может (например, поиск по адресу в Bitcoin Core
+/// без индекса адресов), возвращают [`ExplorerError::Unsupported`].
+#[async_trait]
+pub trait ChainBackend: Send + Sync {
+    /// Короткое имя backend'а для логов и сообщений об ошибках
+    fn name(&self) -> &'static str;
+
+    /// Получает транзакцию по её ID
+    ///
+    /// # Arguments
+    /// * `txid` - ID транзакции в формате hex string
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError>;
+
+    /// Получает статусы трат всех выходов транзакции
+    ///
+    /// # Arguments
+    /// * `txid` - ID транзакции в формате hex string
+    async fn get_outspends(&self, txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError>;
+
+    /// Получает статистику адреса (число полученных и потраченных выходов)
+    ///
+    /// # Arguments
+    /// * `address` - Bitcoin адрес
+    async fn get_address_stats(&self, address: &str) -> Result<ChainAddressStats, ExplorerError>;
+
+    /// Получает историю транзакций адреса
+    ///
+    /// История длиннее `max_pages` страниц обрезается с предупреждением в логе.
+    ///
+    /// # Arguments
+    /// * `address` - Bitcoin адрес
+    /// * `max_pages` - Максимальное число страниц истории
+    async fn get_address_transactions(
+        &self,
+        address: &str,
+        max_pages: usize,
+    ) -> Result<Vec<ChainTransaction>, ExplorerError>;
+
+    /// Получает высоту последнего блока
+    async fn get_tip_height(&self) -> Result<u64, ExplorerError>;
+}
+
+/// Backend без индекса адресов и сведений о тратах, как Bitcoin Core
+///
+/// Отдает транзакцию `b0714235…` из `tests/fixtures/esplora`; остальные txid
+/// считаются ее родителями, подтвержденными за сутки до нее.
+#[cfg(test)]
+pub(crate) struct UnindexedFixtureBackend;
+
+#[cfg(test)]
+#[async_trait]
+impl ChainBackend for UnindexedFixtureBackend {
+    fn name(&self) -> &'static str {
+        "unindexed"
+    }
+
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+        use crate::services::explorer::models::esplora::{fixture_transaction, SEGWIT_TX};
+
+        let mut tx: ChainTransaction = fixture_transaction(SEGWIT_TX).into();
+        if txid != SEGWIT_TX {
+            tx.txid = txid.to_string();
+            tx.status.block_time = tx.status.block_time.map(|time| time - 24 * 60 * 60);
+        }
+        Ok(tx)
+    }
+
+    async fn get_outspends(&self, _txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+        Err(ExplorerError::Unsupported { backend: "unindexed", operation: "spending transactions" })
+    }
+
+    async fn get_address_stats(&self, _address: &str) -> Result<ChainAddressStats, ExplorerError> {
+        Err(ExplorerError::Unsupported { backend: "unindexed", operation: "address statistics" })
+    }
+
+    async fn get_address_transactions(
+        &self,
+        _address: &str,
+        _max_pages: usize,
+    ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+        Err(ExplorerError::Unsupported { backend: "unindexed", operation: "address history" })
+    }
+
+    async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+        Ok(765_200)
+    }
+}
@@ -0,0 +1,286 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, Proxy, Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+use crate::infrastructure::config::ExplorerConfig;
+use crate::infrastructure::constants::{ADDRESS_TXS_PAGE_SIZE, DEFAULT_EXPLORER_NAME};
+use crate::services::explorer::errors::explorer_errors::ExplorerError;
+use crate::services::explorer::models::chain::{
+    ChainAddressStats, ChainOutspend, ChainTransaction,
+};
+use crate::services::explorer::models::esplora::{
+    EsploraAddressStats, EsploraOutspend, EsploraTransaction,
+};
+use crate::services::explorer::request_policy::{parse_retry_after, RequestPolicy};
+use super::chain_backend::ChainBackend;
+
+/// Backend для Esplora-совместимых API (mempool.space, blockstream.info)
+pub struct EsploraBackend {
+    http: Client,
+    base_url: String,
+    policy: RequestPolicy,
+}
+
+impl EsploraBackend {
+    /// Создает backend для Esplora API
+    ///
+    /// # Arguments
+    /// * `base_url` - Базовый URL API (например, `https://mempool.space/api`)
+    ///
+    /// # Returns
+    /// `Result<Self, ExplorerError>` - backend или ошибка создания HTTP клиента
+    pub fn new(base_url: impl Into<String>) -> Result<Self, ExplorerError> {
+        Self::from_config(&ExplorerConfig::new(DEFAULT_EXPLORER_NAME, base_url))
+    }
+
+    /// Создает backend по настройкам клиента explorer'а
+    ///
+    /// Применяются таймаут, прокси, User-Agent, заголовок авторизации,
+    /// повторы и ограничение частоты запросов.
+    ///
+    /// # Arguments
+    /// * `config` - Настройки клиента
+    pub fn from_config(config: &ExplorerConfig) -> Result<Self, ExplorerError> {
+        let build_error = |e: &dyn std::fmt::Display| ExplorerError::ClientBuildError(e.to_string());
+
+        let mut headers = HeaderMap::new();
+        if let Some((name, value)) = &config.auth_header {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| build_error(&e))?;
+            let mut value = HeaderValue::from_str(value).map_err(|e| build_error(&e))?;
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+
+        let mut builder = Client::builder()
+            .pool_max_idle_per_host(8)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(config.user_agent.as_str())
+            .default_headers(headers);
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(Proxy::all(proxy.as_str()).map_err(|e| build_error(&e))?);
+        }
+        let http = builder.build().map_err(|e| build_error(&e))?;
+
+        Ok(EsploraBackend {
+            http,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            policy: RequestPolicy::from_config(config),
+        })
+    }
+
+    /// Выполняет GET запрос с повторами и возвращает успешный ответ
+    async fn get(&self, path: &str) -> Result<Response, ExplorerError> {
+        let url = format!("{}{}", self.base_url, path);
+        self.policy
+            .execute(|| async {
+                let resp = self.http.get(&url).send().await.map_err(transport_error)?;
+                check_status(resp, path)
+            })
+            .await
+    }
+
+    /// Выполняет GET запрос к explorer'у и десериализует JSON ответ
+    async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, ExplorerError> {
+        Ok(self.get(path).await?.json().await?)
+    }
+
+    /// Получает транзакцию в исходном формате Esplora
+    ///
+    /// # Arguments
+    /// * `txid` - ID транзакции в формате hex string
+    pub async fn get_esplora_transaction(&self, txid: &str) -> Result<EsploraTransaction, ExplorerError> {
+        self.get_json(&format!("/tx/{}", txid)).await
+    }
+}
+
+/// Сетевые ошибки (таймаут, отказ соединения) означают недоступность explorer'а
+pub(super) fn transport_error(error: reqwest::Error) -> ExplorerError {
+    if error.is_timeout() || error.is_connect() {
+        ExplorerError::UpstreamUnavailable(error.to_string())
+    } else {
+        ExplorerError::Http(error)
+    }
+}
+
+/// Переводит HTTP статус ответа в ошибку explorer'а
+pub(super) fn check_status(resp: Response, path: &str) -> Result<Response, ExplorerError> {
+    let status = resp.status();
+    if status == StatusCode::NOT_FOUND {
+        Err(ExplorerError::NotFound(path.to_string()))
+    } else if status == StatusCode::TOO_MANY_REQUESTS {
+        Err(ExplorerError::RateLimited {
+            retry_after: parse_retry_after(resp.headers()),
+        })
+    } else if status.is_server_error() {
+        Err(ExplorerError::UpstreamUnavailable(format!("{} for {}", status, path)))
+    } else {
+        Ok(resp.error_for_status()?)
+    }
+}
+
+#[async_trait]
+impl ChainBackend for EsploraBackend {
+    fn name(&self) -> &'static str {
+        "esplora"
+    }
+
+    async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+        Ok(self.get_esplora_transaction(txid).await?.into())
+    }
+
+    async fn get_outspends(&self, txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+        let outspends: Vec<EsploraOutspend> = self.get_json(&format!("/tx/{}/outspends", txid)).await?;
+        Ok(outspends.into_iter().map(ChainOutspend::from).collect())
+    }
+
+    async fn get_address_stats(&self, address: &str) -> Result<ChainAddressStats, ExplorerError> {
+        let stats: EsploraAddressStats = self.get_json(&format!("/address/{}", address)).await?;
+        Ok(stats.into())
+    }
+
+    /// Esplora отдает сначала mempool, затем подтвержденные транзакции страницами
+    /// по 25 штук; следующие страницы запрашиваются по ID последней полученной
+    /// транзакции, пока страницы полные.
+    async fn get_address_transactions(
+        &self,
+        address: &str,
+        max_pages: usize,
+    ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+        let mut txs: Vec<EsploraTransaction> = self
+            .get_json(&format!("/address/{}/txs", address))
+            .await?;
+
+        // Неполная страница подтвержденных транзакций — последняя
+        let mut page_full = txs.iter().filter(|tx| tx.status.confirmed).count() >= ADDRESS_TXS_PAGE_SIZE;
+        let mut pages = 1;
+        while page_full {
+            if pages >= max_pages {
+                log::warn!("address history of {} truncated at {} pages", address, max_pages);
+                break;
+            }
+            let Some(last_txid) = txs.iter().rev().find(|tx| tx.status.confirmed).map(|tx| tx.txid.clone()) else {
+                break;
+            };
+            let page: Vec<EsploraTransaction> = self
+                .get_json(&format!("/address/{}/txs/chain/{}", address, last_txid))
+                .await?;
+            pages += 1;
+            page_full = page.len() >= ADDRESS_TXS_PAGE_SIZE;
+            txs.extend(page);
+        }
+
+        Ok(txs.into_iter().map(ChainTransaction::from).collect())
+    }
+
+    async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+        let body = self.get("/blocks/tip/height").await?.text().await?;
+
+        body.trim()
+            .parse()
+            .map_err(|_| ExplorerError::InvalidResponse(format!("invalid tip height: {}", body)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::models::esplora::{fixture_transaction, SEGWIT_TX};
+    use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    /// Первый запрос получает 429, второй — 503, третий — высоту блока
+    async fn flaky_tip(calls: web::Data<Arc<AtomicU32>>) -> HttpResponse {
+        match calls.fetch_add(1, Ordering::SeqCst) {
+            0 => HttpResponse::TooManyRequests().insert_header(("Retry-After", "0")).finish(),
+            1 => HttpResponse::ServiceUnavailable().finish(),
+            _ => HttpResponse::Ok().body("840000"),
+        }
+    }
+
+    /// История адреса `short` — 3 подтвержденные транзакции, у остальных
+    /// адресов каждая страница полная
+    async fn address_history(calls: web::Data<Arc<AtomicU32>>, req: HttpRequest) -> HttpResponse {
+        let call = calls.fetch_add(1, Ordering::SeqCst);
+        let size = if req.match_info().get("address") == Some("short") { 3 } else { ADDRESS_TXS_PAGE_SIZE };
+        let page: Vec<EsploraTransaction> = (0..size)
+            .map(|i| {
+                let mut tx = fixture_transaction(SEGWIT_TX);
+                tx.txid = format!("{:032x}{:032x}", call, i);
+                tx
+            })
+            .collect();
+        HttpResponse::Ok().json(page)
+    }
+
+    fn start_mock_explorer(calls: Arc<AtomicU32>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!("http://{}", listener.local_addr().expect("Should have address"));
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(web::Data::new(calls.clone()))
+                .route("/blocks/tip/height", web::get().to(flaky_tip))
+                .route("/address/{address}/txs", web::get().to(address_history))
+                .route("/address/{address}/txs/chain/{last}", web::get().to(address_history))
+        })
+        .listen(listener)
+        .expect("Should listen")
+        .workers(1)
+        .run();
+        actix_web::rt::spawn(server);
+        url
+    }
+
+    fn fast_config(url: &str, max_retries: u32) -> ExplorerConfig {
+        let mut config = ExplorerConfig::new("test", url);
+        config.max_retries = max_retries;
+        config.retry_base_delay_ms = 1;
+        config.retry_max_delay_ms = 50;
+        config
+    }
+
+    #[actix_web::test]
+    async fn test_retries_rate_limited_and_unavailable_responses() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let url = start_mock_explorer(calls.clone());
+
+        let backend = EsploraBackend::from_config(&fast_config(&url, 3)).expect("Should build");
+        assert_eq!(backend.get_tip_height().await.expect("Should succeed after retries"), 840_000);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[actix_web::test]
+    async fn test_error_kinds_without_retries() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let url = start_mock_explorer(calls.clone());
+        let backend = EsploraBackend::from_config(&fast_config(&url, 0)).expect("Should build");
+
+        assert!(matches!(
+            backend.get_tip_height().await,
+            Err(ExplorerError::RateLimited { retry_after: Some(d) }) if d.is_zero()
+        ));
+        assert!(matches!(backend.get_tip_height().await, Err(ExplorerError::UpstreamUnavailable(_))));
+        assert!(matches!(
+            backend.get_transaction("00").await,
+            Err(ExplorerError::NotFound(path)) if path == "/tx/00"
+        ));
+    }
+
+    #[actix_web::test]
+    async fn test_address_history_stops_on_short_page_and_cap() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let url = start_mock_explorer(calls.clone());
+        let backend = EsploraBackend::from_config(&fast_config(&url, 0)).expect("Should build");
+
+        let history = backend.get_address_transactions("short", 40).await.expect("Should load");
+        assert_eq!((history.len(), calls.load(Ordering::SeqCst)), (3, 1));
+
+        calls.store(0, Ordering::SeqCst);
+        let history = backend.get_address_transactions("long", 3).await.expect("Should load");
+        assert_eq!((history.len(), calls.load(Ordering::SeqCst)), (3 * ADDRESS_TXS_PAGE_SIZE, 3));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod chain_backend;
+pub mod esplora;
+pub mod blockbook;
+pub mod bitcoin_core;
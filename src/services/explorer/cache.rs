@@ -0,0 +1,435 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::infrastructure::config::Config;
+
+/// Вид данных, хранимых в кэше explorer'а
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    /// Транзакция ([`ChainTransaction`](super::models::chain::ChainTransaction))
+    Transaction,
+    /// Статусы трат выходов транзакции
+    Outspends,
+}
+
+impl CacheKind {
+    const ALL: [CacheKind; 2] = [CacheKind::Transaction, CacheKind::Outspends];
+
+    fn dir_name(self) -> &'static str {
+        match self {
+            CacheKind::Transaction => "tx",
+            CacheKind::Outspends => "outspends",
+        }
+    }
+}
+
+/// Результат поиска в кэше
+#[derive(Debug, PartialEq)]
+pub enum CacheLookup<T> {
+    /// Запись есть и ее TTL не истек
+    Fresh(T),
+    /// Запись есть, но устарела; используется, только если explorer недоступен
+    Stale(T),
+    /// Записи нет
+    Missing,
+}
+
+/// Статистика кэша
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheStats {
+    /// Число сохраненных транзакций
+    pub transactions: u64,
+    /// Число сохраненных списков трат выходов
+    pub outspends: u64,
+    /// Число устаревших записей
+    pub expired: u64,
+    /// Суммарный размер записей в байтах
+    pub total_bytes: u64,
+    /// Попадания в кэш с момента запуска процесса
+    pub hits: u64,
+    /// Промахи кэша с момента запуска процесса
+    pub misses: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    /// Время сохранения (секунды Unix)
+    stored_at: u64,
+    /// Относится ли запись к подтвержденным данным
+    confirmed: bool,
+    data: T,
+}
+
+/// Заголовок записи без данных, нужен для статистики и очистки
+#[derive(Deserialize)]
+struct CacheEntryHeader {
+    stored_at: u64,
+    confirmed: bool,
+}
+
+/// Локальный кэш ответов explorer'а на диске
+///
+/// Записи адресуются по txid: `<root>/<вид>/<первые 2 символа>/<txid>.json`.
+/// Backend'ы возвращают данные разной полноты (у Blockbook нет скриптов
+/// prevout, у Bitcoin Core — тратящих транзакций), поэтому у каждого клиента
+/// explorer'а своя директория `<EXPLORER_CACHE_DIR>/<имя клиента>`.
+/// Подтвержденные данные почти не меняются, поэтому по умолчанию хранятся
+/// бессрочно (`confirmed_ttl = None`), а данные из mempool устаревают
+/// через `mempool_ttl`. Ошибки чтения и записи не прерывают запрос:
+/// кэш в этом случае просто не используется.
+pub struct ExplorerCache {
+    root: PathBuf,
+    mempool_ttl: Duration,
+    confirmed_ttl: Option<Duration>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ExplorerCache {
+    /// Создает кэш в указанной директории
+    ///
+    /// # Arguments
+    /// * `root` - Директория кэша (создается при первой записи)
+    /// * `mempool_ttl` - Время жизни неподтвержденных данных
+    /// * `confirmed_ttl` - Время жизни подтвержденных данных (`None` - бессрочно)
+    pub fn new(root: impl Into<PathBuf>, mempool_ttl: Duration, confirmed_ttl: Option<Duration>) -> Self {
+        ExplorerCache {
+            root: root.into(),
+            mempool_ttl,
+            confirmed_ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Создает кэш клиента explorer'а по настройкам приложения
+    ///
+    /// # Arguments
+    /// * `config` - Конфигурация приложения
+    /// * `explorer` - Имя клиента explorer'а, задающее директорию кэша
+    ///
+    /// # Returns
+    /// `Option<Self>` - кэш или `None`, если он отключен (`EXPLORER_CACHE_DIR` пуст)
+    pub fn from_config(config: &Config, explorer: &str) -> Option<Self> {
+        let root = config.explorer_cache_dir.as_ref()?;
+        Some(Self::new(
+            Path::new(root).join(explorer),
+            Duration::from_secs(config.explorer_cache_mempool_ttl),
+            config.explorer_cache_confirmed_ttl.map(Duration::from_secs),
+        ))
+    }
+
+    /// Директория кэша
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Ищет запись в кэше
+    ///
+    /// # Arguments
+    /// * `kind` - Вид данных
+    /// * `txid` - ID транзакции
+    ///
+    /// # Returns
+    /// `CacheLookup<T>` - свежая, устаревшая или отсутствующая запись
+    pub fn get<T: DeserializeOwned>(&self, kind: CacheKind, txid: &str) -> CacheLookup<T> {
+        let lookup = self.read(kind, txid);
+        match lookup {
+            CacheLookup::Fresh(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            _ => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        lookup
+    }
+
+    /// Ищет запись в кэше, не блокируя поток асинхронного runtime
+    ///
+    /// Чтение файла выполняется в пуле `tokio::task::spawn_blocking`.
+    ///
+    /// # Arguments
+    /// * `kind` - Вид данных
+    /// * `txid` - ID транзакции
+    ///
+    /// # Returns
+    /// `CacheLookup<T>` - свежая, устаревшая или отсутствующая запись
+    pub async fn load<T>(self: &Arc<Self>, kind: CacheKind, txid: &str) -> CacheLookup<T>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let cache = Arc::clone(self);
+        let txid = txid.to_string();
+        tokio::task::spawn_blocking(move || cache.get(kind, &txid))
+            .await
+            .unwrap_or_else(|error| {
+                log::warn!("cache lookup task failed: {}", error);
+                CacheLookup::Missing
+            })
+    }
+
+    fn read<T: DeserializeOwned>(&self, kind: CacheKind, txid: &str) -> CacheLookup<T> {
+        let Some(path) = self.entry_path(kind, txid) else {
+            return CacheLookup::Missing;
+        };
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    log::warn!("failed to read cache entry {:?}: {}", path, error);
+                }
+                return CacheLookup::Missing;
+            }
+        };
+        match serde_json::from_slice::<CacheEntry<T>>(&bytes) {
+            Ok(entry) if self.is_expired(entry.stored_at, entry.confirmed, unix_now()) => {
+                CacheLookup::Stale(entry.data)
+            }
+            Ok(entry) => CacheLookup::Fresh(entry.data),
+            Err(error) => {
+                log::warn!("removing corrupted cache entry {:?}: {}", path, error);
+                let _ = fs::remove_file(&path);
+                CacheLookup::Missing
+            }
+        }
+    }
+
+    /// Сохраняет запись в кэш
+    ///
+    /// Запись сначала пишется во временный файл и затем переименовывается,
+    /// поэтому параллельные читатели не видят недописанный JSON.
+    ///
+    /// # Arguments
+    /// * `kind` - Вид данных
+    /// * `txid` - ID транзакции
+    /// * `data` - Данные
+    /// * `confirmed` - Подтверждены ли данные (определяет TTL)
+    pub fn put<T: Serialize>(&self, kind: CacheKind, txid: &str, data: &T, confirmed: bool) {
+        let Some(path) = self.entry_path(kind, txid) else {
+            return;
+        };
+        if let Err(error) = encode_entry(data, confirmed).and_then(|bytes| write_atomically(&path, &bytes)) {
+            log::warn!("failed to write cache entry {:?}: {}", path, error);
+        }
+    }
+
+    /// Сохраняет запись в кэш, не блокируя поток асинхронного runtime
+    ///
+    /// Данные сериализуются сразу, запись файла выполняется в пуле
+    /// `tokio::task::spawn_blocking`.
+    ///
+    /// # Arguments
+    /// * `kind` - Вид данных
+    /// * `txid` - ID транзакции
+    /// * `data` - Данные
+    /// * `confirmed` - Подтверждены ли данные (определяет TTL)
+    pub async fn store<T: Serialize>(self: &Arc<Self>, kind: CacheKind, txid: &str, data: &T, confirmed: bool) {
+        let Some(path) = self.entry_path(kind, txid) else {
+            return;
+        };
+        let bytes = match encode_entry(data, confirmed) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                log::warn!("failed to encode cache entry {:?}: {}", path, error);
+                return;
+            }
+        };
+        let written = tokio::task::spawn_blocking(move || {
+            write_atomically(&path, &bytes).map_err(|error| format!("{:?}: {}", path, error))
+        })
+        .await;
+        match written {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => log::warn!("failed to write cache entry {}", error),
+            Err(error) => log::warn!("cache write task failed: {}", error),
+        }
+    }
+
+    /// Собирает статистику кэша
+    pub fn stats(&self) -> io::Result<CacheStats> {
+        let now = unix_now();
+        let mut stats = CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            ..CacheStats::default()
+        };
+        for (kind, path) in self.entry_files()? {
+            match kind {
+                CacheKind::Transaction => stats.transactions += 1,
+                CacheKind::Outspends => stats.outspends += 1,
+            }
+            stats.total_bytes += fs::metadata(&path)?.len();
+            if self.is_file_expired(&path, now) {
+                stats.expired += 1;
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Удаляет записи из кэша
+    ///
+    /// # Arguments
+    /// * `only_expired` - Удалять только устаревшие (и поврежденные) записи
+    ///
+    /// # Returns
+    /// `io::Result<u64>` - число удаленных записей
+    pub fn purge(&self, only_expired: bool) -> io::Result<u64> {
+        let now = unix_now();
+        let mut removed = 0;
+        for (_, path) in self.entry_files()? {
+            if !only_expired || self.is_file_expired(&path, now) {
+                fs::remove_file(&path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    fn is_expired(&self, stored_at: u64, confirmed: bool, now: u64) -> bool {
+        let age = Duration::from_secs(now.saturating_sub(stored_at));
+        match (confirmed, self.confirmed_ttl) {
+            (true, Some(ttl)) => age >= ttl,
+            (true, None) => false,
+            (false, _) => age >= self.mempool_ttl,
+        }
+    }
+
+    /// Поврежденные записи считаются устаревшими
+    fn is_file_expired(&self, path: &Path, now: u64) -> bool {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheEntryHeader>(&bytes).ok())
+            .is_none_or(|header| self.is_expired(header.stored_at, header.confirmed, now))
+    }
+
+    /// Путь к записи; `None`, если ключ не похож на txid
+    fn entry_path(&self, kind: CacheKind, txid: &str) -> Option<PathBuf> {
+        if txid.len() != 64 || !txid.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        let txid = txid.to_ascii_lowercase();
+        Some(self.root.join(kind.dir_name()).join(&txid[..2]).join(format!("{}.json", txid)))
+    }
+
+    /// Все файлы записей (включая оставшиеся после сбоя временные файлы)
+    fn entry_files(&self) -> io::Result<Vec<(CacheKind, PathBuf)>> {
+        let mut files = Vec::new();
+        for kind in CacheKind::ALL {
+            for shard in read_dir_if_exists(&self.root.join(kind.dir_name()))? {
+                for file in read_dir_if_exists(&shard)? {
+                    if file.is_file() {
+                        files.push((kind, file));
+                    }
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+fn read_dir_if_exists(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    match fs::read_dir(dir) {
+        Ok(entries) => entries.map(|entry| entry.map(|entry| entry.path())).collect(),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+fn encode_entry<T: Serialize>(data: &T, confirmed: bool) -> io::Result<Vec<u8>> {
+    let entry = CacheEntry {
+        stored_at: unix_now(),
+        confirmed,
+        data,
+    };
+    Ok(serde_json::to_vec(&entry)?)
+}
+
+fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension(format!("{}.tmp", rand::random::<u32>()));
+    fs::write(&tmp, bytes)?;
+    fs::rename(&tmp, path).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp);
+    })
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TXID: &str = "d6176384de4c0b98702eccb97f3ad6670bc8410d9da715fe5b49462d3e603993";
+    const OTHER_TXID: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+
+    fn temp_cache(name: &str, mempool_ttl: Duration) -> ExplorerCache {
+        let root = std::env::temp_dir().join(format!("explorer_cache_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        ExplorerCache::new(root, mempool_ttl, None)
+    }
+
+    #[test]
+    fn test_put_get_stats_and_purge() {
+        let cache = temp_cache("roundtrip", Duration::from_secs(60));
+        assert_eq!(cache.get::<Vec<u64>>(CacheKind::Transaction, TXID), CacheLookup::Missing);
+
+        cache.put(CacheKind::Transaction, TXID, &vec![1u64, 2, 3], true);
+        cache.put(CacheKind::Outspends, TXID, &vec![true], false);
+        assert_eq!(cache.get(CacheKind::Transaction, TXID), CacheLookup::Fresh(vec![1u64, 2, 3]));
+
+        let stats = cache.stats().expect("Should collect stats");
+        assert_eq!((stats.transactions, stats.outspends, stats.expired), (1, 1, 0));
+        assert_eq!((stats.hits, stats.misses), (1, 1));
+        assert!(stats.total_bytes > 0);
+
+        assert_eq!(cache.purge(false).expect("Should purge"), 2);
+        assert_eq!(cache.stats().expect("Should collect stats").transactions, 0);
+        let _ = fs::remove_dir_all(cache.root());
+    }
+
+    #[test]
+    fn test_mempool_entries_expire_and_confirmed_do_not() {
+        let cache = temp_cache("ttl", Duration::ZERO);
+        cache.put(CacheKind::Transaction, TXID, &"confirmed", true);
+        cache.put(CacheKind::Transaction, OTHER_TXID, &"mempool", false);
+
+        assert_eq!(cache.get(CacheKind::Transaction, TXID), CacheLookup::Fresh("confirmed".to_string()));
+        assert_eq!(cache.get(CacheKind::Transaction, OTHER_TXID), CacheLookup::Stale("mempool".to_string()));
+
+        assert_eq!(cache.stats().expect("Should collect stats").expired, 1);
+        assert_eq!(cache.purge(true).expect("Should purge"), 1);
+        assert_eq!(cache.get::<String>(CacheKind::Transaction, OTHER_TXID), CacheLookup::Missing);
+        let _ = fs::remove_dir_all(cache.root());
+    }
+
+    #[tokio::test]
+    async fn test_async_store_and_load() {
+        let cache = Arc::new(temp_cache("async", Duration::from_secs(60)));
+        assert_eq!(cache.load::<Vec<u64>>(CacheKind::Outspends, TXID).await, CacheLookup::Missing);
+
+        cache.store(CacheKind::Outspends, TXID, &vec![7u64], true).await;
+        assert_eq!(cache.load(CacheKind::Outspends, TXID).await, CacheLookup::Fresh(vec![7u64]));
+        assert_eq!(cache.get(CacheKind::Outspends, TXID), CacheLookup::Fresh(vec![7u64]));
+        let _ = fs::remove_dir_all(cache.root());
+    }
+
+    #[test]
+    fn test_rejects_keys_that_are_not_txids() {
+        let cache = temp_cache("keys", Duration::from_secs(60));
+        cache.put(CacheKind::Transaction, "../../etc/passwd", &1u8, true);
+        assert!(cache.entry_path(CacheKind::Transaction, "../../etc/passwd").is_none());
+        assert_eq!(cache.entry_files().expect("Should list").len(), 0);
+        let _ = fs::remove_dir_all(cache.root());
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -12,4 +14,49 @@ pub enum ExplorerError {
     ClientNotInitialized,
     #[error("Failed to build HTTP client: {0}")]
     ClientBuildError(String),
-}
\ No newline at end of file
+    #[error("Invalid explorer response: {0}")]
+    InvalidResponse(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("{backend} backend error: {message}")]
+    Backend { backend: &'static str, message: String },
+    #[error("{backend} backend does not support {operation}")]
+    Unsupported { backend: &'static str, operation: &'static str },
+    #[error("Explorer rate limit exceeded")]
+    RateLimited { retry_after: Option<Duration> },
+    #[error("Not found: {0}")]
+    NotFound(String),
+    #[error("Explorer unavailable: {0}")]
+    UpstreamUnavailable(String),
+}
+
+impl ExplorerError {
+    /// Можно ли повторить запрос, завершившийся этой ошибкой
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ExplorerError::RateLimited { .. } | ExplorerError::UpstreamUnavailable(_))
+    }
+
+    /// Задержка из заголовка `Retry-After`, если explorer ее указал
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ExplorerError::RateLimited { retry_after } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+/// Отделяет неподдерживаемую backend'ом операцию от остальных ошибок
+///
+/// # Arguments
+/// * `result` - Результат запроса к explorer'у
+///
+/// # Returns
+/// `Result<Option<T>, ExplorerError>` - `Ok(None)` для [`ExplorerError::Unsupported`],
+/// иначе исходный результат
+pub fn supported<T>(result: Result<T, ExplorerError>) -> Result<Option<T>, ExplorerError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ExplorerError::Unsupported { .. }) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
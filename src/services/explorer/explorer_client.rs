@@ -1,17 +1,37 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use bitcoincore_rpc_async::Auth;
 use tokio::sync::OnceCell;
-use reqwest::Client;
 
+use crate::infrastructure::config::{Config, ExplorerBackendKind, ExplorerConfig};
 use crate::infrastructure::constants::BITCOIN_EXPLORER_URL;
+use super::backends::bitcoin_core::BitcoinCoreBackend;
+use super::backends::blockbook::BlockbookBackend;
+use super::backends::chain_backend::ChainBackend;
+use super::backends::esplora::EsploraBackend;
+use super::cache::{CacheKind, CacheLookup, ExplorerCache};
 use super::errors::explorer_errors::ExplorerError;
+use super::request_policy::RequestPolicy;
+use super::models::chain::{ChainAddressStats, ChainOutspend, ChainTransaction};
 
-/// Клиент для работы с Bitcoin Explorer API
+/// Клиент для работы с данными блокчейна
+///
+/// Источник данных задается реализацией [`ChainBackend`] (Esplora, Blockbook
+/// или Bitcoin Core), все методы возвращают нормализованную модель
+/// из [`models::chain`](super::models::chain).
+///
+/// Если подключен [`ExplorerCache`], транзакции и статусы трат выходов
+/// сначала ищутся на диске. Устаревшая запись возвращается, когда explorer
+/// недоступен, поэтому повторный анализ работает офлайн.
+#[derive(Clone)]
 pub struct ExplorerClient {
-    http: Client,
-    base_url: String,
+    backend: Arc<dyn ChainBackend>,
+    cache: Option<Arc<ExplorerCache>>,
 }
 
 impl ExplorerClient {
-    /// Создает новый клиент для работы с Bitcoin Explorer API
+    /// Создает клиент для Esplora-совместимого API
     ///
     /// # Arguments
     /// * `base_url` - Базовый URL API explorer'а
@@ -19,36 +39,196 @@ impl ExplorerClient {
     /// # Returns
     /// `Result<Self, ExplorerError>` - клиент или ошибка создания
     pub fn new(base_url: impl Into<String>) -> Result<Self, ExplorerError> {
-        let http = Client::builder()
-            .pool_max_idle_per_host(8)
-            .build()
-            .map_err(|e| ExplorerError::ClientBuildError(e.to_string()))?;
-        
-        Ok(ExplorerClient {
-            http,
-            base_url: base_url.into(),
-        })
+        Ok(Self::with_backend(EsploraBackend::new(base_url)?))
+    }
+
+    /// Создает клиент по настройкам из [`Config`](crate::infrastructure::config::Config)
+    ///
+    /// Повторы и ограничение частоты применяются ко всем backend'ам;
+    /// таймаут, прокси, User-Agent и заголовок авторизации — к Esplora.
+    /// Крейты `blockbook` и `bitcoincore-rpc-async` создают HTTP клиент сами,
+    /// поэтому для них используются только URL и, для Bitcoin Core, данные JSON-RPC
+    /// аутентификации. Клиент без URL (Blockbook без `{prefix}URL`) не создается.
+    ///
+    /// # Arguments
+    /// * `config` - Настройки клиента explorer'а
+    ///
+    /// # Returns
+    /// `Result<Self, ExplorerError>` - клиент или ошибка создания
+    pub fn from_config(config: &ExplorerConfig) -> Result<Self, ExplorerError> {
+        if config.base_url.trim().is_empty() {
+            return Err(ExplorerError::InvalidArgument(format!("explorer {} requires an explicit URL", config.name)));
+        }
+        match config.backend {
+            ExplorerBackendKind::Esplora => Ok(Self::with_backend(EsploraBackend::from_config(config)?)),
+            ExplorerBackendKind::Blockbook => Ok(Self::with_backend(
+                BlockbookBackend::new(&config.base_url)?.with_policy(RequestPolicy::from_config(config)),
+            )),
+            ExplorerBackendKind::BitcoinCore => {
+                let auth = match (&config.rpc_user, &config.rpc_password, &config.rpc_cookie_file) {
+                    (Some(user), password, _) => {
+                        Auth::UserPass(user.clone(), password.clone().unwrap_or_default())
+                    }
+                    (None, _, Some(cookie)) => Auth::CookieFile(cookie.into()),
+                    _ => Auth::None,
+                };
+                Ok(Self::with_backend(
+                    BitcoinCoreBackend::new(config.base_url.clone(), auth)
+                        .with_policy(RequestPolicy::from_config(config)),
+                ))
+            }
+        }
+    }
+
+    /// Создает клиент для Blockbook сервера
+    ///
+    /// # Arguments
+    /// * `base_url` - URL сервера без фрагмента `/api/v2`
+    pub fn blockbook(base_url: &str) -> Result<Self, ExplorerError> {
+        Ok(Self::with_backend(BlockbookBackend::new(base_url)?))
+    }
+
+    /// Создает клиент для узла Bitcoin Core (JSON-RPC)
+    ///
+    /// # Arguments
+    /// * `url` - URL JSON-RPC интерфейса
+    /// * `auth` - Способ аутентификации
+    pub fn bitcoin_core(url: impl Into<String>, auth: Auth) -> Self {
+        Self::with_backend(BitcoinCoreBackend::new(url, auth))
     }
-    
+
+    /// Создает клиент поверх произвольной реализации [`ChainBackend`]
+    ///
+    /// # Arguments
+    /// * `backend` - Источник данных блокчейна
+    pub fn with_backend(backend: impl ChainBackend + 'static) -> Self {
+        ExplorerClient {
+            backend: Arc::new(backend),
+            cache: None,
+        }
+    }
+
+    /// Подключает дисковый кэш ответов
+    ///
+    /// # Arguments
+    /// * `cache` - Кэш, может разделяться несколькими клиентами
+    pub fn with_cache(mut self, cache: Arc<ExplorerCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Подключенный дисковый кэш
+    pub fn cache(&self) -> Option<&ExplorerCache> {
+        self.cache.as_deref()
+    }
+
+    /// Имя используемого backend'а
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
     /// Получает информацию о транзакции по её ID
     ///
     /// # Arguments
     /// * `txid` - ID транзакции в формате hex string
     ///
     /// # Returns
-    /// `Result<serde_json::Value, ExplorerError>` - JSON данные транзакции или ошибка
-    pub async fn get_transaction(&self, txid: &str) -> Result<serde_json::Value, ExplorerError> {
-        let url = format!("{}/tx/{}", self.base_url, txid);
-        let resp = self
-            .http
-            .get(&url)
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
-
-        Ok(resp)
+    /// `Result<ChainTransaction, ExplorerError>` - транзакция или ошибка
+    pub async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+        self.cached(CacheKind::Transaction, txid, |tx: &ChainTransaction| tx.status.confirmed, || {
+            self.backend.get_transaction(txid)
+        })
+        .await
+    }
+
+    /// Получает статусы трат всех выходов транзакции
+    ///
+    /// # Arguments
+    /// * `txid` - ID транзакции в формате hex string
+    pub async fn get_outspends(&self, txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+        // Список окончателен, только когда все выходы потрачены в подтвержденных транзакциях
+        let is_final = |outspends: &Vec<ChainOutspend>| {
+            outspends.iter().all(|outspend| {
+                outspend.spent && outspend.status.as_ref().is_some_and(|status| status.confirmed)
+            })
+        };
+        self.cached(CacheKind::Outspends, txid, is_final, || self.backend.get_outspends(txid))
+            .await
+    }
+
+    /// Получает статистику адреса (число полученных и потраченных выходов)
+    ///
+    /// # Arguments
+    /// * `address` - Bitcoin адрес
+    pub async fn get_address_stats(&self, address: &str) -> Result<ChainAddressStats, ExplorerError> {
+        self.backend.get_address_stats(address).await
+    }
+
+    /// Получает историю транзакций адреса (сначала mempool, затем подтвержденные)
+    ///
+    /// # Arguments
+    /// * `address` - Bitcoin адрес
+    /// * `max_pages` - Максимальное число страниц подтвержденной истории
+    pub async fn get_address_transactions(
+        &self,
+        address: &str,
+        max_pages: usize,
+    ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+        let transactions = self.backend.get_address_transactions(address, max_pages).await?;
+        if let Some(cache) = &self.cache {
+            for tx in transactions.iter().filter(|tx| tx.status.confirmed) {
+                cache.store(CacheKind::Transaction, &tx.txid, tx, true).await;
+            }
+        }
+        Ok(transactions)
+    }
+
+    /// Получает высоту последнего блока
+    pub async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+        self.backend.get_tip_height().await
+    }
+
+    /// Выполняет запрос через кэш
+    ///
+    /// # Arguments
+    /// * `kind` - Вид данных в кэше
+    /// * `txid` - ID транзакции (ключ кэша)
+    /// * `is_confirmed` - Определяет, хранить ли ответ с TTL подтвержденных данных
+    /// * `fetch` - Запрос к backend'у
+    async fn cached<T, F, Fut>(
+        &self,
+        kind: CacheKind,
+        txid: &str,
+        is_confirmed: impl Fn(&T) -> bool,
+        fetch: F,
+    ) -> Result<T, ExplorerError>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + Send + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, ExplorerError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return fetch().await;
+        };
+        let stale = match cache.load(kind, txid).await {
+            CacheLookup::Fresh(value) => return Ok(value),
+            CacheLookup::Stale(value) => Some(value),
+            CacheLookup::Missing => None,
+        };
+        match fetch().await {
+            Ok(value) => {
+                cache.store(kind, txid, &value, is_confirmed(&value)).await;
+                Ok(value)
+            }
+            Err(error) if error.is_retryable() => match stale {
+                Some(value) => {
+                    log::warn!("explorer unavailable ({}), using stale cache entry for {}", error, txid);
+                    Ok(value)
+                }
+                None => Err(error),
+            },
+            Err(error) => Err(error),
+        }
     }
 }
 
@@ -56,12 +236,126 @@ pub static BITCOIN_EXPLORER_CLIENT: OnceCell<ExplorerClient> = OnceCell::const_n
 
 /// Получает или инициализирует глобальный клиент Bitcoin Explorer
 ///
+/// Клиент строится по настройкам explorer'а по умолчанию из [`Config::from_env`].
+/// Веб-сервер использует клиенты из состояния приложения, глобальный клиент
+/// нужен для CLI и функций-оберток `get_*`.
+///
 /// # Returns
 /// `Result<&'static ExplorerClient, ExplorerError>` - ссылка на клиент или ошибка инициализации
 pub async fn get_or_init_client() -> Result<&'static ExplorerClient, ExplorerError> {
     BITCOIN_EXPLORER_CLIENT
         .get_or_try_init(|| async {
-            ExplorerClient::new(&BITCOIN_EXPLORER_URL.to_string())
+            let config = Config::from_env().map_err(|e| ExplorerError::InvalidArgument(e.to_string()))?;
+            let client = match config.explorer(&config.default_explorer) {
+                Some(explorer) => ExplorerClient::from_config(explorer)?,
+                None => ExplorerClient::new(BITCOIN_EXPLORER_URL)?,
+            };
+            Ok(match ExplorerCache::from_config(&config, &config.default_explorer) {
+                Some(cache) => client.with_cache(Arc::new(cache)),
+                None => client,
+            })
         })
         .await
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::explorer::models::chain::ChainStatus;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+    use std::time::Duration;
+
+    const TXID: &str = "d6176384de4c0b98702eccb97f3ad6670bc8410d9da715fe5b49462d3e603993";
+
+    /// Backend, считающий запросы; после `go_offline` отвечает ошибкой сети
+    struct CountingBackend {
+        calls: Arc<AtomicU32>,
+        offline: Arc<AtomicBool>,
+        confirmed: bool,
+    }
+
+    #[async_trait]
+    impl ChainBackend for CountingBackend {
+        fn name(&self) -> &'static str {
+            "counting"
+        }
+
+        async fn get_transaction(&self, txid: &str) -> Result<ChainTransaction, ExplorerError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.offline.load(Ordering::SeqCst) {
+                return Err(ExplorerError::UpstreamUnavailable("offline".to_string()));
+            }
+            Ok(ChainTransaction {
+                txid: txid.to_string(),
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                fee: Some(0),
+                status: ChainStatus {
+                    confirmed: self.confirmed,
+                    ..ChainStatus::default()
+                },
+            })
+        }
+
+        async fn get_outspends(&self, _txid: &str) -> Result<Vec<ChainOutspend>, ExplorerError> {
+            Err(ExplorerError::Unsupported { backend: "counting", operation: "outspends" })
+        }
+
+        async fn get_address_stats(&self, _address: &str) -> Result<ChainAddressStats, ExplorerError> {
+            Err(ExplorerError::Unsupported { backend: "counting", operation: "address stats" })
+        }
+
+        async fn get_address_transactions(
+            &self,
+            _address: &str,
+            _max_pages: usize,
+        ) -> Result<Vec<ChainTransaction>, ExplorerError> {
+            Err(ExplorerError::Unsupported { backend: "counting", operation: "address txs" })
+        }
+
+        async fn get_tip_height(&self) -> Result<u64, ExplorerError> {
+            Ok(0)
+        }
+    }
+
+    fn cached_client(name: &str, confirmed: bool) -> (ExplorerClient, Arc<AtomicU32>, Arc<AtomicBool>) {
+        let root = std::env::temp_dir().join(format!("explorer_client_cache_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let calls = Arc::new(AtomicU32::new(0));
+        let offline = Arc::new(AtomicBool::new(false));
+        let backend = CountingBackend { calls: calls.clone(), offline: offline.clone(), confirmed };
+        let cache = ExplorerCache::new(root, Duration::ZERO, None);
+        (ExplorerClient::with_backend(backend).with_cache(Arc::new(cache)), calls, offline)
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_transaction_is_served_from_cache() {
+        let (client, calls, offline) = cached_client("confirmed", true);
+
+        client.get_transaction(TXID).await.expect("First request goes to backend");
+        offline.store(true, Ordering::SeqCst);
+        let tx = client.get_transaction(TXID).await.expect("Second request is served offline");
+
+        assert_eq!(tx.txid, TXID);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let stats = client.cache().expect("Cache attached").stats().expect("Should collect stats");
+        assert_eq!((stats.transactions, stats.hits), (1, 1));
+        let _ = std::fs::remove_dir_all(client.cache().expect("Cache attached").root());
+    }
+
+    #[tokio::test]
+    async fn test_stale_mempool_entry_is_used_when_backend_is_unavailable() {
+        let (client, calls, offline) = cached_client("mempool", false);
+
+        client.get_transaction(TXID).await.expect("First request goes to backend");
+        client.get_transaction(TXID).await.expect("Stale entry is refreshed");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        offline.store(true, Ordering::SeqCst);
+        let tx = client.get_transaction(TXID).await.expect("Stale entry is used offline");
+        assert!(!tx.status.confirmed);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        let _ = std::fs::remove_dir_all(client.cache().expect("Cache attached").root());
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::infrastructure::config::Config;
+use super::cache::ExplorerCache;
+use super::errors::explorer_errors::ExplorerError;
+use super::explorer_client::ExplorerClient;
+
+/// Набор именованных клиентов explorer'а
+///
+/// Создается один раз из [`Config`] и хранится в состоянии приложения,
+/// поэтому несколько explorer'ов (или тестовый сервер) могут использоваться
+/// одновременно.
+#[derive(Clone)]
+pub struct ExplorerRegistry {
+    clients: BTreeMap<String, ExplorerClient>,
+    default_name: String,
+}
+
+impl ExplorerRegistry {
+    /// Создает пустой набор с именем клиента по умолчанию
+    ///
+    /// # Arguments
+    /// * `default_name` - Имя клиента, возвращаемого [`ExplorerRegistry::default_client`]
+    pub fn new(default_name: impl Into<String>) -> Self {
+        ExplorerRegistry {
+            clients: BTreeMap::new(),
+            default_name: default_name.into(),
+        }
+    }
+
+    /// Создает клиенты для всех explorer'ов из конфигурации
+    ///
+    /// Если кэш включен, каждый клиент получает свой [`ExplorerCache`] в
+    /// поддиректории с именем клиента: ответы разных backend'ов не смешиваются.
+    ///
+    /// # Arguments
+    /// * `config` - Конфигурация приложения
+    ///
+    /// # Returns
+    /// `Result<Self, ExplorerError>` - набор клиентов или ошибка создания одного из них
+    pub fn from_config(config: &Config) -> Result<Self, ExplorerError> {
+        let mut registry = Self::new(config.default_explorer.clone());
+        for explorer in &config.explorers {
+            let client = ExplorerClient::from_config(explorer)?;
+            let client = match ExplorerCache::from_config(config, &explorer.name) {
+                Some(cache) => client.with_cache(Arc::new(cache)),
+                None => client,
+            };
+            registry.insert(explorer.name.clone(), client);
+        }
+        Ok(registry)
+    }
+
+    /// Добавляет или заменяет клиент с указанным именем
+    pub fn insert(&mut self, name: impl Into<String>, client: ExplorerClient) {
+        self.clients.insert(name.into(), client);
+    }
+
+    /// Возвращает клиент по имени
+    pub fn get(&self, name: &str) -> Option<&ExplorerClient> {
+        self.clients.get(name)
+    }
+
+    /// Возвращает клиент по умолчанию
+    ///
+    /// # Returns
+    /// `Result<&ExplorerClient, ExplorerError>` - клиент или `ClientNotInitialized`,
+    /// если клиент с именем по умолчанию не зарегистрирован
+    pub fn default_client(&self) -> Result<&ExplorerClient, ExplorerError> {
+        self.get(&self.default_name).ok_or(ExplorerError::ClientNotInitialized)
+    }
+
+    /// Возвращает клиент по имени, а при его отсутствии — клиент по умолчанию
+    pub fn get_or_default(&self, name: Option<&str>) -> Result<&ExplorerClient, ExplorerError> {
+        match name {
+            Some(name) => self.get(name).ok_or(ExplorerError::ClientNotInitialized),
+            None => self.default_client(),
+        }
+    }
+
+    /// Имена зарегистрированных клиентов в алфавитном порядке
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.clients.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::config::ExplorerConfig;
+    use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+    use std::net::TcpListener;
+
+    /// Отвечает высотой блока, только если пришли ожидаемые заголовки
+    async fn tip_height(req: HttpRequest) -> HttpResponse {
+        let header = |name: &str| req.headers().get(name).and_then(|v| v.to_str().ok()).unwrap_or("");
+        if header("user-agent") == "test-agent" && header("x-api-key") == "secret" {
+            HttpResponse::Ok().body("840000")
+        } else {
+            HttpResponse::Unauthorized().finish()
+        }
+    }
+
+    fn start_mock_explorer() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!("http://{}", listener.local_addr().expect("Should have address"));
+        let server = HttpServer::new(|| App::new().route("/blocks/tip/height", web::get().to(tip_height)))
+            .listen(listener)
+            .expect("Should listen")
+            .workers(1)
+            .run();
+        actix_web::rt::spawn(server);
+        url
+    }
+
+    #[actix_web::test]
+    async fn test_named_clients_use_their_own_settings() {
+        let url = start_mock_explorer();
+
+        let mut configured = ExplorerConfig::new("local", url.clone());
+        configured.user_agent = "test-agent".to_string();
+        configured.auth_header = Some(("X-Api-Key".to_string(), "secret".to_string()));
+
+        let mut registry = ExplorerRegistry::new("local");
+        registry.insert("local", ExplorerClient::from_config(&configured).expect("Should build"));
+        registry.insert("plain", ExplorerClient::new(url).expect("Should build"));
+
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["local", "plain"]);
+        let tip = registry.default_client().expect("Default exists").get_tip_height().await;
+        assert_eq!(tip.expect("Headers should be accepted"), 840_000);
+        assert!(registry.get("plain").expect("Registered").get_tip_height().await.is_err());
+        assert!(matches!(
+            registry.get_or_default(Some("missing")),
+            Err(ExplorerError::ClientNotInitialized)
+        ));
+    }
+
+    #[test]
+    fn test_clients_get_separate_caches_and_blockbook_requires_url() {
+        use crate::infrastructure::config::ExplorerBackendKind;
+
+        let root = std::env::temp_dir().join(format!("explorer_registry_cache_{}", std::process::id()));
+        let mut core = ExplorerConfig::new("node", "http://127.0.0.1:8332");
+        core.backend = ExplorerBackendKind::BitcoinCore;
+        let config = Config {
+            explorers: vec![ExplorerConfig::new("default", "http://127.0.0.1:1"), core],
+            default_explorer: "default".to_string(),
+            explorer_cache_dir: Some(root.to_string_lossy().into_owned()),
+            ..Config::from_env().expect("Valid config")
+        };
+        let registry = ExplorerRegistry::from_config(&config).expect("Should build");
+        let cache_root = |name: &str| registry.get(name).and_then(ExplorerClient::cache).map(|cache| cache.root().to_path_buf());
+        assert_eq!(cache_root("default"), Some(root.join("default")));
+        assert_eq!(cache_root("node"), Some(root.join("node")));
+
+        let mut blockbook = ExplorerConfig::new("trezor", "");
+        blockbook.backend = ExplorerBackendKind::Blockbook;
+        let config = Config { explorers: vec![blockbook], ..config };
+        assert!(matches!(ExplorerRegistry::from_config(&config), Err(ExplorerError::InvalidArgument(_))));
+    }
+}
@@ -1,2 +1,8 @@
+pub mod cache;
 pub mod explorer_client;
-pub mod errors;
\ No newline at end of file
+pub mod explorer_registry;
+pub mod request_policy;
+pub mod rate_limiter;
+pub mod backends;
+pub mod errors;
+pub mod models;
@@ -0,0 +1,201 @@
+//! Нормализованная модель данных блокчейна
+//!
+//! Все реализации [`ChainBackend`](crate::services::explorer::backends::chain_backend::ChainBackend)
+//! приводят ответы своих API к этим типам, поэтому извлечение признаков
+//! не зависит от того, откуда получены данные. Суммы хранятся в сатоши.
+
+use bitcoin::Amount;
+use serde::{Deserialize, Serialize};
+
+/// Транзакция в нормализованном виде
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainTransaction {
+    /// ID транзакции
+    pub txid: String,
+    /// Входы транзакции
+    pub inputs: Vec<ChainInput>,
+    /// Выходы транзакции
+    pub outputs: Vec<ChainOutput>,
+    /// Комиссия в сатоши (если известна)
+    pub fee: Option<u64>,
+    /// Статус подтверждения
+    pub status: ChainStatus,
+}
+
+impl ChainTransaction {
+    /// Сумма входов (входы без известного `prevout` не учитываются)
+    pub fn input_value(&self) -> Amount {
+        self.inputs
+            .iter()
+            .filter_map(|input| input.prevout.as_ref())
+            .map(|prevout| Amount::from_sat(prevout.value))
+            .sum()
+    }
+
+    /// Сумма выходов
+    pub fn output_value(&self) -> Amount {
+        self.outputs.iter().map(|output| Amount::from_sat(output.value)).sum()
+    }
+
+    /// Комиссия, вычисленная как разность сумм входов и выходов
+    ///
+    /// # Returns
+    /// `Option<Amount>` - комиссия или `None`, если у какого-то входа неизвестен
+    /// расходуемый выход (в том числе у coinbase) либо выходы превышают входы
+    pub fn computed_fee(&self) -> Option<Amount> {
+        if self.inputs.is_empty() || self.inputs.iter().any(|input| input.prevout.is_none()) {
+            return None;
+        }
+        self.input_value().checked_sub(self.output_value())
+    }
+}
+
+/// Вход транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainInput {
+    /// ID родительской транзакции
+    pub txid: String,
+    /// Номер выхода родительской транзакции
+    pub vout: u32,
+    /// Расходуемый выход (отсутствует у coinbase входа)
+    pub prevout: Option<ChainOutput>,
+    /// Признак coinbase входа
+    pub is_coinbase: bool,
+}
+
+/// Выход транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainOutput {
+    /// Скрипт выхода в hex (может быть пустым, если backend его не отдает)
+    pub script_pubkey: String,
+    /// Адрес получателя (отсутствует для нестандартных скриптов)
+    pub address: Option<String>,
+    /// Сумма в сатоши
+    pub value: u64,
+}
+
+impl ChainOutput {
+    /// Ключ владельца выхода: адрес, а при его отсутствии — скрипт
+    pub fn owner_key(&self) -> &str {
+        self.address.as_deref().unwrap_or(&self.script_pubkey)
+    }
+}
+
+/// Статус подтверждения транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ChainStatus {
+    /// Включена ли транзакция в блок
+    pub confirmed: bool,
+    /// Высота блока
+    pub block_height: Option<u64>,
+    /// Время блока (unix timestamp)
+    pub block_time: Option<u64>,
+}
+
+/// Статус траты выхода транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChainOutspend {
+    /// Потрачен ли выход
+    pub spent: bool,
+    /// ID расходующей транзакции (если backend его знает)
+    pub txid: Option<String>,
+    /// Номер входа в расходующей транзакции
+    pub vin: Option<u32>,
+    /// Статус расходующей транзакции
+    pub status: Option<ChainStatus>,
+}
+
+/// Статистика адреса
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ChainAddressStats {
+    /// Адрес
+    pub address: String,
+    /// Число выходов, полученных адресом
+    pub funded_txo_count: u64,
+    /// Число выходов, потраченных адресом
+    pub spent_txo_count: u64,
+    /// Число транзакций с участием адреса
+    pub tx_count: u64,
+}
+
+impl ChainAddressStats {
+    /// Подсчитывает статистику адреса по его истории транзакций
+    ///
+    /// Используется backend'ами, у которых нет готовых счетчиков UTXO.
+    ///
+    /// # Arguments
+    /// * `address` - Bitcoin адрес
+    /// * `history` - Транзакции с участием адреса
+    pub fn from_history(address: &str, history: &[ChainTransaction]) -> Self {
+        let is_own = |output: &ChainOutput| output.address.as_deref() == Some(address);
+        ChainAddressStats {
+            address: address.to_string(),
+            funded_txo_count: history
+                .iter()
+                .flat_map(|tx| tx.outputs.iter())
+                .filter(|output| is_own(output))
+                .count() as u64,
+            spent_txo_count: history
+                .iter()
+                .flat_map(|tx| tx.inputs.iter())
+                .filter_map(|input| input.prevout.as_ref())
+                .filter(|prevout| is_own(prevout))
+                .count() as u64,
+            tx_count: history.len() as u64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(address: &str, value: u64) -> ChainOutput {
+        ChainOutput {
+            script_pubkey: String::new(),
+            address: Some(address.to_string()),
+            value,
+        }
+    }
+
+    fn transaction(inputs: &[(&str, u64)], outputs: &[(&str, u64)]) -> ChainTransaction {
+        ChainTransaction {
+            txid: "tx".to_string(),
+            inputs: inputs
+                .iter()
+                .map(|(address, value)| ChainInput {
+                    txid: "parent".to_string(),
+                    vout: 0,
+                    prevout: Some(output(address, *value)),
+                    is_coinbase: false,
+                })
+                .collect(),
+            outputs: outputs.iter().map(|(address, value)| output(address, *value)).collect(),
+            fee: None,
+            status: ChainStatus::default(),
+        }
+    }
+
+    #[test]
+    fn test_transaction_values() {
+        let tx = transaction(&[("A", 70_000), ("B", 30_000)], &[("C", 95_000)]);
+        assert_eq!(tx.input_value(), Amount::from_sat(100_000));
+        assert_eq!(tx.output_value(), Amount::from_sat(95_000));
+        assert_eq!(tx.computed_fee(), Some(Amount::from_sat(5_000)));
+
+        let overspending = transaction(&[("A", 1_000)], &[("B", 1_001)]);
+        assert_eq!(overspending.computed_fee(), None);
+    }
+
+    #[test]
+    fn test_address_stats_from_history() {
+        let history = vec![
+            transaction(&[("X", 10)], &[("A", 5), ("A", 4)]),
+            transaction(&[("A", 5)], &[("Y", 4)]),
+        ];
+        let stats = ChainAddressStats::from_history("A", &history);
+        assert_eq!(stats.funded_txo_count, 2);
+        assert_eq!(stats.spent_txo_count, 1);
+        assert_eq!(stats.tx_count, 2);
+    }
+}
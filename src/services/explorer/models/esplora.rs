@@ -0,0 +1,358 @@
+//! Модели ответов Esplora-совместимого API (mempool.space, blockstream.info)
+
+use serde::{Deserialize, Serialize};
+
+use super::chain::{
+    ChainAddressStats, ChainInput, ChainOutput, ChainOutspend, ChainStatus, ChainTransaction,
+};
+
+/// Максимальное значение `nSequence`, при котором вход сигнализирует RBF (BIP 125)
+const MAX_RBF_SEQUENCE: u32 = 0xffff_fffd;
+
+/// Транзакция в формате Esplora (`GET /tx/{txid}`)
+///
+/// Все суммы в ответах Esplora указаны в сатоши: значения входов находятся
+/// в `vin[].prevout.value`, у самих входов поля `value` нет.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EsploraTransaction {
+    /// ID транзакции
+    pub txid: String,
+    /// Версия транзакции
+    #[serde(default)]
+    pub version: u32,
+    /// `nLockTime`
+    #[serde(default)]
+    pub locktime: u32,
+    /// Входы транзакции
+    pub vin: Vec<EsploraVin>,
+    /// Выходы транзакции
+    pub vout: Vec<EsploraVout>,
+    /// Размер в байтах
+    #[serde(default)]
+    pub size: u64,
+    /// Вес в weight units
+    #[serde(default)]
+    pub weight: u64,
+    /// Комиссия в сатоши
+    #[serde(default)]
+    pub fee: Option<u64>,
+    /// Статус подтверждения
+    pub status: EsploraStatus,
+}
+
+impl EsploraTransaction {
+    /// Виртуальный размер в vbytes (вес / 4 с округлением вверх)
+    pub fn vsize(&self) -> u64 {
+        self.weight.div_ceil(4)
+    }
+
+    /// Является ли транзакция coinbase
+    pub fn is_coinbase(&self) -> bool {
+        self.vin.iter().any(|vin| vin.is_coinbase)
+    }
+
+    /// Сигнализирует ли транзакция замену по комиссии (BIP 125)
+    pub fn signals_rbf(&self) -> bool {
+        self.vin.iter().any(|vin| vin.sequence <= MAX_RBF_SEQUENCE)
+    }
+}
+
+/// Вход транзакции Esplora
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EsploraVin {
+    /// ID родительской транзакции (нули у coinbase входа)
+    pub txid: String,
+    /// Номер выхода родительской транзакции (`0xffffffff` у coinbase входа)
+    pub vout: u32,
+    /// Расходуемый выход (`null` у coinbase входа)
+    #[serde(default)]
+    pub prevout: Option<EsploraVout>,
+    /// `scriptSig` в hex (пустой у нативных SegWit входов)
+    #[serde(default)]
+    pub scriptsig: String,
+    /// Элементы witness в hex (поле отсутствует у входов без witness)
+    #[serde(default)]
+    pub witness: Vec<String>,
+    /// Признак coinbase входа
+    #[serde(default)]
+    pub is_coinbase: bool,
+    /// `nSequence`
+    #[serde(default = "default_sequence")]
+    pub sequence: u32,
+}
+
+fn default_sequence() -> u32 {
+    u32::MAX
+}
+
+/// Выход транзакции Esplora
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EsploraVout {
+    /// Скрипт выхода в hex
+    #[serde(default)]
+    pub scriptpubkey: String,
+    /// Тип скрипта: `p2pkh`, `p2sh`, `v0_p2wpkh`, `v0_p2wsh`, `v1_p2tr`, `op_return`, ...
+    #[serde(default)]
+    pub scriptpubkey_type: String,
+    /// Адрес получателя (отсутствует для нестандартных скриптов)
+    #[serde(default)]
+    pub scriptpubkey_address: Option<String>,
+    /// Сумма в сатоши
+    pub value: u64,
+}
+
+impl EsploraVout {
+    /// Ключ владельца выхода: адрес, а при его отсутствии — скрипт
+    pub fn owner_key(&self) -> &str {
+        self.scriptpubkey_address
+            .as_deref()
+            .unwrap_or(&self.scriptpubkey)
+    }
+}
+
+/// Статус подтверждения транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct EsploraStatus {
+    /// Включена ли транзакция в блок
+    pub confirmed: bool,
+    /// Высота блока
+    #[serde(default)]
+    pub block_height: Option<u64>,
+    /// Хеш блока
+    #[serde(default)]
+    pub block_hash: Option<String>,
+    /// Время блока (unix timestamp)
+    #[serde(default)]
+    pub block_time: Option<u64>,
+}
+
+/// Статус траты выхода (`GET /tx/{txid}/outspends`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EsploraOutspend {
+    /// Потрачен ли выход
+    pub spent: bool,
+    /// ID расходующей транзакции
+    #[serde(default)]
+    pub txid: Option<String>,
+    /// Номер входа в расходующей транзакции
+    #[serde(default)]
+    pub vin: Option<u32>,
+    /// Статус расходующей транзакции
+    #[serde(default)]
+    pub status: Option<EsploraStatus>,
+}
+
+/// Статистика адреса (`GET /address/{address}`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EsploraAddressStats {
+    /// Адрес
+    pub address: String,
+    /// Статистика по подтвержденным транзакциям
+    pub chain_stats: EsploraTxoStats,
+    /// Статистика по транзакциям в mempool
+    pub mempool_stats: EsploraTxoStats,
+}
+
+impl EsploraAddressStats {
+    /// Общее число выходов, полученных адресом
+    pub fn funded_txo_count(&self) -> u64 {
+        self.chain_stats.funded_txo_count + self.mempool_stats.funded_txo_count
+    }
+
+    /// Общее число выходов, потраченных адресом
+    pub fn spent_txo_count(&self) -> u64 {
+        self.chain_stats.spent_txo_count + self.mempool_stats.spent_txo_count
+    }
+}
+
+/// Счетчики UTXO адреса
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct EsploraTxoStats {
+    /// Число полученных выходов
+    pub funded_txo_count: u64,
+    /// Сумма полученных выходов в сатоши
+    pub funded_txo_sum: u64,
+    /// Число потраченных выходов
+    pub spent_txo_count: u64,
+    /// Сумма потраченных выходов в сатоши
+    pub spent_txo_sum: u64,
+    /// Число транзакций
+    pub tx_count: u64,
+}
+
+impl From<EsploraTransaction> for ChainTransaction {
+    fn from(tx: EsploraTransaction) -> Self {
+        ChainTransaction {
+            txid: tx.txid,
+            inputs: tx.vin.into_iter().map(ChainInput::from).collect(),
+            outputs: tx.vout.into_iter().map(ChainOutput::from).collect(),
+            fee: tx.fee,
+            status: tx.status.into(),
+        }
+    }
+}
+
+impl From<EsploraVin> for ChainInput {
+    fn from(vin: EsploraVin) -> Self {
+        ChainInput {
+            txid: vin.txid,
+            vout: vin.vout,
+            prevout: vin.prevout.map(ChainOutput::from),
+            is_coinbase: vin.is_coinbase,
+        }
+    }
+}
+
+impl From<EsploraVout> for ChainOutput {
+    fn from(vout: EsploraVout) -> Self {
+        ChainOutput {
+            script_pubkey: vout.scriptpubkey,
+            address: vout.scriptpubkey_address,
+            value: vout.value,
+        }
+    }
+}
+
+impl From<EsploraStatus> for ChainStatus {
+    fn from(status: EsploraStatus) -> Self {
+        ChainStatus {
+            confirmed: status.confirmed,
+            block_height: status.block_height,
+            block_time: status.block_time,
+        }
+    }
+}
+
+impl From<EsploraOutspend> for ChainOutspend {
+    fn from(outspend: EsploraOutspend) -> Self {
+        ChainOutspend {
+            spent: outspend.spent,
+            txid: outspend.txid,
+            vin: outspend.vin,
+            status: outspend.status.map(ChainStatus::from),
+        }
+    }
+}
+
+impl From<EsploraAddressStats> for ChainAddressStats {
+    fn from(stats: EsploraAddressStats) -> Self {
+        ChainAddressStats {
+            funded_txo_count: stats.funded_txo_count(),
+            spent_txo_count: stats.spent_txo_count(),
+            tx_count: stats.chain_stats.tx_count + stats.mempool_stats.tx_count,
+            address: stats.address,
+        }
+    }
+}
+
+/// Первая транзакция между двумя людьми (блок 170), P2PK
+#[cfg(test)]
+pub(crate) const FIRST_P2P_TX: &str = "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16";
+
+/// SegWit транзакция блока 765165: два P2WPKH входа одного адреса, сдача на тот же адрес
+#[cfg(test)]
+pub(crate) const SEGWIT_TX: &str = "b0714235addd08daf83b979aa35cc9ed7558efb8327b86b4d3ccacd8b0482ae1";
+
+/// Coinbase транзакция блока 1
+#[cfg(test)]
+pub(crate) const BLOCK_1_COINBASE: &str = "0e3e2357e806b6cdb1f70b54c3a3a17b6714ee1f0e68bebb44a74b1efd512098";
+
+/// Читает файл из `tests/fixtures/esplora`
+#[cfg(test)]
+pub(crate) fn fixture_json(name: &str) -> String {
+    let path = format!("{}/tests/fixtures/esplora/{}", env!("CARGO_MANIFEST_DIR"), name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("Fixture {} should exist: {}", path, e))
+}
+
+/// Разбирает транзакцию из `tests/fixtures/esplora/{name}.json`
+#[cfg(test)]
+pub(crate) fn fixture_transaction(name: &str) -> EsploraTransaction {
+    serde_json::from_str(&fixture_json(&format!("{}.json", name))).expect("Fixture should parse")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_legacy_transaction() {
+        let tx = fixture_transaction(FIRST_P2P_TX);
+
+        assert_eq!(tx.version, 1);
+        assert_eq!((tx.size, tx.weight, tx.vsize()), (275, 1100, 275));
+        assert!(!tx.is_coinbase() && !tx.signals_rbf());
+
+        let prevout = tx.vin[0].prevout.as_ref().expect("Prevout is present");
+        assert_eq!(prevout.value, 5_000_000_000);
+        assert_eq!(prevout.scriptpubkey_type, "p2pk");
+        assert_eq!(prevout.scriptpubkey_address, None);
+        assert!(tx.vin[0].witness.is_empty());
+        assert_eq!(tx.vin[0].sequence, u32::MAX);
+        assert_eq!(tx.vout.iter().map(|v| v.value).collect::<Vec<_>>(), vec![1_000_000_000, 4_000_000_000]);
+
+        assert_eq!(tx.status.block_height, Some(170));
+        assert_eq!(tx.status.block_time, Some(1_231_731_025));
+        assert_eq!(
+            tx.status.block_hash.as_deref(),
+            Some("00000000d1145790a8694403d4063f323d499e655c83426834d4ce2f8dd4a2ee")
+        );
+    }
+
+    #[test]
+    fn test_parse_segwit_transaction() {
+        let mut tx = fixture_transaction(SEGWIT_TX);
+
+        assert_eq!((tx.size, tx.weight, tx.vsize()), (402, 957, 240));
+        assert!(!tx.signals_rbf());
+        assert!(tx.vin.iter().all(|v| v.witness.len() == 2 && v.scriptsig.is_empty()));
+        assert_eq!(
+            tx.vin.iter().map(|v| v.prevout.as_ref().unwrap().scriptpubkey_type.as_str()).collect::<Vec<_>>(),
+            vec!["v0_p2wpkh", "v0_p2wpkh"]
+        );
+        assert_eq!(tx.vout[2].owner_key(), "bc1qxxkqkdljm7s2xnr9u47zpymp4yt0ad3df4hudm");
+        assert_eq!(tx.status.block_height, Some(765_165));
+
+        tx.vin[1].sequence = 0xffff_fffd;
+        assert!(tx.signals_rbf());
+
+        let chain = ChainTransaction::from(tx);
+        assert_eq!(chain.input_value().to_sat(), 96_935_907);
+        assert_eq!(chain.computed_fee().map(|fee| fee.to_sat()), Some(26_517));
+        assert_eq!(chain.computed_fee().map(|fee| fee.to_sat()), chain.fee);
+    }
+
+    #[test]
+    fn test_parse_coinbase_and_mempool_transactions() {
+        let coinbase = fixture_transaction(BLOCK_1_COINBASE);
+        assert!(coinbase.is_coinbase());
+        assert_eq!(coinbase.vin[0].prevout, None);
+        assert_eq!(coinbase.vin[0].vout, u32::MAX);
+        // P2PK выход без адреса идентифицируется скриптом
+        assert_eq!(coinbase.vout[0].owner_key(), coinbase.vout[0].scriptpubkey);
+        let chain = ChainTransaction::from(coinbase);
+        assert!(chain.inputs[0].is_coinbase);
+        assert_eq!(chain.computed_fee(), None);
+
+        // Неподтвержденная транзакция приходит со статусом без блока
+        let mut mempool = fixture_transaction(SEGWIT_TX);
+        mempool.status = serde_json::from_str(r#"{"confirmed": false}"#).expect("Should parse");
+        assert_eq!(mempool.status, EsploraStatus::default());
+        let chain = ChainTransaction::from(mempool);
+        assert_eq!(chain.status, ChainStatus::default());
+        assert_eq!(chain.fee, Some(26_517));
+    }
+
+    #[test]
+    fn test_parse_outspends_and_address_stats() {
+        let outspends: Vec<EsploraOutspend> =
+            serde_json::from_str(&fixture_json("outspends.json")).expect("Should parse");
+        let outspends: Vec<ChainOutspend> = outspends.into_iter().map(ChainOutspend::from).collect();
+        assert!(outspends.iter().all(|o| o.spent));
+        assert_eq!(outspends.iter().map(|o| o.vin).collect::<Vec<_>>(), vec![Some(23), Some(0), Some(0)]);
+        assert_eq!(outspends[1].status.as_ref().and_then(|s| s.block_height), Some(780_500));
+
+        let stats: EsploraAddressStats = serde_json::from_str(&fixture_json("address.json")).expect("Should parse");
+        let stats = ChainAddressStats::from(stats);
+        assert_eq!((stats.funded_txo_count, stats.spent_txo_count, stats.tx_count), (1, 1, 2));
+    }
+}
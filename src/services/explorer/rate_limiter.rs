@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use tokio::time::{sleep, Instant};
+
+/// Клиентский ограничитель частоты запросов («token bucket»)
+///
+/// Один экземпляр разделяется всеми параллельными запросами клиента:
+/// запрос ждет, пока в корзине не появится токен. Корзина пополняется
+/// со скоростью `rate` токенов в секунду и вмещает не больше `burst` токенов.
+pub struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    state: Mutex<BucketState>,
+}
+
+struct BucketState {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    /// Создает полную корзину
+    ///
+    /// # Arguments
+    /// * `rate` - Число запросов в секунду (должно быть больше нуля)
+    /// * `burst` - Максимальное число запросов подряд без ожидания
+    pub fn new(rate: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        TokenBucket {
+            rate,
+            burst,
+            state: Mutex::new(BucketState {
+                tokens: burst,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Ожидает свободный токен и забирает его
+    pub async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().await;
+                self.refill(&mut state);
+                if state.tokens >= 1.0 {
+                    state.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.tokens) / self.rate)
+            };
+            sleep(wait).await;
+        }
+    }
+
+    /// Приостанавливает выдачу токенов на `duration`
+    ///
+    /// Используется, когда explorer ответил 429 с `Retry-After`: остальные
+    /// параллельные запросы тоже подождут, а не получат ту же ошибку.
+    pub async fn pause(&self, duration: Duration) {
+        let mut state = self.state.lock().await;
+        self.refill(&mut state);
+        state.tokens = state.tokens.min(0.0) - duration.as_secs_f64() * self.rate;
+    }
+
+    fn refill(&self, state: &mut BucketState) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.updated).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.rate).min(self.burst);
+        state.updated = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bucket_limits_rate_after_burst() {
+        let bucket = TokenBucket::new(20.0, 2);
+        let start = Instant::now();
+
+        bucket.acquire().await;
+        bucket.acquire().await;
+        assert!(start.elapsed() < Duration::from_millis(40));
+
+        // Третий токен появится через 1 / rate = 50 мс
+        bucket.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[tokio::test]
+    async fn test_pause_delays_next_token() {
+        let bucket = TokenBucket::new(100.0, 5);
+        bucket.pause(Duration::from_millis(100)).await;
+        let start = Instant::now();
+
+        bucket.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(95));
+    }
+}
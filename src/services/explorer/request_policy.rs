@@ -0,0 +1,231 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use rand::Rng;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+
+use crate::infrastructure::config::ExplorerConfig;
+use crate::infrastructure::constants::{
+    DEFAULT_EXPLORER_MAX_RETRIES, DEFAULT_EXPLORER_RETRY_BASE_DELAY_MS,
+    DEFAULT_EXPLORER_RETRY_MAX_DELAY_MS,
+};
+use super::errors::explorer_errors::ExplorerError;
+use super::rate_limiter::TokenBucket;
+
+/// Политика выполнения запросов к explorer'у: ограничение частоты и повторы
+///
+/// Повторяются только ошибки, для которых [`ExplorerError::is_retryable`]
+/// возвращает `true` (429, 5xx, таймауты и ошибки соединения). Задержка растет
+/// экспоненциально от `base_delay` до `max_delay` со случайным разбросом;
+/// `Retry-After` из ответа 429 соблюдается, а если он больше `max_delay`,
+/// запрос не повторяется.
+#[derive(Clone)]
+pub struct RequestPolicy {
+    max_retries: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    limiter: Option<Arc<TokenBucket>>,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        RequestPolicy {
+            max_retries: DEFAULT_EXPLORER_MAX_RETRIES,
+            base_delay: Duration::from_millis(DEFAULT_EXPLORER_RETRY_BASE_DELAY_MS),
+            max_delay: Duration::from_millis(DEFAULT_EXPLORER_RETRY_MAX_DELAY_MS),
+            limiter: None,
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// Создает политику без ограничения частоты
+    ///
+    /// # Arguments
+    /// * `max_retries` - Число повторов после первой попытки
+    /// * `base_delay` - Задержка перед первым повтором
+    /// * `max_delay` - Максимальная задержка между повторами
+    pub fn new(max_retries: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        RequestPolicy {
+            max_retries,
+            base_delay,
+            max_delay,
+            limiter: None,
+        }
+    }
+
+    /// Создает политику по настройкам клиента explorer'а
+    pub fn from_config(config: &ExplorerConfig) -> Self {
+        let policy = Self::new(
+            config.max_retries,
+            Duration::from_millis(config.retry_base_delay_ms),
+            Duration::from_millis(config.retry_max_delay_ms),
+        );
+        match config.rate_limit_per_sec {
+            Some(rate) => policy.with_rate_limit(rate, config.rate_limit_burst),
+            None => policy,
+        }
+    }
+
+    /// Включает общий для всех запросов ограничитель частоты
+    ///
+    /// # Arguments
+    /// * `rate` - Число запросов в секунду
+    /// * `burst` - Число запросов подряд без ожидания
+    pub fn with_rate_limit(mut self, rate: f64, burst: u32) -> Self {
+        self.limiter = Some(Arc::new(TokenBucket::new(rate, burst)));
+        self
+    }
+
+    /// Выполняет запрос с учетом ограничения частоты и повторов
+    ///
+    /// # Arguments
+    /// * `request` - Фабрика запроса, вызывается на каждую попытку
+    ///
+    /// # Returns
+    /// `Result<T, ExplorerError>` - результат первой успешной попытки или последняя ошибка
+    pub async fn execute<T, F, Fut>(&self, mut request: F) -> Result<T, ExplorerError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ExplorerError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            if let Some(limiter) = &self.limiter {
+                limiter.acquire().await;
+            }
+
+            let error = match request().await {
+                Ok(value) => return Ok(value),
+                Err(error) => error,
+            };
+            if !error.is_retryable() || attempt >= self.max_retries {
+                return Err(error);
+            }
+            let Some(delay) = self.retry_delay(attempt, error.retry_after()) else {
+                return Err(error);
+            };
+
+            if let (Some(limiter), Some(retry_after)) = (&self.limiter, error.retry_after()) {
+                limiter.pause(retry_after).await;
+            }
+            log::debug!("explorer request failed ({}), retry {} in {:?}", error, attempt + 1, delay);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Задержка перед повтором с номером `attempt` (с нуля)
+    ///
+    /// Экспоненциальная задержка берется со случайным разбросом в диапазоне
+    /// `[d/2, d]`, чтобы параллельные клиенты не повторяли запросы синхронно.
+    /// Возвращает `None`, если `Retry-After` превышает `max_delay`.
+    fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        let jittered = half + rand::thread_rng().gen_range(Duration::ZERO..=exponential - half);
+
+        match retry_after {
+            Some(retry_after) if retry_after > self.max_delay => None,
+            Some(retry_after) => Some(retry_after.max(jittered)),
+            None => Some(jittered),
+        }
+    }
+}
+
+/// Разбирает заголовок `Retry-After` (число секунд или HTTP дата)
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy(max_retries: u32) -> RequestPolicy {
+        RequestPolicy::new(max_retries, Duration::from_millis(1), Duration::from_millis(20))
+    }
+
+    #[test]
+    fn test_retry_delay_grows_and_is_capped() {
+        let policy = RequestPolicy::new(10, Duration::from_millis(100), Duration::from_secs(1));
+        for attempt in 0..10 {
+            let delay = policy.retry_delay(attempt, None).expect("Should retry");
+            let expected = (Duration::from_millis(100) * 2u32.pow(attempt)).min(Duration::from_secs(1));
+            assert!(delay >= expected / 2 && delay <= expected, "attempt {}: {:?}", attempt, delay);
+        }
+        assert_eq!(
+            policy.retry_delay(0, Some(Duration::from_millis(800))),
+            Some(Duration::from_millis(800))
+        );
+        assert_eq!(policy.retry_delay(0, Some(Duration::from_secs(60))), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_execute_retries_transient_errors() {
+        let calls = AtomicU32::new(0);
+        let result = fast_policy(3)
+            .execute(|| async {
+                match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => Err(ExplorerError::UpstreamUnavailable("503".to_string())),
+                    1 => Err(ExplorerError::RateLimited { retry_after: Some(Duration::from_millis(5)) }),
+                    _ => Ok(42),
+                }
+            })
+            .await;
+
+        assert_eq!(result.expect("Third attempt succeeds"), 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_execute_does_not_retry_not_found() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = fast_policy(3)
+            .execute(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(ExplorerError::NotFound("/tx/00".to_string()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(ExplorerError::NotFound(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_gives_up_after_max_retries() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), _> = fast_policy(2)
+            .execute(|| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(ExplorerError::UpstreamUnavailable("502".to_string()))
+            })
+            .await;
+
+        assert!(matches!(result, Err(ExplorerError::UpstreamUnavailable(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+}
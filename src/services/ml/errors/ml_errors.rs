@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MLError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("JSON parsing error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("ML service error {code}: {message}")]
+    Service {
+        code: String,
+        message: String,
+        details: serde_json::Value,
+    },
+    #[error("Unexpected ML service response (status {status}): {body}")]
+    InvalidResponse { status: u16, body: String },
+    #[error("Failed to build HTTP client: {0}")]
+    ClientBuildError(String),
+}
@@ -0,0 +1 @@
+pub mod ml_errors;
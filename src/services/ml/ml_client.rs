@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+use crate::infrastructure::config::Config;
+use super::errors::ml_errors::MLError;
+use super::models::{BatchRequest, BatchResponse, ErrorResponse, HealthResponse, MLRequest, MLResponse};
+
+/// Клиент Python ML сервиса
+pub struct MLClient {
+    http: Client,
+    base_url: String,
+}
+
+impl MLClient {
+    /// Создает новый клиент ML сервиса
+    ///
+    /// # Arguments
+    /// * `base_url` - Базовый URL сервиса, например `http://localhost:8001`
+    /// * `timeout` - Таймаут одного запроса
+    ///
+    /// # Returns
+    /// `Result<Self, MLError>` - клиент или ошибка создания
+    pub fn new(base_url: impl Into<String>, timeout: Duration) -> Result<Self, MLError> {
+        let http = Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| MLError::ClientBuildError(e.to_string()))?;
+
+        Ok(MLClient {
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Создает клиент по `ML_SERVICE_URL` и `ML_SERVICE_TIMEOUT` из конфигурации
+    pub fn from_config(config: &Config) -> Result<Self, MLError> {
+        Self::new(
+            config.ml_service_url.clone(),
+            Duration::from_secs(config.ml_service_timeout),
+        )
+    }
+
+    /// Отправляет запрос и разбирает ответ, учитывая конверт ошибки `success: false`
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, MLError> {
+        let resp = request.send().await?;
+        let status = resp.status();
+        let body = resp.text().await?;
+
+        let value: serde_json::Value = match serde_json::from_str(&body) {
+            Ok(value) => value,
+            Err(_) => {
+                return Err(MLError::InvalidResponse {
+                    status: status.as_u16(),
+                    body,
+                })
+            }
+        };
+
+        if value.get("success").and_then(|s| s.as_bool()) == Some(false) {
+            let error: ErrorResponse = serde_json::from_value(value)?;
+            return Err(MLError::Service {
+                code: error.error.code,
+                message: error.error.message,
+                details: error.error.details,
+            });
+        }
+
+        if !status.is_success() {
+            return Err(MLError::InvalidResponse {
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Анализирует транзакцию (`POST /api/v1/analyze`)
+    ///
+    /// # Arguments
+    /// * `request` - Признаки транзакции
+    ///
+    /// # Returns
+    /// `Result<MLResponse, MLError>` - предсказание модели или ошибка
+    pub async fn analyze_transaction(&self, request: &MLRequest) -> Result<MLResponse, MLError> {
+        let url = format!("{}/api/v1/analyze", self.base_url);
+        self.send(self.http.post(&url).json(request)).await
+    }
+
+    /// Анализирует несколько транзакций одним запросом (`POST /api/v1/batch_analyze`)
+    ///
+    /// # Arguments
+    /// * `requests` - Признаки транзакций
+    ///
+    /// # Returns
+    /// `Result<Vec<MLResponse>, MLError>` - предсказания в порядке запросов
+    pub async fn batch_analyze(&self, requests: &[MLRequest]) -> Result<Vec<MLResponse>, MLError> {
+        let url = format!("{}/api/v1/batch_analyze", self.base_url);
+        let batch = BatchRequest {
+            transactions: requests.to_vec(),
+        };
+        let resp: BatchResponse = self.send(self.http.post(&url).json(&batch)).await?;
+        Ok(resp.results)
+    }
+
+    /// Проверяет доступность сервиса (`GET /api/v1/health`)
+    pub async fn health(&self) -> Result<HealthResponse, MLError> {
+        let url = format!("{}/api/v1/health", self.base_url);
+        self.send(self.http.get(&url)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::transaction::transaction_info::TransactionFeatures;
+    use bitcoin::Amount;
+    use crate::services::ml::models::PredictionClass;
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use serde_json::json;
+    use std::net::TcpListener;
+
+    const TX_ID: &str = "d6176384de4c0b98702eccb97f3ad6670bc8410d9da715fe5b49462d3e603993";
+
+    fn features() -> TransactionFeatures {
+        TransactionFeatures::new(
+            2,
+            3,
+            Amount::from_sat(150_000_000),
+            Amount::from_sat(149_000_000),
+            Amount::from_sat(1_000_000),
+        )
+    }
+
+    fn verdict(tx_id: &str) -> serde_json::Value {
+        json!({
+            "success": true,
+            "transaction_id": tx_id,
+            "prediction": {"class": "illicit", "confidence": 0.95, "risk_score": 0.87},
+            "explanation": "mixing detected",
+            "details": {
+                "model_version": "1.0.0",
+                "inference_time_ms": 45.2,
+                "feature_importance": {"transaction_fee": 0.15}
+            }
+        })
+    }
+
+    async fn analyze(body: web::Json<MLRequest>) -> HttpResponse {
+        if body.transaction_id.is_empty() {
+            return HttpResponse::BadRequest().json(json!({
+                "success": false,
+                "error": {"code": "INVALID_INPUT", "message": "Missing required field: transaction_id", "details": {}}
+            }));
+        }
+        HttpResponse::Ok().json(verdict(&body.transaction_id))
+    }
+
+    async fn batch(body: web::Json<BatchRequest>) -> HttpResponse {
+        let results: Vec<_> = body.transactions.iter().map(|r| verdict(&r.transaction_id)).collect();
+        HttpResponse::Ok().json(json!({"success": true, "results": results}))
+    }
+
+    async fn health() -> HttpResponse {
+        HttpResponse::Ok().json(json!({"status": "ok", "model_version": "1.0.0"}))
+    }
+
+    /// Поднимает локальный mock ML сервиса и возвращает его URL
+    fn start_mock_service() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!("http://{}", listener.local_addr().expect("Should have address"));
+        let server = HttpServer::new(|| {
+            App::new()
+                .route("/api/v1/analyze", web::post().to(analyze))
+                .route("/api/v1/batch_analyze", web::post().to(batch))
+                .route("/api/v1/health", web::get().to(health))
+        })
+        .workers(1)
+        .listen(listener)
+        .expect("Should listen")
+        .run();
+        actix_web::rt::spawn(server);
+        url
+    }
+
+    #[test]
+    fn test_minimal_request_serialization() {
+        let request = MLRequest::new(TX_ID, &features());
+        let value = serde_json::to_value(&request).expect("Should serialize");
+
+        assert_eq!(value["transaction_id"], TX_ID);
+        assert_eq!(value["transaction_features"]["n_inputs"], 2);
+        assert_eq!(value["transaction_features"]["transaction_fee"], 0.01);
+        assert_eq!(value["transaction_features"]["avg_input_value"], 0.75);
+        assert!(value.get("extended_features").is_none());
+        assert!(value.get("input_features").is_none());
+    }
+
+    #[actix_web::test]
+    async fn test_analyze_transaction() {
+        let client = MLClient::new(start_mock_service(), Duration::from_secs(5)).expect("Should build");
+
+        let response = client
+            .analyze_transaction(&MLRequest::new(TX_ID, &features()))
+            .await
+            .expect("Should analyze");
+
+        assert!(response.success);
+        assert_eq!(response.transaction_id, TX_ID);
+        assert_eq!(response.prediction.class, PredictionClass::Illicit);
+        assert_eq!(response.details.model_version, "1.0.0");
+        assert_eq!(
+            response.details.feature_importance.and_then(|f| f.get("transaction_fee").copied()),
+            Some(0.15)
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_error_envelope() {
+        let client = MLClient::new(start_mock_service(), Duration::from_secs(5)).expect("Should build");
+
+        let err = client
+            .analyze_transaction(&MLRequest::new("", &features()))
+            .await
+            .expect_err("Should fail");
+
+        match err {
+            MLError::Service { code, .. } => assert_eq!(code, "INVALID_INPUT"),
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_batch_analyze_and_health() {
+        let client = MLClient::new(start_mock_service(), Duration::from_secs(5)).expect("Should build");
+
+        let requests = vec![MLRequest::new("a", &features()), MLRequest::new("b", &features())];
+        let results = client.batch_analyze(&requests).await.expect("Should analyze batch");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].transaction_id, "b");
+
+        let health = client.health().await.expect("Should be healthy");
+        assert_eq!(health.status, "ok");
+    }
+
+    #[actix_web::test]
+    async fn test_unreachable_service() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind");
+        let url = format!("http://{}", listener.local_addr().expect("Should have address"));
+        drop(listener);
+
+        let client = MLClient::new(url, Duration::from_secs(1)).expect("Should build");
+        assert!(matches!(client.health().await, Err(MLError::Http(_))));
+    }
+}
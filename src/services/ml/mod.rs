@@ -0,0 +1,3 @@
+pub mod ml_client;
+pub mod models;
+pub mod errors;
@@ -0,0 +1,207 @@
+//! Модели данных API Python ML сервиса (см. `PYTHON_API_SPEC.md`)
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::application::services::address::address_features::AddressFeatures;
+use crate::application::services::transaction::extended_features::ExtendedFeatures;
+use crate::application::services::transaction::transaction_info::TransactionFeatures;
+
+/// Запрос `POST /api/v1/analyze`
+///
+/// Необязательные части опускаются при сериализации, поэтому запрос
+/// только с `transaction_features` соответствует «минимальному» формату спецификации.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MLRequest {
+    /// ID транзакции
+    pub transaction_id: String,
+    /// Базовые характеристики транзакции
+    pub transaction_features: MLTransactionFeatures,
+    /// Расширенные характеристики транзакции
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extended_features: Option<ExtendedFeatures>,
+    /// Адреса входов и выходов
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<Addresses>,
+    /// Признаки адресов входов
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_features: Vec<MLAddressFeatures>,
+    /// Признаки адресов выходов
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_features: Vec<MLAddressFeatures>,
+}
+
+impl MLRequest {
+    /// Создает минимальный запрос только с базовыми признаками
+    pub fn new(transaction_id: impl Into<String>, transaction_features: &TransactionFeatures) -> Self {
+        Self {
+            transaction_id: transaction_id.into(),
+            transaction_features: transaction_features.into(),
+            extended_features: None,
+            addresses: None,
+            input_features: Vec::new(),
+            output_features: Vec::new(),
+        }
+    }
+
+    /// Добавляет расширенные признаки
+    pub fn with_extended_features(mut self, extended_features: ExtendedFeatures) -> Self {
+        self.extended_features = Some(extended_features);
+        self
+    }
+
+    /// Добавляет признаки адресов; списки адресов заполняются по ним же
+    pub fn with_address_features(mut self, inputs: &[AddressFeatures], outputs: &[AddressFeatures]) -> Self {
+        self.addresses = Some(Addresses {
+            inputs: inputs.iter().map(|f| f.address.clone()).collect(),
+            outputs: outputs.iter().map(|f| f.address.clone()).collect(),
+        });
+        self.input_features = inputs.iter().map(MLAddressFeatures::from).collect();
+        self.output_features = outputs.iter().map(MLAddressFeatures::from).collect();
+        self
+    }
+}
+
+/// Базовые характеристики транзакции в формате ML сервиса
+///
+/// Единственное место, где суммы переводятся из сатоши в BTC (`f64`).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MLTransactionFeatures {
+    pub n_inputs: usize,
+    pub n_outputs: usize,
+    /// Сумма BTC на входах
+    pub input_value_sum: f64,
+    /// Сумма BTC на выходах
+    pub output_value_sum: f64,
+    /// Комиссия транзакции в BTC
+    pub transaction_fee: f64,
+    /// Средний размер входящего UTXO в BTC
+    pub avg_input_value: f64,
+    /// Средний размер создаваемого UTXO в BTC
+    pub avg_output_value: f64,
+}
+
+impl From<&TransactionFeatures> for MLTransactionFeatures {
+    fn from(features: &TransactionFeatures) -> Self {
+        Self {
+            n_inputs: features.n_inputs,
+            n_outputs: features.n_outputs,
+            input_value_sum: features.input_value_sum.to_btc(),
+            output_value_sum: features.output_value_sum.to_btc(),
+            transaction_fee: features.transaction_fee.to_btc(),
+            avg_input_value: features.avg_input_value,
+            avg_output_value: features.avg_output_value,
+        }
+    }
+}
+
+/// Адреса входов и выходов транзакции
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Addresses {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+/// Признаки адреса в формате ML сервиса
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MLAddressFeatures {
+    /// Bitcoin адрес
+    pub address: String,
+    /// Временной шаг и 55 признаков адреса
+    pub features: Vec<f64>,
+}
+
+impl From<&AddressFeatures> for MLAddressFeatures {
+    fn from(features: &AddressFeatures) -> Self {
+        Self {
+            address: features.address.clone(),
+            features: features.to_vector(),
+        }
+    }
+}
+
+/// Класс транзакции по версии модели
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum PredictionClass {
+    Illicit,
+    Licit,
+    Unknown,
+}
+
+/// Предсказание модели
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Prediction {
+    /// Класс транзакции
+    pub class: PredictionClass,
+    /// Уверенность модели (0.0 - 1.0)
+    pub confidence: f64,
+    /// Оценка риска (0.0 - 1.0)
+    pub risk_score: f64,
+}
+
+/// Служебная информация об инференсе
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ResponseDetails {
+    /// Версия модели ML
+    pub model_version: String,
+    /// Время инференса в миллисекундах
+    pub inference_time_ms: f64,
+    /// Важность признаков
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feature_importance: Option<HashMap<String, f64>>,
+}
+
+/// Успешный ответ `POST /api/v1/analyze`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MLResponse {
+    pub success: bool,
+    /// ID транзакции (эхо запроса)
+    pub transaction_id: String,
+    pub prediction: Prediction,
+    /// Текстовое объяснение предсказания
+    pub explanation: String,
+    #[serde(default)]
+    pub details: ResponseDetails,
+}
+
+/// Тело ошибки в конверте `{"success": false, "error": {...}}`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ErrorBody {
+    /// Машиночитаемый код ошибки, например `INVALID_INPUT`
+    pub code: String,
+    pub message: String,
+    #[serde(default)]
+    pub details: serde_json::Value,
+}
+
+/// Ответ с ошибкой
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ErrorResponse {
+    pub success: bool,
+    pub error: ErrorBody,
+}
+
+/// Запрос `POST /api/v1/batch_analyze`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchRequest {
+    pub transactions: Vec<MLRequest>,
+}
+
+/// Ответ `POST /api/v1/batch_analyze`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BatchResponse {
+    pub success: bool,
+    pub results: Vec<MLResponse>,
+}
+
+/// Ответ `GET /api/v1/health`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthResponse {
+    /// Состояние сервиса, например `"ok"`
+    pub status: String,
+    /// Версия модели, если сервис ее сообщает
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_version: Option<String>,
+}
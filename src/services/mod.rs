@@ -1 +1,2 @@
-pub mod explorer;
\ No newline at end of file
+pub mod explorer;
+pub mod ml;
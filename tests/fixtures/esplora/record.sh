@@ -0,0 +1,27 @@
+#!/bin/sh
+# Записывает ответы Esplora для фикстур без изменений и отмечает источник в sources.txt
+#
+#   tests/fixtures/esplora/record.sh [base_url]
+#
+# По умолчанию используется https://mempool.space/api.
+set -eu
+
+BASE_URL="${1:-https://mempool.space/api}"
+DIR="$(cd "$(dirname "$0")" && pwd)"
+
+SEGWIT_TX="b0714235addd08daf83b979aa35cc9ed7558efb8327b86b4d3ccacd8b0482ae1"
+CHANGE_ADDRESS="bc1qll6gq9v38t9nttwek0r5kkn0wmedz3w2gshe0a"
+
+record() {
+    file="$1"
+    url="$BASE_URL$2"
+    curl -sSf "$url" -o "$DIR/$file.tmp"
+    mv "$DIR/$file.tmp" "$DIR/$file"
+    grep -v "^$file " "$DIR/sources.txt" > "$DIR/sources.txt.tmp" 2>/dev/null || true
+    echo "$file $url $(date -u +%Y-%m-%dT%H:%M:%SZ)" >> "$DIR/sources.txt.tmp"
+    sort "$DIR/sources.txt.tmp" > "$DIR/sources.txt"
+    rm "$DIR/sources.txt.tmp"
+}
+
+record outspends.json "/tx/$SEGWIT_TX/outspends"
+record address.json "/address/$CHANGE_ADDRESS"